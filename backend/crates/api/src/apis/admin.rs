@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+use axum::extract::*;
+use axum_extra::extract::{CookieJar, Host};
+use bytes::Bytes;
+use http::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::{models, types::*};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum UploadWorkbookResponse {
+    /// Workbook activated
+    Status200_WorkbookActivated(models::WorkbookReport),
+    /// Missing or unreadable upload
+    Status400_MissingOrUnreadableUpload(models::ErrorResponse),
+    /// Missing or invalid admin key
+    Status401_MissingOrInvalidAdminKey,
+    /// Upload larger than the limit
+    Status413_UploadLargerThanTheLimit(models::ErrorResponse),
+    /// Workbook rejected, the current one stays active
+    Status422_WorkbookRejected(models::WorkbookReport),
+}
+
+/// Admin
+#[async_trait]
+#[allow(clippy::ptr_arg)]
+pub trait Admin<E: std::fmt::Debug + Send + Sync + 'static = ()>: super::ErrorHandler<E> {
+    type Claims;
+
+    /// Upload a new version of the issuer workbook.
+    ///
+    /// UploadWorkbook - POST /admin/workbook
+    async fn upload_workbook(
+        &self,
+
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+        body: Multipart,
+    ) -> Result<UploadWorkbookResponse, E>;
+}
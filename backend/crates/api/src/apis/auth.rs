@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use axum::extract::*;
+use axum_extra::extract::{CookieJar, Host};
+use bytes::Bytes;
+use http::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::{models, types::*};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum CurrentUserResponse {
+    /// The logged in user
+    Status200_TheLoggedInUser(models::CurrentUser),
+    /// Missing or invalid token
+    Status401_MissingOrInvalidToken,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum LoginResponse {
+    /// Logged in
+    Status200_LoggedIn(models::LoginToken),
+    /// Invalid email or password
+    Status401_InvalidEmailOrPassword(models::ErrorResponse),
+    /// Logging in is disabled
+    Status503_LoggingInIsDisabled(models::ErrorResponse),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum RegisterResponse {
+    /// Account created
+    Status200_AccountCreated(models::CurrentUser),
+    /// Invalid registration
+    Status400_InvalidRegistration(models::ErrorResponse),
+    /// Email already registered
+    Status409_EmailAlreadyRegistered(models::ErrorResponse),
+}
+
+/// Auth
+#[async_trait]
+#[allow(clippy::ptr_arg)]
+pub trait Auth<E: std::fmt::Debug + Send + Sync + 'static = ()>: super::ErrorHandler<E> {
+    type Claims;
+
+    /// Returns the user the token belongs to.
+    ///
+    /// CurrentUser - GET /auth/current
+    async fn current_user(
+        &self,
+
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+    ) -> Result<CurrentUserResponse, E>;
+
+    /// Exchange the credentials of a user for a token.
+    ///
+    /// Login - POST /auth/login
+    async fn login(
+        &self,
+
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        body: &models::LoginParams,
+    ) -> Result<LoginResponse, E>;
+
+    /// Create a user account.
+    ///
+    /// Register - POST /auth/register
+    async fn register(
+        &self,
+
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        body: &models::RegisterParams,
+    ) -> Result<RegisterResponse, E>;
+}
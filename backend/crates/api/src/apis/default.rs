@@ -7,24 +7,74 @@ use serde::{Deserialize, Serialize};
 
 use crate::{models, types::*};
 
+#[derive(Debug)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum ExportBondsResponse {
+    /// Values of every bond
+    Status200_ValuesOfEveryBond {
+        body: axum::body::Body,
+        content_type: String,
+        content_disposition: String,
+        e_tag: String,
+        last_modified: chrono::DateTime<chrono::Utc>,
+        cache_control: String,
+    },
+    /// Not modified
+    Status304_NotModified {
+        e_tag: String,
+        cache_control: String,
+    },
+    /// Invalid layout or format
+    Status400_InvalidLayoutOrFormat(models::ErrorResponse),
+    /// No dataset version published as of the given date
+    Status404_NoDatasetVersionPublishedAsOfTheGivenDate(models::ErrorResponse),
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[must_use]
 #[allow(clippy::large_enum_variant)]
 pub enum GetBondResponse {
     /// A single bond object
-    Status200_ASingleBondObject(models::GetBond200Response),
+    Status200_ASingleBondObject {
+        body: models::GetBond200Response,
+        e_tag: String,
+        last_modified: chrono::DateTime<chrono::Utc>,
+        cache_control: String,
+    },
+    /// Not modified
+    Status304_NotModified {
+        e_tag: String,
+        cache_control: String,
+    },
     /// Bond not found
-    Status404_BondNotFound(models::GetBond404Response),
+    Status404_BondNotFound(models::ErrorResponse),
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug)]
 #[must_use]
 #[allow(clippy::large_enum_variant)]
 pub enum GetBondCsvResponse {
-    /// Bond data in CSV format
-    Status200_BondDataInCSVFormat(String),
+    /// Bond values in the requested format
+    Status200_BondValuesInTheRequestedFormat {
+        body: axum::body::Body,
+        content_type: String,
+        e_tag: String,
+        last_modified: chrono::DateTime<chrono::Utc>,
+        cache_control: String,
+        vary: String,
+    },
+    /// Not modified
+    Status304_NotModified {
+        e_tag: String,
+        cache_control: String,
+    },
+    /// Invalid format or CSV options
+    Status400_InvalidFormatOrCSVOptions(models::ErrorResponse),
     /// Bond not found
-    Status404_BondNotFound(models::GetBond404Response),
+    Status404_BondNotFound(models::ErrorResponse),
+    /// None of the accepted media types can be produced
+    Status406_NoneOfTheAcceptedMediaTypesCanBeProduced(models::ErrorResponse),
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -32,13 +82,44 @@ pub enum GetBondCsvResponse {
 #[allow(clippy::large_enum_variant)]
 pub enum GetBondsResponse {
     /// A JSON array of bond names
-    Status200_AJSONArrayOfBondNames(Vec<String>),
+    Status200_AJSONArrayOfBondNames {
+        body: Vec<String>,
+        x_total_count: i32,
+    },
+    /// Invalid query parameters
+    Status400_InvalidQueryParameters(models::ErrorResponse),
+    /// No dataset version published as of the given date
+    Status404_NoDatasetVersionPublishedAsOfTheGivenDate(models::ErrorResponse),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum ListVersionsResponse {
+    /// Dataset versions
+    Status200_DatasetVersions(Vec<models::DatasetVersion>),
 }
 
 /// Default
 #[async_trait]
 #[allow(clippy::ptr_arg)]
 pub trait Default<E: std::fmt::Debug + Send + Sync + 'static = ()>: super::ErrorHandler<E> {
+    type Claims;
+
+    /// Download the daily values of every bond.
+    ///
+    /// ExportBonds - GET /export
+    async fn export_bonds(
+        &self,
+
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+        header_params: &models::ExportBondsHeaderParams,
+        query_params: &models::ExportBondsQueryParams,
+    ) -> Result<ExportBondsResponse, E>;
+
     /// Returns a single bond by ID..
     ///
     /// GetBond - GET /bonds/{id}
@@ -48,10 +129,12 @@ pub trait Default<E: std::fmt::Debug + Send + Sync + 'static = ()>: super::Error
         method: &Method,
         host: &Host,
         cookies: &CookieJar,
+        header_params: &models::GetBondHeaderParams,
         path_params: &models::GetBondPathParams,
+        query_params: &models::GetBondQueryParams,
     ) -> Result<GetBondResponse, E>;
 
-    /// Download bond data in CSV format.
+    /// Download the daily values of a bond.
     ///
     /// GetBondCsv - GET /bonds/{id}/csv
     async fn get_bond_csv(
@@ -60,7 +143,9 @@ pub trait Default<E: std::fmt::Debug + Send + Sync + 'static = ()>: super::Error
         method: &Method,
         host: &Host,
         cookies: &CookieJar,
+        header_params: &models::GetBondCsvHeaderParams,
         path_params: &models::GetBondCsvPathParams,
+        query_params: &models::GetBondCsvQueryParams,
     ) -> Result<GetBondCsvResponse, E>;
 
     /// Returns a list of bonds..
@@ -72,5 +157,18 @@ pub trait Default<E: std::fmt::Debug + Send + Sync + 'static = ()>: super::Error
         method: &Method,
         host: &Host,
         cookies: &CookieJar,
+        query_params: &models::GetBondsQueryParams,
     ) -> Result<GetBondsResponse, E>;
+
+    /// Returns every published dataset version, oldest first.
+    ///
+    /// ListVersions - GET /versions
+    async fn list_versions(
+        &self,
+
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+    ) -> Result<ListVersionsResponse, E>;
 }
@@ -1,4 +1,41 @@
+pub mod admin;
+pub mod auth;
 pub mod default;
+pub mod portfolios;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BasicAuthKind {
+    Basic,
+    Bearer,
+}
+
+/// API Key Authentication - Header.
+#[async_trait::async_trait]
+pub trait ApiKeyAuthHeader {
+    type Claims;
+
+    /// Extracting Claims from Header. Return None if the Claims are invalid.
+    async fn extract_claims_from_header(
+        &self,
+        headers: &axum::http::header::HeaderMap,
+        key: &str,
+    ) -> Option<Self::Claims>;
+}
+
+/// API Key Authentication - Authentication Header.
+/// For `Basic token` and `Bearer token`
+#[async_trait::async_trait]
+pub trait ApiAuthBasic {
+    type Claims;
+
+    /// Extracting Claims from Header. Return None if the Claims are invalid.
+    async fn extract_claims_from_auth_header(
+        &self,
+        kind: BasicAuthKind,
+        headers: &axum::http::header::HeaderMap,
+        key: &str,
+    ) -> Option<Self::Claims>;
+}
 
 // Error handler for unhandled errors.
 #[async_trait::async_trait]
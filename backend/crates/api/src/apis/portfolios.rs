@@ -0,0 +1,168 @@
+use async_trait::async_trait;
+use axum::extract::*;
+use axum_extra::extract::{CookieJar, Host};
+use bytes::Bytes;
+use http::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::{models, types::*};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum CreatePortfolioResponse {
+    /// Portfolio created
+    Status201_PortfolioCreated(models::Portfolio),
+    /// Invalid portfolio
+    Status400_InvalidPortfolio(models::ErrorResponse),
+    /// Missing or invalid token
+    Status401_MissingOrInvalidToken,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum DeletePortfolioResponse {
+    /// Portfolio deleted
+    Status204_PortfolioDeleted,
+    /// Missing or invalid token
+    Status401_MissingOrInvalidToken,
+    /// Portfolio not found
+    Status404_PortfolioNotFound(models::ErrorResponse),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum GetPortfolioResponse {
+    /// A single portfolio
+    Status200_ASinglePortfolio(models::Portfolio),
+    /// Missing or invalid token
+    Status401_MissingOrInvalidToken,
+    /// Portfolio not found
+    Status404_PortfolioNotFound(models::ErrorResponse),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum ListPortfoliosResponse {
+    /// Portfolios of the user
+    Status200_PortfoliosOfTheUser(Vec<models::Portfolio>),
+    /// Missing or invalid token
+    Status401_MissingOrInvalidToken,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum UpdatePortfolioResponse {
+    /// Portfolio updated
+    Status200_PortfolioUpdated(models::Portfolio),
+    /// Invalid portfolio
+    Status400_InvalidPortfolio(models::ErrorResponse),
+    /// Missing or invalid token
+    Status401_MissingOrInvalidToken,
+    /// Portfolio not found
+    Status404_PortfolioNotFound(models::ErrorResponse),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum ValuePortfolioResponse {
+    /// Value of the portfolio
+    Status200_ValueOfThePortfolio(models::PortfolioValuation),
+    /// Missing or invalid token
+    Status401_MissingOrInvalidToken,
+    /// Portfolio not found
+    Status404_PortfolioNotFound(models::ErrorResponse),
+}
+
+/// Portfolios
+#[async_trait]
+#[allow(clippy::ptr_arg)]
+pub trait Portfolios<E: std::fmt::Debug + Send + Sync + 'static = ()>:
+    super::ErrorHandler<E>
+{
+    type Claims;
+
+    /// Save a new portfolio.
+    ///
+    /// CreatePortfolio - POST /portfolios
+    async fn create_portfolio(
+        &self,
+
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+        body: &models::PortfolioParams,
+    ) -> Result<CreatePortfolioResponse, E>;
+
+    /// Delete a portfolio.
+    ///
+    /// DeletePortfolio - DELETE /portfolios/{id}
+    async fn delete_portfolio(
+        &self,
+
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+        path_params: &models::DeletePortfolioPathParams,
+    ) -> Result<DeletePortfolioResponse, E>;
+
+    /// Returns a single portfolio of the user.
+    ///
+    /// GetPortfolio - GET /portfolios/{id}
+    async fn get_portfolio(
+        &self,
+
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+        path_params: &models::GetPortfolioPathParams,
+    ) -> Result<GetPortfolioResponse, E>;
+
+    /// Returns the portfolios of the user.
+    ///
+    /// ListPortfolios - GET /portfolios
+    async fn list_portfolios(
+        &self,
+
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+    ) -> Result<ListPortfoliosResponse, E>;
+
+    /// Replace the name and lots of a portfolio.
+    ///
+    /// UpdatePortfolio - PUT /portfolios/{id}
+    async fn update_portfolio(
+        &self,
+
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+        path_params: &models::UpdatePortfolioPathParams,
+        body: &models::PortfolioParams,
+    ) -> Result<UpdatePortfolioResponse, E>;
+
+    /// Values every lot of a portfolio with the bond series.
+    ///
+    /// ValuePortfolio - GET /portfolios/{id}/value
+    async fn value_portfolio(
+        &self,
+
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+        path_params: &models::ValuePortfolioPathParams,
+        query_params: &models::ValuePortfolioQueryParams,
+    ) -> Result<ValuePortfolioResponse, E>;
+}
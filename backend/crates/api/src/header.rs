@@ -156,14 +156,14 @@ impl TryFrom<IntoHeaderValue<bool>> for HeaderValue {
     }
 }
 
-// DateTime
+// DateTime, as an HTTP-date like `Sun, 06 Nov 1994 08:49:37 GMT`
 
 impl TryFrom<HeaderValue> for IntoHeaderValue<DateTime<Utc>> {
     type Error = String;
 
     fn try_from(hdr_value: HeaderValue) -> Result<Self, Self::Error> {
         match hdr_value.to_str() {
-            Ok(hdr_value) => match DateTime::parse_from_rfc3339(hdr_value) {
+            Ok(hdr_value) => match DateTime::parse_from_rfc2822(hdr_value) {
                 Ok(date) => Ok(IntoHeaderValue(date.with_timezone(&Utc))),
                 Err(e) => Err(format!(r#"Unable to parse: {hdr_value} as date - {e}"#)),
             },
@@ -178,7 +178,13 @@ impl TryFrom<IntoHeaderValue<DateTime<Utc>>> for HeaderValue {
     type Error = String;
 
     fn try_from(hdr_value: IntoHeaderValue<DateTime<Utc>>) -> Result<Self, Self::Error> {
-        match HeaderValue::from_str(hdr_value.0.to_rfc3339().as_str()) {
+        match HeaderValue::from_str(
+            hdr_value
+                .0
+                .format("%a, %d %b %Y %H:%M:%S GMT")
+                .to_string()
+                .as_str(),
+        ) {
             Ok(hdr_value) => Ok(hdr_value),
             Err(e) => Err(format!(
                 r#"Unable to convert {hdr_value:?} to a header: {e}"#
@@ -61,6 +61,43 @@ pub fn check_xss_map<T>(
     }
 }
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct ExportBondsHeaderParams {
+    /// ETags of the copies the client has, answered with 304 when one of them is current
+    pub if_none_match: Option<String>,
+    /// Answered with 304 when the data did not change since, ignored together with If-None-Match
+    pub if_modified_since: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct ExportBondsQueryParams {
+    /// Layout of the export (long or zip), `long` by default
+    #[serde(rename = "layout")]
+    #[validate(custom(function = "check_xss_string"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layout: Option<String>,
+    /// Format of the values (csv, json, ndjson, parquet, arrow, or xlsx with the zip layout), `csv` by default
+    #[serde(rename = "format")]
+    #[validate(custom(function = "check_xss_string"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Return the data as it was published on the given date instead of the current data
+    #[serde(rename = "as_of")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<chrono::naive::NaiveDate>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct GetBondHeaderParams {
+    /// ETags of the copies the client has, answered with 304 when one of them is current
+    pub if_none_match: Option<String>,
+    /// Answered with 304 when the data did not change since, ignored together with If-None-Match
+    pub if_modified_since: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
 pub struct GetBondPathParams {
@@ -68,42 +105,397 @@ pub struct GetBondPathParams {
     pub id: String,
 }
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct GetBondQueryParams {
+    /// Return the data as it was published on the given date instead of the current data
+    #[serde(rename = "as_of")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<chrono::naive::NaiveDate>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct GetBondCsvHeaderParams {
+    /// Media types the client accepts, used when the format parameter is omitted
+    pub accept: Option<String>,
+    /// ETags of the copies the client has, answered with 304 when one of them is current
+    pub if_none_match: Option<String>,
+    /// Answered with 304 when the data did not change since, ignored together with If-None-Match
+    pub if_modified_since: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
 pub struct GetBondCsvPathParams {
-    /// The ID of the bond to download as CSV
+    /// The ID of the bond to download
     pub id: String,
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
-pub struct GetBond200Response {
-    /// The bond ID
-    #[serde(rename = "id")]
+pub struct GetBondCsvQueryParams {
+    /// Return the data as it was published on the given date instead of the current data
+    #[serde(rename = "as_of")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<chrono::naive::NaiveDate>,
+    /// Format of the values (csv, json, ndjson, parquet, arrow or xlsx)
+    #[serde(rename = "format")]
     #[validate(custom(function = "check_xss_string"))]
-    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Character separating the CSV fields, `,` by default
+    #[serde(rename = "delimiter")]
+    #[validate(custom(function = "check_xss_string"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delimiter: Option<String>,
+    /// Decimal separator of the CSV numbers, `.` or `,`, `.` by default
+    #[serde(rename = "decimal_separator")]
+    #[validate(custom(function = "check_xss_string"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decimal_separator: Option<String>,
+    /// strftime format of the CSV dates, `%Y-%m-%d` by default
+    #[serde(rename = "date_format")]
+    #[validate(custom(function = "check_xss_string"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
+    /// Comma separated CSV columns (date, value, period or accrued_interest), `date,value` by default
+    #[serde(rename = "columns")]
+    #[validate(custom(function = "check_xss_string"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub columns: Option<String>,
+    /// Comma separated names of the CSV columns in the header row, the column names by default
+    #[serde(rename = "header")]
+    #[validate(custom(function = "check_xss_string"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct GetBondsQueryParams {
+    /// Only return bonds of the given type (OTS, ROR, DOR, TOS, COI, EDO, ROS, ROD or DOS)
+    #[serde(rename = "type")]
+    #[validate(custom(function = "check_xss_string"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+    /// Only return bonds that are on sale on the given date
+    #[serde(rename = "on_sale")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_sale: Option<chrono::naive::NaiveDate>,
+    /// Only return bonds that were on sale at any point of the given month (YYYY-MM)
+    #[serde(rename = "sale_month")]
+    #[validate(custom(function = "check_xss_string"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sale_month: Option<String>,
+    /// Only return bonds maturing on or after the given date
+    #[serde(rename = "maturity_from")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maturity_from: Option<chrono::naive::NaiveDate>,
+    /// Only return bonds maturing on or before the given date
+    #[serde(rename = "maturity_to")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maturity_to: Option<chrono::naive::NaiveDate>,
+    /// Page number, starting from 1
+    #[serde(rename = "page")]
+    #[validate(range(min = 1u32))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    /// Number of bonds per page. All matching bonds are returned when omitted
+    #[serde(rename = "per_page")]
+    #[validate(range(min = 1u16, max = 1000u16))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_page: Option<u16>,
+    /// Return the data as it was published on the given date instead of the current data
+    #[serde(rename = "as_of")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<chrono::naive::NaiveDate>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct DeletePortfolioPathParams {
+    /// The ID of the portfolio
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct GetPortfolioPathParams {
+    /// The ID of the portfolio
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct UpdatePortfolioPathParams {
+    /// The ID of the portfolio
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct ValuePortfolioPathParams {
+    /// The ID of the portfolio
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct ValuePortfolioQueryParams {
+    /// Day to value the portfolio on, today when omitted
+    #[serde(rename = "date")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<chrono::naive::NaiveDate>,
+}
+
+/// Name of the configured source that supplied each field of a bond
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct BondProvenance {
+    /// Source that introduced the series
+    #[serde(rename = "series")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub series: String,
+
+    #[serde(rename = "initial_date")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub initial_date: String,
+
+    #[serde(rename = "sale_end")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub sale_end: String,
+
+    #[serde(rename = "nominal")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub nominal: String,
+
+    #[serde(rename = "rates")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub rates: String,
+}
+
+impl BondProvenance {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(
+        series: String,
+        initial_date: String,
+        sale_end: String,
+        nominal: String,
+        rates: String,
+    ) -> BondProvenance {
+        BondProvenance {
+            series,
+            initial_date,
+            sale_end,
+            nominal,
+            rates,
+        }
+    }
+}
+
+/// Converts the BondProvenance value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for BondProvenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            Some("series".to_string()),
+            Some(self.series.to_string()),
+            Some("initial_date".to_string()),
+            Some(self.initial_date.to_string()),
+            Some("sale_end".to_string()),
+            Some(self.sale_end.to_string()),
+            Some("nominal".to_string()),
+            Some(self.nominal.to_string()),
+            Some("rates".to_string()),
+            Some(self.rates.to_string()),
+        ];
+
+        write!(
+            f,
+            "{}",
+            params.into_iter().flatten().collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a BondProvenance value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for BondProvenance {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub series: Vec<String>,
+            pub initial_date: Vec<String>,
+            pub sale_end: Vec<String>,
+            pub nominal: Vec<String>,
+            pub rates: Vec<String>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err(
+                        "Missing value while parsing BondProvenance".to_string(),
+                    )
+                }
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "series" => intermediate_rep.series.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "initial_date" => intermediate_rep.initial_date.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "sale_end" => intermediate_rep.sale_end.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "nominal" => intermediate_rep.nominal.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "rates" => intermediate_rep.rates.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing BondProvenance".to_string(),
+                        )
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(BondProvenance {
+            series: intermediate_rep
+                .series
+                .into_iter()
+                .next()
+                .ok_or_else(|| "series missing in BondProvenance".to_string())?,
+            initial_date: intermediate_rep
+                .initial_date
+                .into_iter()
+                .next()
+                .ok_or_else(|| "initial_date missing in BondProvenance".to_string())?,
+            sale_end: intermediate_rep
+                .sale_end
+                .into_iter()
+                .next()
+                .ok_or_else(|| "sale_end missing in BondProvenance".to_string())?,
+            nominal: intermediate_rep
+                .nominal
+                .into_iter()
+                .next()
+                .ok_or_else(|| "nominal missing in BondProvenance".to_string())?,
+            rates: intermediate_rep
+                .rates
+                .into_iter()
+                .next()
+                .ok_or_else(|| "rates missing in BondProvenance".to_string())?,
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<BondProvenance> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<BondProvenance>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<BondProvenance>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Invalid header value for BondProvenance - value: {hdr_value} is invalid {e}"#
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<BondProvenance> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <BondProvenance as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        r#"Unable to convert header value '{value}' into BondProvenance - {err}"#
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Unable to convert header: {hdr_value:?} to string: {e}"#
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct CurrentUser {
+    /// Public ID of the user
+    #[serde(rename = "pid")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub pid: String,
+
+    #[serde(rename = "email")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub email: String,
 
-    /// The bond name
     #[serde(rename = "name")]
     #[validate(custom(function = "check_xss_string"))]
     pub name: String,
 }
 
-impl GetBond200Response {
+impl CurrentUser {
     #[allow(clippy::new_without_default, clippy::too_many_arguments)]
-    pub fn new(id: String, name: String) -> GetBond200Response {
-        GetBond200Response { id, name }
+    pub fn new(pid: String, email: String, name: String) -> CurrentUser {
+        CurrentUser { pid, email, name }
     }
 }
 
-/// Converts the GetBond200Response value to the Query Parameters representation (style=form, explode=false)
+/// Converts the CurrentUser value to the Query Parameters representation (style=form, explode=false)
 /// specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde serializer
-impl std::fmt::Display for GetBond200Response {
+impl std::fmt::Display for CurrentUser {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let params: Vec<Option<String>> = vec![
-            Some("id".to_string()),
-            Some(self.id.to_string()),
+            Some("pid".to_string()),
+            Some(self.pid.to_string()),
+            Some("email".to_string()),
+            Some(self.email.to_string()),
             Some("name".to_string()),
             Some(self.name.to_string()),
         ];
@@ -116,10 +508,10 @@ impl std::fmt::Display for GetBond200Response {
     }
 }
 
-/// Converts Query Parameters representation (style=form, explode=false) to a GetBond200Response value
+/// Converts Query Parameters representation (style=form, explode=false) to a CurrentUser value
 /// as specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde deserializer
-impl std::str::FromStr for GetBond200Response {
+impl std::str::FromStr for CurrentUser {
     type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
@@ -127,7 +519,8 @@ impl std::str::FromStr for GetBond200Response {
         #[derive(Default)]
         #[allow(dead_code)]
         struct IntermediateRep {
-            pub id: Vec<String>,
+            pub pid: Vec<String>,
+            pub email: Vec<String>,
             pub name: Vec<String>,
         }
 
@@ -142,7 +535,7 @@ impl std::str::FromStr for GetBond200Response {
                 Some(x) => x,
                 None => {
                     return std::result::Result::Err(
-                        "Missing value while parsing GetBond200Response".to_string(),
+                        "Missing value while parsing CurrentUser".to_string(),
                     )
                 }
             };
@@ -151,7 +544,11 @@ impl std::str::FromStr for GetBond200Response {
                 #[allow(clippy::match_single_binding)]
                 match key {
                     #[allow(clippy::redundant_clone)]
-                    "id" => intermediate_rep.id.push(
+                    "pid" => intermediate_rep.pid.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "email" => intermediate_rep.email.push(
                         <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
                     ),
                     #[allow(clippy::redundant_clone)]
@@ -160,7 +557,7 @@ impl std::str::FromStr for GetBond200Response {
                     ),
                     _ => {
                         return std::result::Result::Err(
-                            "Unexpected key while parsing GetBond200Response".to_string(),
+                            "Unexpected key while parsing CurrentUser".to_string(),
                         )
                     }
                 }
@@ -171,53 +568,58 @@ impl std::str::FromStr for GetBond200Response {
         }
 
         // Use the intermediate representation to return the struct
-        std::result::Result::Ok(GetBond200Response {
-            id: intermediate_rep
-                .id
+        std::result::Result::Ok(CurrentUser {
+            pid: intermediate_rep
+                .pid
                 .into_iter()
                 .next()
-                .ok_or_else(|| "id missing in GetBond200Response".to_string())?,
+                .ok_or_else(|| "pid missing in CurrentUser".to_string())?,
+            email: intermediate_rep
+                .email
+                .into_iter()
+                .next()
+                .ok_or_else(|| "email missing in CurrentUser".to_string())?,
             name: intermediate_rep
                 .name
                 .into_iter()
                 .next()
-                .ok_or_else(|| "name missing in GetBond200Response".to_string())?,
+                .ok_or_else(|| "name missing in CurrentUser".to_string())?,
         })
     }
 }
 
-// Methods for converting between header::IntoHeaderValue<GetBond200Response> and HeaderValue
+// Methods for converting between header::IntoHeaderValue<CurrentUser> and HeaderValue
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<header::IntoHeaderValue<GetBond200Response>> for HeaderValue {
+impl std::convert::TryFrom<header::IntoHeaderValue<CurrentUser>> for HeaderValue {
     type Error = String;
 
     fn try_from(
-        hdr_value: header::IntoHeaderValue<GetBond200Response>,
+        hdr_value: header::IntoHeaderValue<CurrentUser>,
     ) -> std::result::Result<Self, Self::Error> {
         let hdr_value = hdr_value.to_string();
         match HeaderValue::from_str(&hdr_value) {
             std::result::Result::Ok(value) => std::result::Result::Ok(value),
             std::result::Result::Err(e) => std::result::Result::Err(format!(
-                r#"Invalid header value for GetBond200Response - value: {hdr_value} is invalid {e}"#
+                r#"Invalid header value for CurrentUser - value: {hdr_value} is invalid {e}"#
             )),
         }
     }
 }
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<GetBond200Response> {
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<CurrentUser> {
     type Error = String;
 
     fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
         match hdr_value.to_str() {
             std::result::Result::Ok(value) => {
-                match <GetBond200Response as std::str::FromStr>::from_str(value) {
+                match <CurrentUser as std::str::FromStr>::from_str(value) {
                     std::result::Result::Ok(value) => {
                         std::result::Result::Ok(header::IntoHeaderValue(value))
                     }
                     std::result::Result::Err(err) => std::result::Result::Err(format!(
-                        r#"Unable to convert header value '{value}' into GetBond200Response - {err}"#
+                        r#"Unable to convert header value '{value}' into CurrentUser - {err}"#
                     )),
                 }
             }
@@ -230,27 +632,34 @@ impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<GetBond200Re
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
-pub struct GetBond404Response {
-    /// Error message
-    #[serde(rename = "error")]
+pub struct DatasetVersion {
+    /// SHA-256 of the bonds merged from the workbook and the sources
+    #[serde(rename = "hash")]
     #[validate(custom(function = "check_xss_string"))]
-    pub error: String,
+    pub hash: String,
+
+    /// When the workbook was ingested
+    #[serde(rename = "ingested_at")]
+    pub ingested_at: chrono::DateTime<chrono::Utc>,
 }
 
-impl GetBond404Response {
+impl DatasetVersion {
     #[allow(clippy::new_without_default, clippy::too_many_arguments)]
-    pub fn new(error: String) -> GetBond404Response {
-        GetBond404Response { error }
+    pub fn new(hash: String, ingested_at: chrono::DateTime<chrono::Utc>) -> DatasetVersion {
+        DatasetVersion { hash, ingested_at }
     }
 }
 
-/// Converts the GetBond404Response value to the Query Parameters representation (style=form, explode=false)
+/// Converts the DatasetVersion value to the Query Parameters representation (style=form, explode=false)
 /// specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde serializer
-impl std::fmt::Display for GetBond404Response {
+impl std::fmt::Display for DatasetVersion {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let params: Vec<Option<String>> =
-            vec![Some("error".to_string()), Some(self.error.to_string())];
+        let params: Vec<Option<String>> = vec![
+            Some("hash".to_string()),
+            Some(self.hash.to_string()),
+            // Skipping non-primitive type ingested_at in query parameter serialization
+        ];
 
         write!(
             f,
@@ -260,10 +669,10 @@ impl std::fmt::Display for GetBond404Response {
     }
 }
 
-/// Converts Query Parameters representation (style=form, explode=false) to a GetBond404Response value
+/// Converts Query Parameters representation (style=form, explode=false) to a DatasetVersion value
 /// as specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde deserializer
-impl std::str::FromStr for GetBond404Response {
+impl std::str::FromStr for DatasetVersion {
     type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
@@ -271,7 +680,8 @@ impl std::str::FromStr for GetBond404Response {
         #[derive(Default)]
         #[allow(dead_code)]
         struct IntermediateRep {
-            pub error: Vec<String>,
+            pub hash: Vec<String>,
+            pub ingested_at: Vec<chrono::DateTime<chrono::Utc>>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -285,7 +695,7 @@ impl std::str::FromStr for GetBond404Response {
                 Some(x) => x,
                 None => {
                     return std::result::Result::Err(
-                        "Missing value while parsing GetBond404Response".to_string(),
+                        "Missing value while parsing DatasetVersion".to_string(),
                     )
                 }
             };
@@ -294,12 +704,17 @@ impl std::str::FromStr for GetBond404Response {
                 #[allow(clippy::match_single_binding)]
                 match key {
                     #[allow(clippy::redundant_clone)]
-                    "error" => intermediate_rep.error.push(
+                    "hash" => intermediate_rep.hash.push(
                         <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
                     ),
+                    #[allow(clippy::redundant_clone)]
+                    "ingested_at" => intermediate_rep.ingested_at.push(
+                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
                     _ => {
                         return std::result::Result::Err(
-                            "Unexpected key while parsing GetBond404Response".to_string(),
+                            "Unexpected key while parsing DatasetVersion".to_string(),
                         )
                     }
                 }
@@ -310,48 +725,2342 @@ impl std::str::FromStr for GetBond404Response {
         }
 
         // Use the intermediate representation to return the struct
-        std::result::Result::Ok(GetBond404Response {
-            error: intermediate_rep
-                .error
+        std::result::Result::Ok(DatasetVersion {
+            hash: intermediate_rep
+                .hash
+                .into_iter()
+                .next()
+                .ok_or_else(|| "hash missing in DatasetVersion".to_string())?,
+            ingested_at: intermediate_rep
+                .ingested_at
+                .into_iter()
+                .next()
+                .ok_or_else(|| "ingested_at missing in DatasetVersion".to_string())?,
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<DatasetVersion> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<DatasetVersion>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<DatasetVersion>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Invalid header value for DatasetVersion - value: {hdr_value} is invalid {e}"#
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<DatasetVersion> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <DatasetVersion as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        r#"Unable to convert header value '{value}' into DatasetVersion - {err}"#
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Unable to convert header: {hdr_value:?} to string: {e}"#
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct ErrorResponse {
+    /// Error message
+    #[serde(rename = "error")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub error: String,
+}
+
+impl ErrorResponse {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(error: String) -> ErrorResponse {
+        ErrorResponse { error }
+    }
+}
+
+/// Converts the ErrorResponse value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for ErrorResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> =
+            vec![Some("error".to_string()), Some(self.error.to_string())];
+
+        write!(
+            f,
+            "{}",
+            params.into_iter().flatten().collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a ErrorResponse value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for ErrorResponse {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub error: Vec<String>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err(
+                        "Missing value while parsing ErrorResponse".to_string(),
+                    )
+                }
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "error" => intermediate_rep.error.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing ErrorResponse".to_string(),
+                        )
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(ErrorResponse {
+            error: intermediate_rep
+                .error
+                .into_iter()
+                .next()
+                .ok_or_else(|| "error missing in ErrorResponse".to_string())?,
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<ErrorResponse> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<ErrorResponse>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<ErrorResponse>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Invalid header value for ErrorResponse - value: {hdr_value} is invalid {e}"#
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<ErrorResponse> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <ErrorResponse as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        r#"Unable to convert header value '{value}' into ErrorResponse - {err}"#
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Unable to convert header: {hdr_value:?} to string: {e}"#
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct GetBond200Response {
+    /// The bond ID
+    #[serde(rename = "id")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub id: String,
+
+    /// The bond name, which is the name of its series
+    #[serde(rename = "name")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub name: String,
+
+    /// First day of sale
+    #[serde(rename = "initial_date")]
+    pub initial_date: chrono::naive::NaiveDate,
+
+    /// Last day of sale
+    #[serde(rename = "sale_end")]
+    pub sale_end: chrono::naive::NaiveDate,
+
+    /// Day the bond is bought out
+    #[serde(rename = "buyout_date")]
+    pub buyout_date: chrono::naive::NaiveDate,
+
+    /// Price of a single bond on the first day of sale, in PLN
+    #[serde(rename = "nominal")]
+    pub nominal: f64,
+
+    /// Interest rate for every year of the term, as a fraction
+    #[serde(rename = "rates")]
+    pub rates: Vec<f64>,
+
+    #[serde(rename = "provenance")]
+    #[validate(nested)]
+    pub provenance: models::BondProvenance,
+}
+
+impl GetBond200Response {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(
+        id: String,
+        name: String,
+        initial_date: chrono::naive::NaiveDate,
+        sale_end: chrono::naive::NaiveDate,
+        buyout_date: chrono::naive::NaiveDate,
+        nominal: f64,
+        rates: Vec<f64>,
+        provenance: models::BondProvenance,
+    ) -> GetBond200Response {
+        GetBond200Response {
+            id,
+            name,
+            initial_date,
+            sale_end,
+            buyout_date,
+            nominal,
+            rates,
+            provenance,
+        }
+    }
+}
+
+/// Converts the GetBond200Response value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for GetBond200Response {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            Some("id".to_string()),
+            Some(self.id.to_string()),
+            Some("name".to_string()),
+            Some(self.name.to_string()),
+            // Skipping non-primitive type initial_date in query parameter serialization
+            // Skipping non-primitive type sale_end in query parameter serialization
+            // Skipping non-primitive type buyout_date in query parameter serialization
+            Some("nominal".to_string()),
+            Some(self.nominal.to_string()),
+            Some("rates".to_string()),
+            Some(
+                self.rates
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+            // Skipping non-primitive type provenance in query parameter serialization
+        ];
+
+        write!(
+            f,
+            "{}",
+            params.into_iter().flatten().collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a GetBond200Response value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for GetBond200Response {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub id: Vec<String>,
+            pub name: Vec<String>,
+            pub initial_date: Vec<chrono::naive::NaiveDate>,
+            pub sale_end: Vec<chrono::naive::NaiveDate>,
+            pub buyout_date: Vec<chrono::naive::NaiveDate>,
+            pub nominal: Vec<f64>,
+            pub rates: Vec<Vec<f64>>,
+            pub provenance: Vec<models::BondProvenance>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err(
+                        "Missing value while parsing GetBond200Response".to_string(),
+                    )
+                }
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "id" => intermediate_rep.id.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "name" => intermediate_rep.name.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "initial_date" => intermediate_rep.initial_date.push(
+                        <chrono::naive::NaiveDate as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "sale_end" => intermediate_rep.sale_end.push(
+                        <chrono::naive::NaiveDate as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "buyout_date" => intermediate_rep.buyout_date.push(
+                        <chrono::naive::NaiveDate as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "nominal" => intermediate_rep.nominal.push(
+                        <f64 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    "rates" => return std::result::Result::Err(
+                        "Parsing a container in this style is not supported in GetBond200Response"
+                            .to_string(),
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "provenance" => intermediate_rep.provenance.push(
+                        <models::BondProvenance as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing GetBond200Response".to_string(),
+                        )
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(GetBond200Response {
+            id: intermediate_rep
+                .id
+                .into_iter()
+                .next()
+                .ok_or_else(|| "id missing in GetBond200Response".to_string())?,
+            name: intermediate_rep
+                .name
+                .into_iter()
+                .next()
+                .ok_or_else(|| "name missing in GetBond200Response".to_string())?,
+            initial_date: intermediate_rep
+                .initial_date
+                .into_iter()
+                .next()
+                .ok_or_else(|| "initial_date missing in GetBond200Response".to_string())?,
+            sale_end: intermediate_rep
+                .sale_end
+                .into_iter()
+                .next()
+                .ok_or_else(|| "sale_end missing in GetBond200Response".to_string())?,
+            buyout_date: intermediate_rep
+                .buyout_date
+                .into_iter()
+                .next()
+                .ok_or_else(|| "buyout_date missing in GetBond200Response".to_string())?,
+            nominal: intermediate_rep
+                .nominal
+                .into_iter()
+                .next()
+                .ok_or_else(|| "nominal missing in GetBond200Response".to_string())?,
+            rates: intermediate_rep
+                .rates
+                .into_iter()
+                .next()
+                .ok_or_else(|| "rates missing in GetBond200Response".to_string())?,
+            provenance: intermediate_rep
+                .provenance
+                .into_iter()
+                .next()
+                .ok_or_else(|| "provenance missing in GetBond200Response".to_string())?,
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<GetBond200Response> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<GetBond200Response>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<GetBond200Response>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Invalid header value for GetBond200Response - value: {hdr_value} is invalid {e}"#
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<GetBond200Response> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <GetBond200Response as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        r#"Unable to convert header value '{value}' into GetBond200Response - {err}"#
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Unable to convert header: {hdr_value:?} to string: {e}"#
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct LoginParams {
+    #[serde(rename = "email")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub email: String,
+
+    #[serde(rename = "password")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub password: String,
+}
+
+impl LoginParams {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(email: String, password: String) -> LoginParams {
+        LoginParams { email, password }
+    }
+}
+
+/// Converts the LoginParams value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for LoginParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            Some("email".to_string()),
+            Some(self.email.to_string()),
+            Some("password".to_string()),
+            Some(self.password.to_string()),
+        ];
+
+        write!(
+            f,
+            "{}",
+            params.into_iter().flatten().collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a LoginParams value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for LoginParams {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub email: Vec<String>,
+            pub password: Vec<String>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err(
+                        "Missing value while parsing LoginParams".to_string(),
+                    )
+                }
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "email" => intermediate_rep.email.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "password" => intermediate_rep.password.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing LoginParams".to_string(),
+                        )
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(LoginParams {
+            email: intermediate_rep
+                .email
+                .into_iter()
+                .next()
+                .ok_or_else(|| "email missing in LoginParams".to_string())?,
+            password: intermediate_rep
+                .password
+                .into_iter()
+                .next()
+                .ok_or_else(|| "password missing in LoginParams".to_string())?,
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<LoginParams> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<LoginParams>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<LoginParams>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Invalid header value for LoginParams - value: {hdr_value} is invalid {e}"#
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<LoginParams> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <LoginParams as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        r#"Unable to convert header value '{value}' into LoginParams - {err}"#
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Unable to convert header: {hdr_value:?} to string: {e}"#
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct LoginToken {
+    /// JWT to send as a bearer token
+    #[serde(rename = "token")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub token: String,
+
+    /// Public ID of the user
+    #[serde(rename = "pid")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub pid: String,
+
+    #[serde(rename = "name")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub name: String,
+}
+
+impl LoginToken {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(token: String, pid: String, name: String) -> LoginToken {
+        LoginToken { token, pid, name }
+    }
+}
+
+/// Converts the LoginToken value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for LoginToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            Some("token".to_string()),
+            Some(self.token.to_string()),
+            Some("pid".to_string()),
+            Some(self.pid.to_string()),
+            Some("name".to_string()),
+            Some(self.name.to_string()),
+        ];
+
+        write!(
+            f,
+            "{}",
+            params.into_iter().flatten().collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a LoginToken value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for LoginToken {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub token: Vec<String>,
+            pub pid: Vec<String>,
+            pub name: Vec<String>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err(
+                        "Missing value while parsing LoginToken".to_string(),
+                    )
+                }
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "token" => intermediate_rep.token.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "pid" => intermediate_rep.pid.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "name" => intermediate_rep.name.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing LoginToken".to_string(),
+                        )
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(LoginToken {
+            token: intermediate_rep
+                .token
+                .into_iter()
+                .next()
+                .ok_or_else(|| "token missing in LoginToken".to_string())?,
+            pid: intermediate_rep
+                .pid
+                .into_iter()
+                .next()
+                .ok_or_else(|| "pid missing in LoginToken".to_string())?,
+            name: intermediate_rep
+                .name
+                .into_iter()
+                .next()
+                .ok_or_else(|| "name missing in LoginToken".to_string())?,
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<LoginToken> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<LoginToken>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<LoginToken>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Invalid header value for LoginToken - value: {hdr_value} is invalid {e}"#
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<LoginToken> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <LoginToken as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        r#"Unable to convert header value '{value}' into LoginToken - {err}"#
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Unable to convert header: {hdr_value:?} to string: {e}"#
+            )),
+        }
+    }
+}
+
+/// Bonds of a single series bought on the same day
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct Lot {
+    #[serde(rename = "bond_id")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub bond_id: String,
+
+    #[serde(rename = "quantity")]
+    #[validate(range(min = 1u32))]
+    pub quantity: u32,
+
+    /// Day the bonds were bought, during the sale of the series
+    #[serde(rename = "purchase_date")]
+    pub purchase_date: chrono::naive::NaiveDate,
+}
+
+impl Lot {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(bond_id: String, quantity: u32, purchase_date: chrono::naive::NaiveDate) -> Lot {
+        Lot {
+            bond_id,
+            quantity,
+            purchase_date,
+        }
+    }
+}
+
+/// Converts the Lot value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for Lot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            Some("bond_id".to_string()),
+            Some(self.bond_id.to_string()),
+            Some("quantity".to_string()),
+            Some(self.quantity.to_string()),
+            // Skipping non-primitive type purchase_date in query parameter serialization
+        ];
+
+        write!(
+            f,
+            "{}",
+            params.into_iter().flatten().collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a Lot value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for Lot {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub bond_id: Vec<String>,
+            pub quantity: Vec<u32>,
+            pub purchase_date: Vec<chrono::naive::NaiveDate>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err("Missing value while parsing Lot".to_string())
+                }
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "bond_id" => intermediate_rep.bond_id.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "quantity" => intermediate_rep.quantity.push(
+                        <u32 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "purchase_date" => intermediate_rep.purchase_date.push(
+                        <chrono::naive::NaiveDate as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing Lot".to_string(),
+                        )
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(Lot {
+            bond_id: intermediate_rep
+                .bond_id
+                .into_iter()
+                .next()
+                .ok_or_else(|| "bond_id missing in Lot".to_string())?,
+            quantity: intermediate_rep
+                .quantity
+                .into_iter()
+                .next()
+                .ok_or_else(|| "quantity missing in Lot".to_string())?,
+            purchase_date: intermediate_rep
+                .purchase_date
+                .into_iter()
+                .next()
+                .ok_or_else(|| "purchase_date missing in Lot".to_string())?,
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<Lot> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<Lot>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(hdr_value: header::IntoHeaderValue<Lot>) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Invalid header value for Lot - value: {hdr_value} is invalid {e}"#
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<Lot> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => match <Lot as std::str::FromStr>::from_str(value) {
+                std::result::Result::Ok(value) => {
+                    std::result::Result::Ok(header::IntoHeaderValue(value))
+                }
+                std::result::Result::Err(err) => std::result::Result::Err(format!(
+                    r#"Unable to convert header value '{value}' into Lot - {err}"#
+                )),
+            },
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Unable to convert header: {hdr_value:?} to string: {e}"#
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct LotValuation {
+    #[serde(rename = "bond_id")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub bond_id: String,
+
+    #[serde(rename = "quantity")]
+    #[validate(range(min = 1u32))]
+    pub quantity: u32,
+
+    #[serde(rename = "purchase_date")]
+    pub purchase_date: chrono::naive::NaiveDate,
+
+    /// Value of the whole lot, missing before the purchase or when the rates for the day are not published yet
+    #[serde(rename = "value")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+}
+
+impl LotValuation {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(
+        bond_id: String,
+        quantity: u32,
+        purchase_date: chrono::naive::NaiveDate,
+    ) -> LotValuation {
+        LotValuation {
+            bond_id,
+            quantity,
+            purchase_date,
+            value: None,
+        }
+    }
+}
+
+/// Converts the LotValuation value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for LotValuation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            Some("bond_id".to_string()),
+            Some(self.bond_id.to_string()),
+            Some("quantity".to_string()),
+            Some(self.quantity.to_string()),
+            // Skipping non-primitive type purchase_date in query parameter serialization
+            self.value
+                .as_ref()
+                .map(|value| ["value".to_string(), value.to_string()].join(",")),
+        ];
+
+        write!(
+            f,
+            "{}",
+            params.into_iter().flatten().collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a LotValuation value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for LotValuation {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub bond_id: Vec<String>,
+            pub quantity: Vec<u32>,
+            pub purchase_date: Vec<chrono::naive::NaiveDate>,
+            pub value: Vec<f64>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err(
+                        "Missing value while parsing LotValuation".to_string(),
+                    )
+                }
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "bond_id" => intermediate_rep.bond_id.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "quantity" => intermediate_rep.quantity.push(
+                        <u32 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "purchase_date" => intermediate_rep.purchase_date.push(
+                        <chrono::naive::NaiveDate as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "value" => intermediate_rep.value.push(
+                        <f64 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing LotValuation".to_string(),
+                        )
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(LotValuation {
+            bond_id: intermediate_rep
+                .bond_id
+                .into_iter()
+                .next()
+                .ok_or_else(|| "bond_id missing in LotValuation".to_string())?,
+            quantity: intermediate_rep
+                .quantity
+                .into_iter()
+                .next()
+                .ok_or_else(|| "quantity missing in LotValuation".to_string())?,
+            purchase_date: intermediate_rep
+                .purchase_date
+                .into_iter()
+                .next()
+                .ok_or_else(|| "purchase_date missing in LotValuation".to_string())?,
+            value: intermediate_rep.value.into_iter().next(),
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<LotValuation> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<LotValuation>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<LotValuation>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Invalid header value for LotValuation - value: {hdr_value} is invalid {e}"#
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<LotValuation> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <LotValuation as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        r#"Unable to convert header value '{value}' into LotValuation - {err}"#
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Unable to convert header: {hdr_value:?} to string: {e}"#
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct Portfolio {
+    #[serde(rename = "id")]
+    pub id: i64,
+
+    #[serde(rename = "name")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub name: String,
+
+    #[serde(rename = "lots")]
+    #[validate(nested)]
+    pub lots: Vec<models::Lot>,
+}
+
+impl Portfolio {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(id: i64, name: String, lots: Vec<models::Lot>) -> Portfolio {
+        Portfolio { id, name, lots }
+    }
+}
+
+/// Converts the Portfolio value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for Portfolio {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            Some("id".to_string()),
+            Some(self.id.to_string()),
+            Some("name".to_string()),
+            Some(self.name.to_string()),
+            // Skipping non-primitive type lots in query parameter serialization
+        ];
+
+        write!(
+            f,
+            "{}",
+            params.into_iter().flatten().collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a Portfolio value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for Portfolio {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub id: Vec<i64>,
+            pub name: Vec<String>,
+            pub lots: Vec<Vec<models::Lot>>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err(
+                        "Missing value while parsing Portfolio".to_string(),
+                    )
+                }
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "id" => intermediate_rep.id.push(
+                        <i64 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "name" => intermediate_rep.name.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    "lots" => {
+                        return std::result::Result::Err(
+                            "Parsing a container in this style is not supported in Portfolio"
+                                .to_string(),
+                        )
+                    }
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing Portfolio".to_string(),
+                        )
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(Portfolio {
+            id: intermediate_rep
+                .id
+                .into_iter()
+                .next()
+                .ok_or_else(|| "id missing in Portfolio".to_string())?,
+            name: intermediate_rep
+                .name
+                .into_iter()
+                .next()
+                .ok_or_else(|| "name missing in Portfolio".to_string())?,
+            lots: intermediate_rep
+                .lots
+                .into_iter()
+                .next()
+                .ok_or_else(|| "lots missing in Portfolio".to_string())?,
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<Portfolio> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<Portfolio>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<Portfolio>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Invalid header value for Portfolio - value: {hdr_value} is invalid {e}"#
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<Portfolio> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <Portfolio as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        r#"Unable to convert header value '{value}' into Portfolio - {err}"#
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Unable to convert header: {hdr_value:?} to string: {e}"#
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct PortfolioParams {
+    #[serde(rename = "name")]
+    #[validate(length(min = 1), custom(function = "check_xss_string"))]
+    pub name: String,
+
+    #[serde(rename = "lots")]
+    #[validate(nested)]
+    pub lots: Vec<models::Lot>,
+}
+
+impl PortfolioParams {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(name: String, lots: Vec<models::Lot>) -> PortfolioParams {
+        PortfolioParams { name, lots }
+    }
+}
+
+/// Converts the PortfolioParams value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for PortfolioParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            Some("name".to_string()),
+            Some(self.name.to_string()),
+            // Skipping non-primitive type lots in query parameter serialization
+        ];
+
+        write!(
+            f,
+            "{}",
+            params.into_iter().flatten().collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a PortfolioParams value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for PortfolioParams {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub name: Vec<String>,
+            pub lots: Vec<Vec<models::Lot>>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err(
+                        "Missing value while parsing PortfolioParams".to_string(),
+                    )
+                }
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "name" => intermediate_rep.name.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    "lots" => {
+                        return std::result::Result::Err(
+                            "Parsing a container in this style is not supported in PortfolioParams"
+                                .to_string(),
+                        )
+                    }
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing PortfolioParams".to_string(),
+                        )
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(PortfolioParams {
+            name: intermediate_rep
+                .name
+                .into_iter()
+                .next()
+                .ok_or_else(|| "name missing in PortfolioParams".to_string())?,
+            lots: intermediate_rep
+                .lots
+                .into_iter()
+                .next()
+                .ok_or_else(|| "lots missing in PortfolioParams".to_string())?,
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<PortfolioParams> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<PortfolioParams>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<PortfolioParams>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Invalid header value for PortfolioParams - value: {hdr_value} is invalid {e}"#
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<PortfolioParams> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <PortfolioParams as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        r#"Unable to convert header value '{value}' into PortfolioParams - {err}"#
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Unable to convert header: {hdr_value:?} to string: {e}"#
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct PortfolioValuation {
+    #[serde(rename = "date")]
+    pub date: chrono::naive::NaiveDate,
+
+    /// Sum of the lots with a known value
+    #[serde(rename = "total")]
+    pub total: f64,
+
+    #[serde(rename = "lots")]
+    #[validate(nested)]
+    pub lots: Vec<models::LotValuation>,
+}
+
+impl PortfolioValuation {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(
+        date: chrono::naive::NaiveDate,
+        total: f64,
+        lots: Vec<models::LotValuation>,
+    ) -> PortfolioValuation {
+        PortfolioValuation { date, total, lots }
+    }
+}
+
+/// Converts the PortfolioValuation value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for PortfolioValuation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            // Skipping non-primitive type date in query parameter serialization
+            Some("total".to_string()),
+            Some(self.total.to_string()),
+            // Skipping non-primitive type lots in query parameter serialization
+        ];
+
+        write!(
+            f,
+            "{}",
+            params.into_iter().flatten().collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a PortfolioValuation value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for PortfolioValuation {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub date: Vec<chrono::naive::NaiveDate>,
+            pub total: Vec<f64>,
+            pub lots: Vec<Vec<models::LotValuation>>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err(
+                        "Missing value while parsing PortfolioValuation".to_string(),
+                    )
+                }
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "date" => intermediate_rep.date.push(
+                        <chrono::naive::NaiveDate as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "total" => intermediate_rep.total.push(
+                        <f64 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    "lots" => return std::result::Result::Err(
+                        "Parsing a container in this style is not supported in PortfolioValuation"
+                            .to_string(),
+                    ),
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing PortfolioValuation".to_string(),
+                        )
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(PortfolioValuation {
+            date: intermediate_rep
+                .date
+                .into_iter()
+                .next()
+                .ok_or_else(|| "date missing in PortfolioValuation".to_string())?,
+            total: intermediate_rep
+                .total
+                .into_iter()
+                .next()
+                .ok_or_else(|| "total missing in PortfolioValuation".to_string())?,
+            lots: intermediate_rep
+                .lots
+                .into_iter()
+                .next()
+                .ok_or_else(|| "lots missing in PortfolioValuation".to_string())?,
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<PortfolioValuation> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<PortfolioValuation>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<PortfolioValuation>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Invalid header value for PortfolioValuation - value: {hdr_value} is invalid {e}"#
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<PortfolioValuation> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <PortfolioValuation as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        r#"Unable to convert header value '{value}' into PortfolioValuation - {err}"#
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Unable to convert header: {hdr_value:?} to string: {e}"#
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct RegisterParams {
+    #[serde(rename = "email")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub email: String,
+
+    #[serde(rename = "password")]
+    #[validate(length(min = 8), custom(function = "check_xss_string"))]
+    pub password: String,
+
+    #[serde(rename = "name")]
+    #[validate(length(min = 1), custom(function = "check_xss_string"))]
+    pub name: String,
+}
+
+impl RegisterParams {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(email: String, password: String, name: String) -> RegisterParams {
+        RegisterParams {
+            email,
+            password,
+            name,
+        }
+    }
+}
+
+/// Converts the RegisterParams value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for RegisterParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            Some("email".to_string()),
+            Some(self.email.to_string()),
+            Some("password".to_string()),
+            Some(self.password.to_string()),
+            Some("name".to_string()),
+            Some(self.name.to_string()),
+        ];
+
+        write!(
+            f,
+            "{}",
+            params.into_iter().flatten().collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a RegisterParams value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for RegisterParams {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub email: Vec<String>,
+            pub password: Vec<String>,
+            pub name: Vec<String>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err(
+                        "Missing value while parsing RegisterParams".to_string(),
+                    )
+                }
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "email" => intermediate_rep.email.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "password" => intermediate_rep.password.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "name" => intermediate_rep.name.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing RegisterParams".to_string(),
+                        )
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(RegisterParams {
+            email: intermediate_rep
+                .email
+                .into_iter()
+                .next()
+                .ok_or_else(|| "email missing in RegisterParams".to_string())?,
+            password: intermediate_rep
+                .password
+                .into_iter()
+                .next()
+                .ok_or_else(|| "password missing in RegisterParams".to_string())?,
+            name: intermediate_rep
+                .name
+                .into_iter()
+                .next()
+                .ok_or_else(|| "name missing in RegisterParams".to_string())?,
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<RegisterParams> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<RegisterParams>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<RegisterParams>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Invalid header value for RegisterParams - value: {hdr_value} is invalid {e}"#
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<RegisterParams> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <RegisterParams as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        r#"Unable to convert header value '{value}' into RegisterParams - {err}"#
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Unable to convert header: {hdr_value:?} to string: {e}"#
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct SheetStatistics {
+    /// Sheet name, same as the bond type
+    #[serde(rename = "sheet")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub sheet: String,
+
+    /// Number of bonds read from the sheet
+    #[serde(rename = "bonds")]
+    pub bonds: i32,
+
+    /// Earliest first day of sale on the sheet
+    #[serde(rename = "first_sale")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_sale: Option<chrono::naive::NaiveDate>,
+
+    /// Latest last day of sale on the sheet
+    #[serde(rename = "last_sale")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_sale: Option<chrono::naive::NaiveDate>,
+}
+
+impl SheetStatistics {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(sheet: String, bonds: i32) -> SheetStatistics {
+        SheetStatistics {
+            sheet,
+            bonds,
+            first_sale: None,
+            last_sale: None,
+        }
+    }
+}
+
+/// Converts the SheetStatistics value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for SheetStatistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            Some("sheet".to_string()),
+            Some(self.sheet.to_string()),
+            Some("bonds".to_string()),
+            Some(self.bonds.to_string()),
+            // Skipping non-primitive type first_sale in query parameter serialization
+            // Skipping non-primitive type last_sale in query parameter serialization
+        ];
+
+        write!(
+            f,
+            "{}",
+            params.into_iter().flatten().collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a SheetStatistics value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for SheetStatistics {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub sheet: Vec<String>,
+            pub bonds: Vec<i32>,
+            pub first_sale: Vec<chrono::naive::NaiveDate>,
+            pub last_sale: Vec<chrono::naive::NaiveDate>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err(
+                        "Missing value while parsing SheetStatistics".to_string(),
+                    )
+                }
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "sheet" => intermediate_rep.sheet.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "bonds" => intermediate_rep.bonds.push(
+                        <i32 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "first_sale" => intermediate_rep.first_sale.push(
+                        <chrono::naive::NaiveDate as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "last_sale" => intermediate_rep.last_sale.push(
+                        <chrono::naive::NaiveDate as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing SheetStatistics".to_string(),
+                        )
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(SheetStatistics {
+            sheet: intermediate_rep
+                .sheet
+                .into_iter()
+                .next()
+                .ok_or_else(|| "sheet missing in SheetStatistics".to_string())?,
+            bonds: intermediate_rep
+                .bonds
+                .into_iter()
+                .next()
+                .ok_or_else(|| "bonds missing in SheetStatistics".to_string())?,
+            first_sale: intermediate_rep.first_sale.into_iter().next(),
+            last_sale: intermediate_rep.last_sale.into_iter().next(),
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<SheetStatistics> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<SheetStatistics>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<SheetStatistics>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Invalid header value for SheetStatistics - value: {hdr_value} is invalid {e}"#
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<SheetStatistics> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <SheetStatistics as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        r#"Unable to convert header value '{value}' into SheetStatistics - {err}"#
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Unable to convert header: {hdr_value:?} to string: {e}"#
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct WorkbookDiagnostic {
+    /// Sheet the problem was found on, missing when it concerns the whole workbook
+    #[serde(rename = "sheet")]
+    #[validate(custom(function = "check_xss_string"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sheet: Option<String>,
+
+    /// A1 reference of the offending cell, missing when it concerns the whole sheet
+    #[serde(rename = "cell")]
+    #[validate(custom(function = "check_xss_string"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cell: Option<String>,
+
+    /// Note: inline enums are not fully supported by openapi-generator
+    #[serde(rename = "severity")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub severity: String,
+
+    #[serde(rename = "message")]
+    #[validate(custom(function = "check_xss_string"))]
+    pub message: String,
+}
+
+impl WorkbookDiagnostic {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(severity: String, message: String) -> WorkbookDiagnostic {
+        WorkbookDiagnostic {
+            sheet: None,
+            cell: None,
+            severity,
+            message,
+        }
+    }
+}
+
+/// Converts the WorkbookDiagnostic value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for WorkbookDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            self.sheet
+                .as_ref()
+                .map(|sheet| ["sheet".to_string(), sheet.to_string()].join(",")),
+            self.cell
+                .as_ref()
+                .map(|cell| ["cell".to_string(), cell.to_string()].join(",")),
+            Some("severity".to_string()),
+            Some(self.severity.to_string()),
+            Some("message".to_string()),
+            Some(self.message.to_string()),
+        ];
+
+        write!(
+            f,
+            "{}",
+            params.into_iter().flatten().collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a WorkbookDiagnostic value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for WorkbookDiagnostic {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub sheet: Vec<String>,
+            pub cell: Vec<String>,
+            pub severity: Vec<String>,
+            pub message: Vec<String>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err(
+                        "Missing value while parsing WorkbookDiagnostic".to_string(),
+                    )
+                }
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "sheet" => intermediate_rep.sheet.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "cell" => intermediate_rep.cell.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "severity" => intermediate_rep.severity.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "message" => intermediate_rep.message.push(
+                        <String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing WorkbookDiagnostic".to_string(),
+                        )
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(WorkbookDiagnostic {
+            sheet: intermediate_rep.sheet.into_iter().next(),
+            cell: intermediate_rep.cell.into_iter().next(),
+            severity: intermediate_rep
+                .severity
+                .into_iter()
+                .next()
+                .ok_or_else(|| "severity missing in WorkbookDiagnostic".to_string())?,
+            message: intermediate_rep
+                .message
+                .into_iter()
+                .next()
+                .ok_or_else(|| "message missing in WorkbookDiagnostic".to_string())?,
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<WorkbookDiagnostic> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<WorkbookDiagnostic>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<WorkbookDiagnostic>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Invalid header value for WorkbookDiagnostic - value: {hdr_value} is invalid {e}"#
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<WorkbookDiagnostic> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <WorkbookDiagnostic as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        r#"Unable to convert header value '{value}' into WorkbookDiagnostic - {err}"#
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                r#"Unable to convert header: {hdr_value:?} to string: {e}"#
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct WorkbookReport {
+    /// Whether the uploaded workbook is now being served
+    #[serde(rename = "activated")]
+    pub activated: bool,
+
+    #[serde(rename = "sheets")]
+    #[validate(nested)]
+    pub sheets: Vec<models::SheetStatistics>,
+
+    /// Problems that got the workbook rejected, empty when it was activated
+    #[serde(rename = "errors")]
+    #[validate(nested)]
+    pub errors: Vec<models::WorkbookDiagnostic>,
+}
+
+impl WorkbookReport {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(
+        activated: bool,
+        sheets: Vec<models::SheetStatistics>,
+        errors: Vec<models::WorkbookDiagnostic>,
+    ) -> WorkbookReport {
+        WorkbookReport {
+            activated,
+            sheets,
+            errors,
+        }
+    }
+}
+
+/// Converts the WorkbookReport value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for WorkbookReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            Some("activated".to_string()),
+            Some(self.activated.to_string()),
+            // Skipping non-primitive type sheets in query parameter serialization
+            // Skipping non-primitive type errors in query parameter serialization
+        ];
+
+        write!(
+            f,
+            "{}",
+            params.into_iter().flatten().collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a WorkbookReport value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for WorkbookReport {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub activated: Vec<bool>,
+            pub sheets: Vec<Vec<models::SheetStatistics>>,
+            pub errors: Vec<Vec<models::WorkbookDiagnostic>>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err(
+                        "Missing value while parsing WorkbookReport".to_string(),
+                    )
+                }
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "activated" => intermediate_rep.activated.push(
+                        <bool as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?,
+                    ),
+                    "sheets" => {
+                        return std::result::Result::Err(
+                            "Parsing a container in this style is not supported in WorkbookReport"
+                                .to_string(),
+                        )
+                    }
+                    "errors" => {
+                        return std::result::Result::Err(
+                            "Parsing a container in this style is not supported in WorkbookReport"
+                                .to_string(),
+                        )
+                    }
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing WorkbookReport".to_string(),
+                        )
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(WorkbookReport {
+            activated: intermediate_rep
+                .activated
+                .into_iter()
+                .next()
+                .ok_or_else(|| "activated missing in WorkbookReport".to_string())?,
+            sheets: intermediate_rep
+                .sheets
+                .into_iter()
+                .next()
+                .ok_or_else(|| "sheets missing in WorkbookReport".to_string())?,
+            errors: intermediate_rep
+                .errors
                 .into_iter()
                 .next()
-                .ok_or_else(|| "error missing in GetBond404Response".to_string())?,
+                .ok_or_else(|| "errors missing in WorkbookReport".to_string())?,
         })
     }
 }
 
-// Methods for converting between header::IntoHeaderValue<GetBond404Response> and HeaderValue
+// Methods for converting between header::IntoHeaderValue<WorkbookReport> and HeaderValue
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<header::IntoHeaderValue<GetBond404Response>> for HeaderValue {
+impl std::convert::TryFrom<header::IntoHeaderValue<WorkbookReport>> for HeaderValue {
     type Error = String;
 
     fn try_from(
-        hdr_value: header::IntoHeaderValue<GetBond404Response>,
+        hdr_value: header::IntoHeaderValue<WorkbookReport>,
     ) -> std::result::Result<Self, Self::Error> {
         let hdr_value = hdr_value.to_string();
         match HeaderValue::from_str(&hdr_value) {
             std::result::Result::Ok(value) => std::result::Result::Ok(value),
             std::result::Result::Err(e) => std::result::Result::Err(format!(
-                r#"Invalid header value for GetBond404Response - value: {hdr_value} is invalid {e}"#
+                r#"Invalid header value for WorkbookReport - value: {hdr_value} is invalid {e}"#
             )),
         }
     }
 }
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<GetBond404Response> {
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<WorkbookReport> {
     type Error = String;
 
     fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
         match hdr_value.to_str() {
             std::result::Result::Ok(value) => {
-                match <GetBond404Response as std::str::FromStr>::from_str(value) {
+                match <WorkbookReport as std::str::FromStr>::from_str(value) {
                     std::result::Result::Ok(value) => {
                         std::result::Result::Ok(header::IntoHeaderValue(value))
                     }
                     std::result::Result::Err(err) => std::result::Result::Err(format!(
-                        r#"Unable to convert header value '{value}' into GetBond404Response - {err}"#
+                        r#"Unable to convert header value '{value}' into WorkbookReport - {err}"#
                     )),
                 }
             }
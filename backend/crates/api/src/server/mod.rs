@@ -15,49 +15,1912 @@ use loco_rs::prelude::{AppContext, Routes};
 use crate::{apis, models};
 
 /// Setup API Server.
-pub fn new<I, A, E>(ctx: &AppContext, api_impl: I) -> Routes
+pub fn new<I, A, E, C>(ctx: &AppContext, api_impl: I) -> Routes
 where
     I: AsRef<A> + Send + Sync + 'static,
-    A: apis::default::Default<E> + Send + Sync + 'static,
+    A: apis::admin::Admin<E, Claims = C>
+        + apis::auth::Auth<E, Claims = C>
+        + apis::default::Default<E, Claims = C>
+        + apis::portfolios::Portfolios<E, Claims = C>
+        + apis::ApiKeyAuthHeader<Claims = C>
+        + apis::ApiAuthBasic<Claims = C>
+        + Send
+        + Sync
+        + 'static,
     E: std::fmt::Debug + Send + Sync + 'static,
+    C: Send + Sync + 'static,
 {
     ctx.shared_store.insert(api_impl);
 
     // build our application with a route
     Routes::new()
+        .add(
+            "/admin/workbook",
+            post(upload_workbook::<I, A, E, C>).layer(DefaultBodyLimit::max(8388608)),
+        )
+        .add("/auth/current", get(current_user::<I, A, E, C>))
+        .add("/auth/login", post(login::<I, A, E>))
+        .add("/auth/register", post(register::<I, A, E>))
         .add("/bonds", get(get_bonds::<I, A, E>))
         .add("/bonds/{id}", get(get_bond::<I, A, E>))
         .add("/bonds/{id}/csv", get(get_bond_csv::<I, A, E>))
+        .add("/export", get(export_bonds::<I, A, E, C>))
+        .add(
+            "/portfolios",
+            get(list_portfolios::<I, A, E, C>).post(create_portfolio::<I, A, E, C>),
+        )
+        .add(
+            "/portfolios/{id}",
+            delete(delete_portfolio::<I, A, E, C>)
+                .get(get_portfolio::<I, A, E, C>)
+                .put(update_portfolio::<I, A, E, C>),
+        )
+        .add("/portfolios/{id}/value", get(value_portfolio::<I, A, E, C>))
+        .add("/versions", get(list_versions::<I, A, E, C>))
+}
+
+#[derive(validator::Validate)]
+#[allow(dead_code)]
+struct CreatePortfolioBodyValidator<'a> {
+    #[validate(nested)]
+    body: &'a models::PortfolioParams,
+}
+
+#[tracing::instrument(skip_all)]
+fn create_portfolio_validation(
+    body: models::PortfolioParams,
+) -> std::result::Result<(models::PortfolioParams,), ValidationErrors> {
+    let b = CreatePortfolioBodyValidator { body: &body };
+    b.validate()?;
+
+    Ok((body,))
+}
+/// CreatePortfolio - POST /portfolios
+#[tracing::instrument(skip_all)]
+async fn create_portfolio<I, A, E, C>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    headers: HeaderMap,
+    State(app_context): State<AppContext>,
+    Json(body): Json<models::PortfolioParams>,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync + 'static,
+    A: apis::portfolios::Portfolios<E, Claims = C> + apis::ApiAuthBasic<Claims = C> + Send + Sync,
+    E: std::fmt::Debug + Send + Sync + 'static,
+{
+    // SAFETY - We know that I is in shared store, because the only way to get here is through the `new` function which inserts it into the shared store.
+    let api_impl = unsafe { app_context.shared_store.get_ref::<I>().unwrap_unchecked() };
+    // Authentication
+    let claims_in_auth_header = api_impl
+        .as_ref()
+        .extract_claims_from_auth_header(apis::BasicAuthKind::Bearer, &headers, "authorization")
+        .await;
+    let claims = None.or(claims_in_auth_header);
+    let Some(claims) = claims else {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::empty())
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR);
+    };
+
+    let validation = create_portfolio_validation(body);
+
+    let Ok((body,)) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl
+        .as_ref()
+        .create_portfolio(&method, &host, &cookies, &claims, &body)
+        .await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            apis::portfolios::CreatePortfolioResponse::Status201_PortfolioCreated(body) => {
+                let mut response = response.status(201);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::portfolios::CreatePortfolioResponse::Status400_InvalidPortfolio(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::portfolios::CreatePortfolioResponse::Status401_MissingOrInvalidToken => {
+                let mut response = response.status(401);
+                response.body(Body::empty())
+            }
+        },
+        Err(why) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            return api_impl
+                .as_ref()
+                .handle_error(&method, &host, &cookies, why)
+                .await;
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[tracing::instrument(skip_all)]
+fn current_user_validation() -> std::result::Result<(), ValidationErrors> {
+    Ok(())
+}
+/// CurrentUser - GET /auth/current
+#[tracing::instrument(skip_all)]
+async fn current_user<I, A, E, C>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    headers: HeaderMap,
+    State(app_context): State<AppContext>,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync + 'static,
+    A: apis::auth::Auth<E, Claims = C> + apis::ApiAuthBasic<Claims = C> + Send + Sync,
+    E: std::fmt::Debug + Send + Sync + 'static,
+{
+    // SAFETY - We know that I is in shared store, because the only way to get here is through the `new` function which inserts it into the shared store.
+    let api_impl = unsafe { app_context.shared_store.get_ref::<I>().unwrap_unchecked() };
+    // Authentication
+    let claims_in_auth_header = api_impl
+        .as_ref()
+        .extract_claims_from_auth_header(apis::BasicAuthKind::Bearer, &headers, "authorization")
+        .await;
+    let claims = None.or(claims_in_auth_header);
+    let Some(claims) = claims else {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::empty())
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR);
+    };
+
+    let validation = current_user_validation();
+
+    let Ok(()) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl
+        .as_ref()
+        .current_user(&method, &host, &cookies, &claims)
+        .await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            apis::auth::CurrentUserResponse::Status200_TheLoggedInUser(body) => {
+                let mut response = response.status(200);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::auth::CurrentUserResponse::Status401_MissingOrInvalidToken => {
+                let mut response = response.status(401);
+                response.body(Body::empty())
+            }
+        },
+        Err(why) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            return api_impl
+                .as_ref()
+                .handle_error(&method, &host, &cookies, why)
+                .await;
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[tracing::instrument(skip_all)]
+fn delete_portfolio_validation(
+    path_params: models::DeletePortfolioPathParams,
+) -> std::result::Result<(models::DeletePortfolioPathParams,), ValidationErrors> {
+    path_params.validate()?;
+
+    Ok((path_params,))
+}
+/// DeletePortfolio - DELETE /portfolios/{id}
+#[tracing::instrument(skip_all)]
+async fn delete_portfolio<I, A, E, C>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    headers: HeaderMap,
+    Path(path_params): Path<models::DeletePortfolioPathParams>,
+    State(app_context): State<AppContext>,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync + 'static,
+    A: apis::portfolios::Portfolios<E, Claims = C> + apis::ApiAuthBasic<Claims = C> + Send + Sync,
+    E: std::fmt::Debug + Send + Sync + 'static,
+{
+    // SAFETY - We know that I is in shared store, because the only way to get here is through the `new` function which inserts it into the shared store.
+    let api_impl = unsafe { app_context.shared_store.get_ref::<I>().unwrap_unchecked() };
+    // Authentication
+    let claims_in_auth_header = api_impl
+        .as_ref()
+        .extract_claims_from_auth_header(apis::BasicAuthKind::Bearer, &headers, "authorization")
+        .await;
+    let claims = None.or(claims_in_auth_header);
+    let Some(claims) = claims else {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::empty())
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR);
+    };
+
+    let validation = delete_portfolio_validation(path_params);
+
+    let Ok((path_params,)) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl
+        .as_ref()
+        .delete_portfolio(&method, &host, &cookies, &claims, &path_params)
+        .await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            apis::portfolios::DeletePortfolioResponse::Status204_PortfolioDeleted => {
+                let mut response = response.status(204);
+                response.body(Body::empty())
+            }
+            apis::portfolios::DeletePortfolioResponse::Status401_MissingOrInvalidToken => {
+                let mut response = response.status(401);
+                response.body(Body::empty())
+            }
+            apis::portfolios::DeletePortfolioResponse::Status404_PortfolioNotFound(body) => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+        },
+        Err(why) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            return api_impl
+                .as_ref()
+                .handle_error(&method, &host, &cookies, why)
+                .await;
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[tracing::instrument(skip_all)]
+fn export_bonds_validation(
+    header_params: models::ExportBondsHeaderParams,
+    query_params: models::ExportBondsQueryParams,
+) -> std::result::Result<
+    (
+        models::ExportBondsHeaderParams,
+        models::ExportBondsQueryParams,
+    ),
+    ValidationErrors,
+> {
+    header_params.validate()?;
+    query_params.validate()?;
+
+    Ok((header_params, query_params))
+}
+/// ExportBonds - GET /export
+#[tracing::instrument(skip_all)]
+async fn export_bonds<I, A, E, C>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    headers: HeaderMap,
+    QueryExtra(query_params): QueryExtra<models::ExportBondsQueryParams>,
+    State(app_context): State<AppContext>,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync + 'static,
+    A: apis::default::Default<E, Claims = C> + apis::ApiKeyAuthHeader<Claims = C> + Send + Sync,
+    E: std::fmt::Debug + Send + Sync + 'static,
+{
+    // SAFETY - We know that I is in shared store, because the only way to get here is through the `new` function which inserts it into the shared store.
+    let api_impl = unsafe { app_context.shared_store.get_ref::<I>().unwrap_unchecked() };
+    // Authentication
+    let claims_in_header = api_impl
+        .as_ref()
+        .extract_claims_from_header(&headers, "X-API-Key")
+        .await;
+    let claims = None.or(claims_in_header);
+    let Some(claims) = claims else {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::empty())
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR);
+    };
+
+    // Header parameters
+    let header_params = {
+        let header_if_none_match = headers.get(HeaderName::from_static("if-none-match"));
+
+        let header_if_none_match = match header_if_none_match {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!(
+                            "Invalid header If-None-Match - {}",
+                            err
+                        )))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+        let header_if_modified_since = headers.get(HeaderName::from_static("if-modified-since"));
+
+        let header_if_modified_since = match header_if_modified_since {
+            Some(v) => match header::IntoHeaderValue::<chrono::DateTime<chrono::Utc>>::try_from(
+                (*v).clone(),
+            ) {
+                Ok(result) => Some(result.0),
+                Err(_) => None,
+            },
+            None => None,
+        };
+
+        models::ExportBondsHeaderParams {
+            if_none_match: header_if_none_match,
+            if_modified_since: header_if_modified_since,
+        }
+    };
+
+    let validation = export_bonds_validation(header_params, query_params);
+
+    let Ok((header_params, query_params)) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl
+        .as_ref()
+        .export_bonds(
+            &method,
+            &host,
+            &cookies,
+            &claims,
+            &header_params,
+            &query_params,
+        )
+        .await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            apis::default::ExportBondsResponse::Status200_ValuesOfEveryBond {
+                body,
+                content_type,
+                content_disposition,
+                e_tag,
+                last_modified,
+                cache_control,
+            } => {
+                let content_type = match header::IntoHeaderValue(content_type).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling content_type header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(HeaderName::from_static("content-type"), content_type);
+                }
+                let content_disposition = match header::IntoHeaderValue(content_disposition).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling content_disposition header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(HeaderName::from_static("content-disposition"), content_disposition);
+                }
+                let e_tag = match header::IntoHeaderValue(e_tag).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling e_tag header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(HeaderName::from_static("etag"), e_tag);
+                }
+                let last_modified = match header::IntoHeaderValue(last_modified).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling last_modified header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(HeaderName::from_static("last-modified"), last_modified);
+                }
+                let cache_control = match header::IntoHeaderValue(cache_control).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling cache_control header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(HeaderName::from_static("cache-control"), cache_control);
+                }
+                let mut response = response.status(200);
+                response.body(body)
+            }
+            apis::default::ExportBondsResponse::Status304_NotModified {
+                e_tag,
+                cache_control,
+            } => {
+                let e_tag = match header::IntoHeaderValue(e_tag).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling e_tag header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(HeaderName::from_static("etag"), e_tag);
+                }
+                let cache_control = match header::IntoHeaderValue(cache_control).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling cache_control header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(HeaderName::from_static("cache-control"), cache_control);
+                }
+                let mut response = response.status(304);
+                response.body(Body::empty())
+            }
+            apis::default::ExportBondsResponse::Status400_InvalidLayoutOrFormat(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::default::ExportBondsResponse::Status404_NoDatasetVersionPublishedAsOfTheGivenDate(body) => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+        },
+        Err(why) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            return api_impl
+                .as_ref()
+                .handle_error(&method, &host, &cookies, why)
+                .await;
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[tracing::instrument(skip_all)]
+fn get_bond_validation(
+    header_params: models::GetBondHeaderParams,
+    path_params: models::GetBondPathParams,
+    query_params: models::GetBondQueryParams,
+) -> std::result::Result<
+    (
+        models::GetBondHeaderParams,
+        models::GetBondPathParams,
+        models::GetBondQueryParams,
+    ),
+    ValidationErrors,
+> {
+    header_params.validate()?;
+    path_params.validate()?;
+    query_params.validate()?;
+
+    Ok((header_params, path_params, query_params))
+}
+/// GetBond - GET /bonds/{id}
+#[tracing::instrument(skip_all)]
+async fn get_bond<I, A, E>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    headers: HeaderMap,
+    Path(path_params): Path<models::GetBondPathParams>,
+    QueryExtra(query_params): QueryExtra<models::GetBondQueryParams>,
+    State(app_context): State<AppContext>,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync + 'static,
+    A: apis::default::Default<E> + Send + Sync,
+    E: std::fmt::Debug + Send + Sync + 'static,
+{
+    // SAFETY - We know that I is in shared store, because the only way to get here is through the `new` function which inserts it into the shared store.
+    let api_impl = unsafe { app_context.shared_store.get_ref::<I>().unwrap_unchecked() };
+
+    // Header parameters
+    let header_params = {
+        let header_if_none_match = headers.get(HeaderName::from_static("if-none-match"));
+
+        let header_if_none_match = match header_if_none_match {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!(
+                            "Invalid header If-None-Match - {}",
+                            err
+                        )))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+        let header_if_modified_since = headers.get(HeaderName::from_static("if-modified-since"));
+
+        let header_if_modified_since = match header_if_modified_since {
+            Some(v) => match header::IntoHeaderValue::<chrono::DateTime<chrono::Utc>>::try_from(
+                (*v).clone(),
+            ) {
+                Ok(result) => Some(result.0),
+                Err(_) => None,
+            },
+            None => None,
+        };
+
+        models::GetBondHeaderParams {
+            if_none_match: header_if_none_match,
+            if_modified_since: header_if_modified_since,
+        }
+    };
+
+    let validation = get_bond_validation(header_params, path_params, query_params);
+
+    let Ok((header_params, path_params, query_params)) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl
+        .as_ref()
+        .get_bond(
+            &method,
+            &host,
+            &cookies,
+            &header_params,
+            &path_params,
+            &query_params,
+        )
+        .await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            apis::default::GetBondResponse::Status200_ASingleBondObject {
+                body,
+                e_tag,
+                last_modified,
+                cache_control,
+            } => {
+                let e_tag = match header::IntoHeaderValue(e_tag).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling e_tag header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(HeaderName::from_static("etag"), e_tag);
+                }
+                let last_modified = match header::IntoHeaderValue(last_modified).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling last_modified header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers
+                        .insert(HeaderName::from_static("last-modified"), last_modified);
+                }
+                let cache_control = match header::IntoHeaderValue(cache_control).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling cache_control header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers
+                        .insert(HeaderName::from_static("cache-control"), cache_control);
+                }
+                let mut response = response.status(200);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::default::GetBondResponse::Status304_NotModified {
+                e_tag,
+                cache_control,
+            } => {
+                let e_tag = match header::IntoHeaderValue(e_tag).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling e_tag header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(HeaderName::from_static("etag"), e_tag);
+                }
+                let cache_control = match header::IntoHeaderValue(cache_control).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling cache_control header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers
+                        .insert(HeaderName::from_static("cache-control"), cache_control);
+                }
+                let mut response = response.status(304);
+                response.body(Body::empty())
+            }
+            apis::default::GetBondResponse::Status404_BondNotFound(body) => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+        },
+        Err(why) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            return api_impl
+                .as_ref()
+                .handle_error(&method, &host, &cookies, why)
+                .await;
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[tracing::instrument(skip_all)]
+fn get_bond_csv_validation(
+    header_params: models::GetBondCsvHeaderParams,
+    path_params: models::GetBondCsvPathParams,
+    query_params: models::GetBondCsvQueryParams,
+) -> std::result::Result<
+    (
+        models::GetBondCsvHeaderParams,
+        models::GetBondCsvPathParams,
+        models::GetBondCsvQueryParams,
+    ),
+    ValidationErrors,
+> {
+    header_params.validate()?;
+    path_params.validate()?;
+    query_params.validate()?;
+
+    Ok((header_params, path_params, query_params))
+}
+/// GetBondCsv - GET /bonds/{id}/csv
+#[tracing::instrument(skip_all)]
+async fn get_bond_csv<I, A, E>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    headers: HeaderMap,
+    Path(path_params): Path<models::GetBondCsvPathParams>,
+    QueryExtra(query_params): QueryExtra<models::GetBondCsvQueryParams>,
+    State(app_context): State<AppContext>,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync + 'static,
+    A: apis::default::Default<E> + Send + Sync,
+    E: std::fmt::Debug + Send + Sync + 'static,
+{
+    // SAFETY - We know that I is in shared store, because the only way to get here is through the `new` function which inserts it into the shared store.
+    let api_impl = unsafe { app_context.shared_store.get_ref::<I>().unwrap_unchecked() };
+
+    // Header parameters
+    let header_params = {
+        let header_accept = headers.get(HeaderName::from_static("accept"));
+
+        let header_accept = match header_accept {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header Accept - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+        let header_if_none_match = headers.get(HeaderName::from_static("if-none-match"));
+
+        let header_if_none_match = match header_if_none_match {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!(
+                            "Invalid header If-None-Match - {}",
+                            err
+                        )))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+        let header_if_modified_since = headers.get(HeaderName::from_static("if-modified-since"));
+
+        let header_if_modified_since = match header_if_modified_since {
+            Some(v) => match header::IntoHeaderValue::<chrono::DateTime<chrono::Utc>>::try_from(
+                (*v).clone(),
+            ) {
+                Ok(result) => Some(result.0),
+                Err(_) => None,
+            },
+            None => None,
+        };
+
+        models::GetBondCsvHeaderParams {
+            accept: header_accept,
+            if_none_match: header_if_none_match,
+            if_modified_since: header_if_modified_since,
+        }
+    };
+
+    let validation = get_bond_csv_validation(header_params, path_params, query_params);
+
+    let Ok((header_params, path_params, query_params)) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl
+        .as_ref()
+        .get_bond_csv(
+            &method,
+            &host,
+            &cookies,
+            &header_params,
+            &path_params,
+            &query_params,
+        )
+        .await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            apis::default::GetBondCsvResponse::Status200_BondValuesInTheRequestedFormat {
+                body,
+                content_type,
+                e_tag,
+                last_modified,
+                cache_control,
+                vary,
+            } => {
+                let content_type = match header::IntoHeaderValue(content_type).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling content_type header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(HeaderName::from_static("content-type"), content_type);
+                }
+                let e_tag = match header::IntoHeaderValue(e_tag).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling e_tag header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(HeaderName::from_static("etag"), e_tag);
+                }
+                let last_modified = match header::IntoHeaderValue(last_modified).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling last_modified header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers
+                        .insert(HeaderName::from_static("last-modified"), last_modified);
+                }
+                let cache_control = match header::IntoHeaderValue(cache_control).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling cache_control header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers
+                        .insert(HeaderName::from_static("cache-control"), cache_control);
+                }
+                let vary = match header::IntoHeaderValue(vary).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling vary header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(HeaderName::from_static("vary"), vary);
+                }
+                let mut response = response.status(200);
+                response.body(body)
+            }
+            apis::default::GetBondCsvResponse::Status304_NotModified {
+                e_tag,
+                cache_control,
+            } => {
+                let e_tag = match header::IntoHeaderValue(e_tag).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling e_tag header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(HeaderName::from_static("etag"), e_tag);
+                }
+                let cache_control = match header::IntoHeaderValue(cache_control).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling cache_control header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers
+                        .insert(HeaderName::from_static("cache-control"), cache_control);
+                }
+                let mut response = response.status(304);
+                response.body(Body::empty())
+            }
+            apis::default::GetBondCsvResponse::Status400_InvalidFormatOrCSVOptions(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::default::GetBondCsvResponse::Status404_BondNotFound(body) => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::default::GetBondCsvResponse::Status406_NoneOfTheAcceptedMediaTypesCanBeProduced(body) => {
+                let mut response = response.status(406);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+        },
+        Err(why) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            return api_impl
+                .as_ref()
+                .handle_error(&method, &host, &cookies, why)
+                .await;
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[tracing::instrument(skip_all)]
+fn get_bonds_validation(
+    query_params: models::GetBondsQueryParams,
+) -> std::result::Result<(models::GetBondsQueryParams,), ValidationErrors> {
+    query_params.validate()?;
+
+    Ok((query_params,))
+}
+/// GetBonds - GET /bonds
+#[tracing::instrument(skip_all)]
+async fn get_bonds<I, A, E>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    QueryExtra(query_params): QueryExtra<models::GetBondsQueryParams>,
+    State(app_context): State<AppContext>,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync + 'static,
+    A: apis::default::Default<E> + Send + Sync,
+    E: std::fmt::Debug + Send + Sync + 'static,
+{
+    // SAFETY - We know that I is in shared store, because the only way to get here is through the `new` function which inserts it into the shared store.
+    let api_impl = unsafe { app_context.shared_store.get_ref::<I>().unwrap_unchecked() };
+
+    let validation = get_bonds_validation(query_params);
+
+    let Ok((query_params,)) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl
+        .as_ref()
+        .get_bonds(&method, &host, &cookies, &query_params)
+        .await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            apis::default::GetBondsResponse::Status200_AJSONArrayOfBondNames {
+                body,
+                x_total_count,
+            } => {
+                let x_total_count = match header::IntoHeaderValue(x_total_count).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling x_total_count header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers
+                        .insert(HeaderName::from_static("x-total-count"), x_total_count);
+                }
+                let mut response = response.status(200);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::default::GetBondsResponse::Status400_InvalidQueryParameters(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::default::GetBondsResponse::Status404_NoDatasetVersionPublishedAsOfTheGivenDate(body) => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+        },
+        Err(why) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            return api_impl
+                .as_ref()
+                .handle_error(&method, &host, &cookies, why)
+                .await;
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[tracing::instrument(skip_all)]
+fn get_portfolio_validation(
+    path_params: models::GetPortfolioPathParams,
+) -> std::result::Result<(models::GetPortfolioPathParams,), ValidationErrors> {
+    path_params.validate()?;
+
+    Ok((path_params,))
+}
+/// GetPortfolio - GET /portfolios/{id}
+#[tracing::instrument(skip_all)]
+async fn get_portfolio<I, A, E, C>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    headers: HeaderMap,
+    Path(path_params): Path<models::GetPortfolioPathParams>,
+    State(app_context): State<AppContext>,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync + 'static,
+    A: apis::portfolios::Portfolios<E, Claims = C> + apis::ApiAuthBasic<Claims = C> + Send + Sync,
+    E: std::fmt::Debug + Send + Sync + 'static,
+{
+    // SAFETY - We know that I is in shared store, because the only way to get here is through the `new` function which inserts it into the shared store.
+    let api_impl = unsafe { app_context.shared_store.get_ref::<I>().unwrap_unchecked() };
+    // Authentication
+    let claims_in_auth_header = api_impl
+        .as_ref()
+        .extract_claims_from_auth_header(apis::BasicAuthKind::Bearer, &headers, "authorization")
+        .await;
+    let claims = None.or(claims_in_auth_header);
+    let Some(claims) = claims else {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::empty())
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR);
+    };
+
+    let validation = get_portfolio_validation(path_params);
+
+    let Ok((path_params,)) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl
+        .as_ref()
+        .get_portfolio(&method, &host, &cookies, &claims, &path_params)
+        .await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            apis::portfolios::GetPortfolioResponse::Status200_ASinglePortfolio(body) => {
+                let mut response = response.status(200);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::portfolios::GetPortfolioResponse::Status401_MissingOrInvalidToken => {
+                let mut response = response.status(401);
+                response.body(Body::empty())
+            }
+            apis::portfolios::GetPortfolioResponse::Status404_PortfolioNotFound(body) => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+        },
+        Err(why) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            return api_impl
+                .as_ref()
+                .handle_error(&method, &host, &cookies, why)
+                .await;
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[tracing::instrument(skip_all)]
+fn list_portfolios_validation() -> std::result::Result<(), ValidationErrors> {
+    Ok(())
+}
+/// ListPortfolios - GET /portfolios
+#[tracing::instrument(skip_all)]
+async fn list_portfolios<I, A, E, C>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    headers: HeaderMap,
+    State(app_context): State<AppContext>,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync + 'static,
+    A: apis::portfolios::Portfolios<E, Claims = C> + apis::ApiAuthBasic<Claims = C> + Send + Sync,
+    E: std::fmt::Debug + Send + Sync + 'static,
+{
+    // SAFETY - We know that I is in shared store, because the only way to get here is through the `new` function which inserts it into the shared store.
+    let api_impl = unsafe { app_context.shared_store.get_ref::<I>().unwrap_unchecked() };
+    // Authentication
+    let claims_in_auth_header = api_impl
+        .as_ref()
+        .extract_claims_from_auth_header(apis::BasicAuthKind::Bearer, &headers, "authorization")
+        .await;
+    let claims = None.or(claims_in_auth_header);
+    let Some(claims) = claims else {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::empty())
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR);
+    };
+
+    let validation = list_portfolios_validation();
+
+    let Ok(()) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl
+        .as_ref()
+        .list_portfolios(&method, &host, &cookies, &claims)
+        .await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            apis::portfolios::ListPortfoliosResponse::Status200_PortfoliosOfTheUser(body) => {
+                let mut response = response.status(200);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::portfolios::ListPortfoliosResponse::Status401_MissingOrInvalidToken => {
+                let mut response = response.status(401);
+                response.body(Body::empty())
+            }
+        },
+        Err(why) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            return api_impl
+                .as_ref()
+                .handle_error(&method, &host, &cookies, why)
+                .await;
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[tracing::instrument(skip_all)]
+fn list_versions_validation() -> std::result::Result<(), ValidationErrors> {
+    Ok(())
+}
+/// ListVersions - GET /versions
+#[tracing::instrument(skip_all)]
+async fn list_versions<I, A, E, C>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    headers: HeaderMap,
+    State(app_context): State<AppContext>,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync + 'static,
+    A: apis::default::Default<E, Claims = C> + apis::ApiKeyAuthHeader<Claims = C> + Send + Sync,
+    E: std::fmt::Debug + Send + Sync + 'static,
+{
+    // SAFETY - We know that I is in shared store, because the only way to get here is through the `new` function which inserts it into the shared store.
+    let api_impl = unsafe { app_context.shared_store.get_ref::<I>().unwrap_unchecked() };
+    // Authentication
+    let claims_in_header = api_impl
+        .as_ref()
+        .extract_claims_from_header(&headers, "X-API-Key")
+        .await;
+    let claims = None.or(claims_in_header);
+    let Some(claims) = claims else {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::empty())
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR);
+    };
+
+    let validation = list_versions_validation();
+
+    let Ok(()) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl
+        .as_ref()
+        .list_versions(&method, &host, &cookies, &claims)
+        .await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            apis::default::ListVersionsResponse::Status200_DatasetVersions(body) => {
+                let mut response = response.status(200);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+        },
+        Err(why) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            return api_impl
+                .as_ref()
+                .handle_error(&method, &host, &cookies, why)
+                .await;
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[derive(validator::Validate)]
+#[allow(dead_code)]
+struct LoginBodyValidator<'a> {
+    #[validate(nested)]
+    body: &'a models::LoginParams,
+}
+
+#[tracing::instrument(skip_all)]
+fn login_validation(
+    body: models::LoginParams,
+) -> std::result::Result<(models::LoginParams,), ValidationErrors> {
+    let b = LoginBodyValidator { body: &body };
+    b.validate()?;
+
+    Ok((body,))
+}
+/// Login - POST /auth/login
+#[tracing::instrument(skip_all)]
+async fn login<I, A, E>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    State(app_context): State<AppContext>,
+    Json(body): Json<models::LoginParams>,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync + 'static,
+    A: apis::auth::Auth<E> + Send + Sync,
+    E: std::fmt::Debug + Send + Sync + 'static,
+{
+    // SAFETY - We know that I is in shared store, because the only way to get here is through the `new` function which inserts it into the shared store.
+    let api_impl = unsafe { app_context.shared_store.get_ref::<I>().unwrap_unchecked() };
+
+    let validation = login_validation(body);
+
+    let Ok((body,)) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl
+        .as_ref()
+        .login(&method, &host, &cookies, &body)
+        .await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            apis::auth::LoginResponse::Status200_LoggedIn(body) => {
+                let mut response = response.status(200);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::auth::LoginResponse::Status401_InvalidEmailOrPassword(body) => {
+                let mut response = response.status(401);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::auth::LoginResponse::Status503_LoggingInIsDisabled(body) => {
+                let mut response = response.status(503);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+        },
+        Err(why) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            return api_impl
+                .as_ref()
+                .handle_error(&method, &host, &cookies, why)
+                .await;
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[derive(validator::Validate)]
+#[allow(dead_code)]
+struct RegisterBodyValidator<'a> {
+    #[validate(nested)]
+    body: &'a models::RegisterParams,
 }
 
 #[tracing::instrument(skip_all)]
-fn get_bond_validation(
-    path_params: models::GetBondPathParams,
-) -> std::result::Result<(models::GetBondPathParams,), ValidationErrors> {
-    path_params.validate()?;
+fn register_validation(
+    body: models::RegisterParams,
+) -> std::result::Result<(models::RegisterParams,), ValidationErrors> {
+    let b = RegisterBodyValidator { body: &body };
+    b.validate()?;
 
-    Ok((path_params,))
+    Ok((body,))
 }
-/// GetBond - GET /bonds/{id}
+/// Register - POST /auth/register
 #[tracing::instrument(skip_all)]
-async fn get_bond<I, A, E>(
+async fn register<I, A, E>(
     method: Method,
     host: Host,
     cookies: CookieJar,
-    Path(path_params): Path<models::GetBondPathParams>,
     State(app_context): State<AppContext>,
+    Json(body): Json<models::RegisterParams>,
 ) -> Result<Response, StatusCode>
 where
     I: AsRef<A> + Send + Sync + 'static,
-    A: apis::default::Default<E> + Send + Sync,
+    A: apis::auth::Auth<E> + Send + Sync,
     E: std::fmt::Debug + Send + Sync + 'static,
 {
     // SAFETY - We know that I is in shared store, because the only way to get here is through the `new` function which inserts it into the shared store.
     let api_impl = unsafe { app_context.shared_store.get_ref::<I>().unwrap_unchecked() };
 
-    let validation = get_bond_validation(path_params);
+    let validation = register_validation(body);
 
-    let Ok((path_params,)) = validation else {
+    let Ok((body,)) = validation else {
         return Response::builder()
             .status(StatusCode::BAD_REQUEST)
             .body(Body::from(validation.unwrap_err().to_string()))
@@ -66,14 +1929,14 @@ where
 
     let result = api_impl
         .as_ref()
-        .get_bond(&method, &host, &cookies, &path_params)
+        .register(&method, &host, &cookies, &body)
         .await;
 
     let mut response = Response::builder();
 
     let resp = match result {
         Ok(rsp) => match rsp {
-            apis::default::GetBondResponse::Status200_ASingleBondObject(body) => {
+            apis::auth::RegisterResponse::Status200_AccountCreated(body) => {
                 let mut response = response.status(200);
                 {
                     let mut response_headers = response.headers_mut().unwrap();
@@ -92,8 +1955,27 @@ where
                 })?;
                 response.body(Body::from(body_content))
             }
-            apis::default::GetBondResponse::Status404_BondNotFound(body) => {
-                let mut response = response.status(404);
+            apis::auth::RegisterResponse::Status400_InvalidRegistration(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::auth::RegisterResponse::Status409_EmailAlreadyRegistered(body) => {
+                let mut response = response.status(409);
                 {
                     let mut response_headers = response.headers_mut().unwrap();
                     response_headers.insert(
@@ -128,34 +2010,61 @@ where
     })
 }
 
+#[derive(validator::Validate)]
+#[allow(dead_code)]
+struct UpdatePortfolioBodyValidator<'a> {
+    #[validate(nested)]
+    body: &'a models::PortfolioParams,
+}
+
 #[tracing::instrument(skip_all)]
-fn get_bond_csv_validation(
-    path_params: models::GetBondCsvPathParams,
-) -> std::result::Result<(models::GetBondCsvPathParams,), ValidationErrors> {
+fn update_portfolio_validation(
+    path_params: models::UpdatePortfolioPathParams,
+    body: models::PortfolioParams,
+) -> std::result::Result<
+    (models::UpdatePortfolioPathParams, models::PortfolioParams),
+    ValidationErrors,
+> {
     path_params.validate()?;
+    let b = UpdatePortfolioBodyValidator { body: &body };
+    b.validate()?;
 
-    Ok((path_params,))
+    Ok((path_params, body))
 }
-/// GetBondCsv - GET /bonds/{id}/csv
+/// UpdatePortfolio - PUT /portfolios/{id}
 #[tracing::instrument(skip_all)]
-async fn get_bond_csv<I, A, E>(
+async fn update_portfolio<I, A, E, C>(
     method: Method,
     host: Host,
     cookies: CookieJar,
-    Path(path_params): Path<models::GetBondCsvPathParams>,
+    headers: HeaderMap,
+    Path(path_params): Path<models::UpdatePortfolioPathParams>,
     State(app_context): State<AppContext>,
+    Json(body): Json<models::PortfolioParams>,
 ) -> Result<Response, StatusCode>
 where
     I: AsRef<A> + Send + Sync + 'static,
-    A: apis::default::Default<E> + Send + Sync,
+    A: apis::portfolios::Portfolios<E, Claims = C> + apis::ApiAuthBasic<Claims = C> + Send + Sync,
     E: std::fmt::Debug + Send + Sync + 'static,
 {
     // SAFETY - We know that I is in shared store, because the only way to get here is through the `new` function which inserts it into the shared store.
     let api_impl = unsafe { app_context.shared_store.get_ref::<I>().unwrap_unchecked() };
+    // Authentication
+    let claims_in_auth_header = api_impl
+        .as_ref()
+        .extract_claims_from_auth_header(apis::BasicAuthKind::Bearer, &headers, "authorization")
+        .await;
+    let claims = None.or(claims_in_auth_header);
+    let Some(claims) = claims else {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::empty())
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR);
+    };
 
-    let validation = get_bond_csv_validation(path_params);
+    let validation = update_portfolio_validation(path_params, body);
 
-    let Ok((path_params,)) = validation else {
+    let Ok((path_params, body)) = validation else {
         return Response::builder()
             .status(StatusCode::BAD_REQUEST)
             .body(Body::from(validation.unwrap_err().to_string()))
@@ -164,30 +2073,56 @@ where
 
     let result = api_impl
         .as_ref()
-        .get_bond_csv(&method, &host, &cookies, &path_params)
+        .update_portfolio(&method, &host, &cookies, &claims, &path_params, &body)
         .await;
 
     let mut response = Response::builder();
 
     let resp = match result {
         Ok(rsp) => match rsp {
-            apis::default::GetBondCsvResponse::Status200_BondDataInCSVFormat(body) => {
+            apis::portfolios::UpdatePortfolioResponse::Status200_PortfolioUpdated(body) => {
                 let mut response = response.status(200);
                 {
                     let mut response_headers = response.headers_mut().unwrap();
                     response_headers.insert(
                         CONTENT_TYPE,
-                        HeaderValue::from_str("text/csv").map_err(|e| {
+                        HeaderValue::from_str("application/json").map_err(|e| {
                             error!(error = ?e);
                             StatusCode::INTERNAL_SERVER_ERROR
                         })?,
                     );
                 }
 
-                let body_content = body;
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
                 response.body(Body::from(body_content))
             }
-            apis::default::GetBondCsvResponse::Status404_BondNotFound(body) => {
+            apis::portfolios::UpdatePortfolioResponse::Status400_InvalidPortfolio(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::portfolios::UpdatePortfolioResponse::Status401_MissingOrInvalidToken => {
+                let mut response = response.status(401);
+                response.body(Body::empty())
+            }
+            apis::portfolios::UpdatePortfolioResponse::Status404_PortfolioNotFound(body) => {
                 let mut response = response.status(404);
                 {
                     let mut response_headers = response.headers_mut().unwrap();
@@ -224,26 +2159,40 @@ where
 }
 
 #[tracing::instrument(skip_all)]
-fn get_bonds_validation() -> std::result::Result<(), ValidationErrors> {
+fn upload_workbook_validation() -> std::result::Result<(), ValidationErrors> {
     Ok(())
 }
-/// GetBonds - GET /bonds
+/// UploadWorkbook - POST /admin/workbook
 #[tracing::instrument(skip_all)]
-async fn get_bonds<I, A, E>(
+async fn upload_workbook<I, A, E, C>(
     method: Method,
     host: Host,
     cookies: CookieJar,
+    headers: HeaderMap,
     State(app_context): State<AppContext>,
+    body: Multipart,
 ) -> Result<Response, StatusCode>
 where
     I: AsRef<A> + Send + Sync + 'static,
-    A: apis::default::Default<E> + Send + Sync,
+    A: apis::admin::Admin<E, Claims = C> + apis::ApiKeyAuthHeader<Claims = C> + Send + Sync,
     E: std::fmt::Debug + Send + Sync + 'static,
 {
     // SAFETY - We know that I is in shared store, because the only way to get here is through the `new` function which inserts it into the shared store.
     let api_impl = unsafe { app_context.shared_store.get_ref::<I>().unwrap_unchecked() };
+    // Authentication
+    let claims_in_header = api_impl
+        .as_ref()
+        .extract_claims_from_header(&headers, "X-Admin-Key")
+        .await;
+    let claims = None.or(claims_in_header);
+    let Some(claims) = claims else {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::empty())
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR);
+    };
 
-    let validation = get_bonds_validation();
+    let validation = upload_workbook_validation();
 
     let Ok(()) = validation else {
         return Response::builder()
@@ -252,13 +2201,185 @@ where
             .map_err(|_| StatusCode::BAD_REQUEST);
     };
 
-    let result = api_impl.as_ref().get_bonds(&method, &host, &cookies).await;
+    let result = api_impl
+        .as_ref()
+        .upload_workbook(&method, &host, &cookies, &claims, body)
+        .await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            apis::admin::UploadWorkbookResponse::Status200_WorkbookActivated(body) => {
+                let mut response = response.status(200);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::admin::UploadWorkbookResponse::Status400_MissingOrUnreadableUpload(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::admin::UploadWorkbookResponse::Status401_MissingOrInvalidAdminKey => {
+                let mut response = response.status(401);
+                response.body(Body::empty())
+            }
+            apis::admin::UploadWorkbookResponse::Status413_UploadLargerThanTheLimit(body) => {
+                let mut response = response.status(413);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::admin::UploadWorkbookResponse::Status422_WorkbookRejected(body) => {
+                let mut response = response.status(422);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+        },
+        Err(why) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            return api_impl
+                .as_ref()
+                .handle_error(&method, &host, &cookies, why)
+                .await;
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[tracing::instrument(skip_all)]
+fn value_portfolio_validation(
+    path_params: models::ValuePortfolioPathParams,
+    query_params: models::ValuePortfolioQueryParams,
+) -> std::result::Result<
+    (
+        models::ValuePortfolioPathParams,
+        models::ValuePortfolioQueryParams,
+    ),
+    ValidationErrors,
+> {
+    path_params.validate()?;
+    query_params.validate()?;
+
+    Ok((path_params, query_params))
+}
+/// ValuePortfolio - GET /portfolios/{id}/value
+#[tracing::instrument(skip_all)]
+async fn value_portfolio<I, A, E, C>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    headers: HeaderMap,
+    Path(path_params): Path<models::ValuePortfolioPathParams>,
+    QueryExtra(query_params): QueryExtra<models::ValuePortfolioQueryParams>,
+    State(app_context): State<AppContext>,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync + 'static,
+    A: apis::portfolios::Portfolios<E, Claims = C> + apis::ApiAuthBasic<Claims = C> + Send + Sync,
+    E: std::fmt::Debug + Send + Sync + 'static,
+{
+    // SAFETY - We know that I is in shared store, because the only way to get here is through the `new` function which inserts it into the shared store.
+    let api_impl = unsafe { app_context.shared_store.get_ref::<I>().unwrap_unchecked() };
+    // Authentication
+    let claims_in_auth_header = api_impl
+        .as_ref()
+        .extract_claims_from_auth_header(apis::BasicAuthKind::Bearer, &headers, "authorization")
+        .await;
+    let claims = None.or(claims_in_auth_header);
+    let Some(claims) = claims else {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::empty())
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR);
+    };
+
+    let validation = value_portfolio_validation(path_params, query_params);
+
+    let Ok((path_params, query_params)) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl
+        .as_ref()
+        .value_portfolio(
+            &method,
+            &host,
+            &cookies,
+            &claims,
+            &path_params,
+            &query_params,
+        )
+        .await;
 
     let mut response = Response::builder();
 
     let resp = match result {
         Ok(rsp) => match rsp {
-            apis::default::GetBondsResponse::Status200_AJSONArrayOfBondNames(body) => {
+            apis::portfolios::ValuePortfolioResponse::Status200_ValueOfThePortfolio(body) => {
                 let mut response = response.status(200);
                 {
                     let mut response_headers = response.headers_mut().unwrap();
@@ -271,6 +2392,29 @@ where
                     );
                 }
 
+                let body_content = serde_json::to_vec(&body).map_err(|e| {
+                    error!(error = ?e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                response.body(Body::from(body_content))
+            }
+            apis::portfolios::ValuePortfolioResponse::Status401_MissingOrInvalidToken => {
+                let mut response = response.status(401);
+                response.body(Body::empty())
+            }
+            apis::portfolios::ValuePortfolioResponse::Status404_PortfolioNotFound(body) => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
                 let body_content = serde_json::to_vec(&body).map_err(|e| {
                     error!(error = ?e);
                     StatusCode::INTERNAL_SERVER_ERROR
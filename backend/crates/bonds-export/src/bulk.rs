@@ -0,0 +1,259 @@
+use crate::{CsvOptions, Format, columnar, export_to, series_json, write_json, write_ndjson};
+use anyhow::{Result, bail};
+use model::{AllBonds, Bond};
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use std::str::FromStr;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// How the bonds of a bulk export are laid out
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Layout {
+    /// A single file with a `bond_id` column in front of the date and the value
+    Long,
+    /// ZIP archive with a file per bond, named after its ID
+    Zip,
+}
+
+impl Layout {
+    /// Every layout, the first one being the default
+    pub const ALL: [Layout; 2] = [Layout::Long, Layout::Zip];
+
+    /// Name of the layout in the `layout` parameter and on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            Layout::Long => "long",
+            Layout::Zip => "zip",
+        }
+    }
+
+    pub fn content_type(self, format: Format) -> &'static str {
+        match self {
+            Layout::Long => format.content_type(),
+            Layout::Zip => "application/zip",
+        }
+    }
+
+    /// Extension of the exported file
+    pub fn extension(self, format: Format) -> &'static str {
+        match self {
+            Layout::Long => format.name(),
+            Layout::Zip => "zip",
+        }
+    }
+
+    /// Fails for the formats that cannot hold every bond in a single file
+    pub fn validate(self, format: Format) -> Result<()> {
+        if self == Layout::Long && format == Format::Xlsx {
+            bail!("xlsx exports are only available with the zip layout");
+        }
+        Ok(())
+    }
+}
+
+impl Display for Layout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Layout {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        match Layout::ALL
+            .into_iter()
+            .find(|layout| layout.name().eq_ignore_ascii_case(name))
+        {
+            Some(layout) => Ok(layout),
+            None => bail!(
+                "Unknown layout {name}, expected one of {}",
+                Layout::ALL.map(Layout::name).join(", ")
+            ),
+        }
+    }
+}
+
+/// Writes the daily values of every bond, sorted by ID, one bond at a time. The writer is not
+/// buffered here, wrap it in a [`std::io::BufWriter`] when writing to a file.
+pub fn export_all<W: Write + Send>(
+    all_bonds: &AllBonds,
+    layout: Layout,
+    format: Format,
+    writer: W,
+) -> Result<()> {
+    layout.validate(format)?;
+    let mut bonds: Vec<&Bond> = all_bonds.iter().collect();
+    bonds.sort_by(|a, b| a.id.cmp(&b.id));
+    let bonds = bonds.into_iter();
+
+    match (layout, format) {
+        (Layout::Zip, _) => write_zip(bonds, format, writer),
+        (Layout::Long, Format::Csv) => write_long_csv(bonds, writer),
+        (Layout::Long, Format::Json) => write_json(bonds.flat_map(long_json), writer),
+        (Layout::Long, Format::Ndjson) => write_ndjson(bonds.flat_map(long_json), writer),
+        (Layout::Long, Format::Parquet) => columnar::write_long_parquet(bonds, writer),
+        (Layout::Long, Format::Arrow) => columnar::write_long_arrow(bonds, writer),
+        (Layout::Long, Format::Xlsx) => unreachable!("Rejected by Layout::validate"),
+    }
+}
+
+fn write_long_csv<'a>(bonds: impl Iterator<Item = &'a Bond>, mut writer: impl Write) -> Result<()> {
+    writer.write_all(b"bond_id,date,value\n")?;
+    for bond in bonds {
+        for (date, value) in bond.series() {
+            writeln!(writer, "{},{},{}", bond.id, date.format("%Y-%m-%d"), value)?;
+        }
+    }
+    Ok(writer.flush()?)
+}
+
+fn long_json(bond: &Bond) -> impl Iterator<Item = serde_json::Value> + '_ {
+    series_json(bond).map(|mut value| {
+        value["bond_id"] = bond.id.as_str().into();
+        value
+    })
+}
+
+/// The archive is written without seeking, every entry is followed by a data descriptor
+fn write_zip<'a>(
+    bonds: impl Iterator<Item = &'a Bond>,
+    format: Format,
+    writer: impl Write + Send,
+) -> Result<()> {
+    // Workbooks are ZIP archives already
+    let compression = match format {
+        Format::Xlsx => CompressionMethod::Stored,
+        _ => CompressionMethod::Deflated,
+    };
+    let options = SimpleFileOptions::default().compression_method(compression);
+
+    let mut zip = ZipWriter::new_stream(writer);
+    for bond in bonds {
+        zip.start_file(format!("{}.{}", bond.id, format.name()), options)?;
+        export_to(bond, format, &CsvOptions::default(), &mut zip)?;
+    }
+    zip.finish()?.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::bond;
+    use chrono::NaiveDate;
+    use model::BondId;
+    use pretty_assertions::assert_eq;
+    use std::io::{Cursor, Read};
+
+    fn all_bonds() -> AllBonds {
+        let date = "2025-09-01".parse::<NaiveDate>().unwrap();
+        let other = Bond::builder()
+            .id(BondId::new("COI0929").unwrap())
+            .initial_date(date)
+            .sale_end(date)
+            .buyout_date(date)
+            .values(vec![100.0, 100.03])
+            .build();
+        [bond(), other].into_iter().collect()
+    }
+
+    fn export_to_vec(layout: Layout, format: Format) -> Vec<u8> {
+        let mut exported = Vec::new();
+        export_all(&all_bonds(), layout, format, &mut exported).unwrap();
+        exported
+    }
+
+    #[test]
+    fn test_parse_layout() {
+        assert_eq!("ZIP".parse::<Layout>().unwrap(), Layout::Zip);
+        assert_eq!(
+            "wide".parse::<Layout>().unwrap_err().to_string(),
+            "Unknown layout wide, expected one of long, zip"
+        );
+    }
+
+    #[test]
+    fn test_long_csv_sorted_by_bond_id() {
+        assert_eq!(
+            String::from_utf8(export_to_vec(Layout::Long, Format::Csv)).unwrap(),
+            "bond_id,date,value\n\
+             COI0929,2025-09-01,100\n\
+             COI0929,2025-09-02,100.03\n\
+             EDO0835,2025-08-01,100\n\
+             EDO0835,2025-08-02,100.02\n\
+             EDO0835,2025-08-03,100.04\n"
+        );
+    }
+
+    #[test]
+    fn test_long_ndjson() {
+        let ndjson = String::from_utf8(export_to_vec(Layout::Long, Format::Ndjson)).unwrap();
+
+        assert_eq!(
+            ndjson.lines().next().unwrap(),
+            r#"{"bond_id":"COI0929","date":"2025-09-01","value":100.0}"#
+        );
+        assert_eq!(ndjson.lines().count(), 5);
+    }
+
+    #[test]
+    fn test_long_json_is_single_array() {
+        let json: Vec<serde_json::Value> =
+            serde_json::from_slice(&export_to_vec(Layout::Long, Format::Json)).unwrap();
+
+        assert_eq!(json.len(), 5);
+        assert_eq!(
+            json[4],
+            serde_json::json!({"bond_id": "EDO0835", "date": "2025-08-03", "value": 100.04})
+        );
+    }
+
+    #[test]
+    fn test_long_parquet_has_row_group_per_bond() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let parquet = bytes::Bytes::from(export_to_vec(Layout::Long, Format::Parquet));
+        let metadata = SerializedFileReader::new(parquet)
+            .unwrap()
+            .metadata()
+            .clone();
+
+        assert_eq!(metadata.num_row_groups(), 2);
+        assert_eq!(metadata.file_metadata().num_rows(), 5);
+        assert_eq!(
+            metadata.file_metadata().schema_descr().column(0).name(),
+            "bond_id"
+        );
+    }
+
+    #[test]
+    fn test_zip_has_file_per_bond() {
+        let exported = export_to_vec(Layout::Zip, Format::Csv);
+        let mut archive = zip::ZipArchive::new(Cursor::new(exported)).unwrap();
+
+        assert_eq!(
+            archive.file_names().collect::<Vec<_>>(),
+            vec!["COI0929.csv", "EDO0835.csv"]
+        );
+        let mut csv = String::new();
+        archive
+            .by_name("EDO0835.csv")
+            .unwrap()
+            .read_to_string(&mut csv)
+            .unwrap();
+        assert_eq!(csv, bond().to_csv());
+    }
+
+    #[test]
+    fn test_reject_long_xlsx() {
+        assert_eq!(
+            export_all(&all_bonds(), Layout::Long, Format::Xlsx, Vec::new())
+                .unwrap_err()
+                .to_string(),
+            "xlsx exports are only available with the zip layout"
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use anyhow::Result;
+use arrow_array::{Date32Array, Float64Array, RecordBatch, StringArray};
+use arrow_ipc::writer::FileWriter;
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use chrono::NaiveDate;
+use model::Bond;
+use parquet::arrow::ArrowWriter;
+use std::io::Write;
+use std::sync::Arc;
+
+/// `Date32` counts the days since the Unix epoch
+const EPOCH: NaiveDate = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+
+fn record_batch(bond: &Bond) -> Result<RecordBatch> {
+    let schema = Schema::new(vec![
+        Field::new("date", DataType::Date32, false),
+        Field::new("value", DataType::Float64, false),
+    ]);
+    let dates: Date32Array = bond
+        .series()
+        .map(|(date, _)| (date - EPOCH).num_days() as i32)
+        .collect::<Vec<_>>()
+        .into();
+    let values = Float64Array::from(bond.values.clone());
+    Ok(RecordBatch::try_new(
+        Arc::new(schema),
+        vec![Arc::new(dates), Arc::new(values)],
+    )?)
+}
+
+/// Schema of bulk exports, the bond ID in front of the columns of [`record_batch`]
+fn long_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("bond_id", DataType::Utf8, false),
+        Field::new("date", DataType::Date32, false),
+        Field::new("value", DataType::Float64, false),
+    ]))
+}
+
+fn long_record_batch(bond: &Bond) -> Result<RecordBatch> {
+    let batch = record_batch(bond)?;
+    let ids = StringArray::from(vec![bond.id.as_str(); batch.num_rows()]);
+    Ok(RecordBatch::try_new(
+        long_schema(),
+        vec![
+            Arc::new(ids),
+            batch.column(0).clone(),
+            batch.column(1).clone(),
+        ],
+    )?)
+}
+
+pub(crate) fn to_parquet(bond: &Bond) -> Result<Vec<u8>> {
+    let batch = record_batch(bond)?;
+    let mut writer = ArrowWriter::try_new(Vec::new(), batch.schema(), None)?;
+    writer.write(&batch)?;
+    Ok(writer.into_inner()?)
+}
+
+/// Arrow IPC file, the random access variant that `pyarrow.ipc.open_file` and polars read
+pub(crate) fn to_arrow(bond: &Bond) -> Result<Vec<u8>> {
+    let batch = record_batch(bond)?;
+    let mut writer = FileWriter::try_new(Vec::new(), &batch.schema())?;
+    writer.write(&batch)?;
+    writer.finish()?;
+    Ok(writer.into_inner()?)
+}
+
+/// Every bond in a single Parquet file, flushing a row group per bond so that only one bond is
+/// buffered at a time
+pub(crate) fn write_long_parquet<'a, W: Write + Send>(
+    bonds: impl Iterator<Item = &'a Bond>,
+    writer: W,
+) -> Result<()> {
+    let mut writer = ArrowWriter::try_new(writer, long_schema(), None)?;
+    for bond in bonds {
+        writer.write(&long_record_batch(bond)?)?;
+        writer.flush()?;
+    }
+    writer.close()?;
+    Ok(())
+}
+
+/// Every bond in a single Arrow IPC file, a record batch per bond
+pub(crate) fn write_long_arrow<'a, W: Write>(
+    bonds: impl Iterator<Item = &'a Bond>,
+    writer: W,
+) -> Result<()> {
+    let mut writer = FileWriter::try_new(writer, &long_schema())?;
+    for bond in bonds {
+        writer.write(&long_record_batch(bond)?)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::bond;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Date32Type, Float64Type};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use pretty_assertions::assert_eq;
+    use std::io::Cursor;
+
+    fn columns(batch: &RecordBatch) -> (Vec<NaiveDate>, Vec<f64>) {
+        let dates = batch.column(0).as_primitive::<Date32Type>();
+        let values = batch.column(1).as_primitive::<Float64Type>();
+        (
+            dates
+                .values()
+                .iter()
+                .map(|days| EPOCH + chrono::Duration::days(i64::from(*days)))
+                .collect(),
+            values.values().to_vec(),
+        )
+    }
+
+    #[test]
+    fn test_parquet_round_trip() {
+        let bond = bond();
+        let parquet = bytes::Bytes::from(to_parquet(&bond).unwrap());
+
+        let batches = ParquetRecordBatchReaderBuilder::try_new(parquet)
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(batches.len(), 1);
+        assert_eq!(columns(&batches[0]), bond.series().unzip());
+    }
+
+    #[test]
+    fn test_arrow_round_trip() {
+        let bond = bond();
+        let arrow = to_arrow(&bond).unwrap();
+
+        let batches = arrow_ipc::reader::FileReader::try_new(Cursor::new(arrow), None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(batches.len(), 1);
+        assert_eq!(columns(&batches[0]), bond.series().unzip());
+    }
+}
@@ -0,0 +1,273 @@
+use anyhow::{Result, bail};
+use chrono::NaiveDate;
+use chrono::format::StrftimeItems;
+use model::Bond;
+use std::fmt::Write as _;
+use std::io::Write;
+use std::str::FromStr;
+
+/// Columns a CSV export can have
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Column {
+    Date,
+    Value,
+    /// Yearly interest period, starting from 1
+    Period,
+    /// Interest accrued since purchase, the value less the nominal
+    AccruedInterest,
+}
+
+impl Column {
+    pub const ALL: [Column; 4] = [
+        Column::Date,
+        Column::Value,
+        Column::Period,
+        Column::AccruedInterest,
+    ];
+
+    /// Name of the column in the `columns` parameter and the header row
+    pub fn name(self) -> &'static str {
+        match self {
+            Column::Date => "date",
+            Column::Value => "value",
+            Column::Period => "period",
+            Column::AccruedInterest => "accrued_interest",
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        match Column::ALL
+            .into_iter()
+            .find(|column| column.name().eq_ignore_ascii_case(name))
+        {
+            Some(column) => Ok(column),
+            None => bail!(
+                "Unknown column {name}, expected one of {}",
+                Column::ALL.map(Column::name).join(", ")
+            ),
+        }
+    }
+}
+
+/// CSV dialect, by default the one of [`Bond::to_csv`]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CsvOptions {
+    pub delimiter: char,
+    /// `.` or `,`, the latter being what spreadsheets expect in Polish and most European locales
+    pub decimal_separator: char,
+    /// `strftime` format of the dates
+    pub date_format: String,
+    pub columns: Vec<Column>,
+    /// Names in the header row, one for every column. The names of the columns when empty
+    pub header: Vec<String>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            decimal_separator: '.',
+            date_format: "%Y-%m-%d".to_string(),
+            columns: vec![Column::Date, Column::Value],
+            header: Vec::new(),
+        }
+    }
+}
+
+impl CsvOptions {
+    pub fn validate(&self) -> Result<()> {
+        if matches!(self.delimiter, '"' | '\r' | '\n') {
+            bail!("Delimiter cannot be {:?}", self.delimiter);
+        }
+        if !matches!(self.decimal_separator, '.' | ',') {
+            bail!(
+                "Decimal separator has to be . or , not {}",
+                self.decimal_separator
+            );
+        }
+        if self.delimiter == self.decimal_separator {
+            bail!(
+                "Delimiter and decimal separator cannot both be {}",
+                self.delimiter
+            );
+        }
+        // Time specifiers are valid strftime, but cannot be formatted from a date
+        let mut sample = String::new();
+        let items = StrftimeItems::new(&self.date_format).parse();
+        if items.is_err()
+            || write!(
+                sample,
+                "{}",
+                NaiveDate::MIN.format_with_items(items.iter().flatten())
+            )
+            .is_err()
+        {
+            bail!("Invalid date format {}", self.date_format);
+        }
+        if self.columns.is_empty() {
+            bail!("At least one column is needed");
+        }
+        if !self.header.is_empty() && self.header.len() != self.columns.len() {
+            bail!(
+                "Header has {} names for {} columns",
+                self.header.len(),
+                self.columns.len()
+            );
+        }
+        Ok(())
+    }
+
+    fn push_field(&self, csv: &mut String, index: usize, field: &str) {
+        if index > 0 {
+            csv.push(self.delimiter);
+        }
+        if field.contains([self.delimiter, '"', '\r', '\n']) {
+            csv.push('"');
+            csv.push_str(&field.replace('"', "\"\""));
+            csv.push('"');
+        } else {
+            csv.push_str(field);
+        }
+    }
+
+    fn number(&self, value: f64) -> String {
+        let number = value.to_string();
+        if self.decimal_separator == '.' {
+            number
+        } else {
+            number.replace('.', &self.decimal_separator.to_string())
+        }
+    }
+}
+
+/// Writes the header and then the bond row by row
+pub(crate) fn write_csv(bond: &Bond, options: &CsvOptions, mut writer: impl Write) -> Result<()> {
+    options.validate()?;
+    let header: Vec<&str> = if options.header.is_empty() {
+        options.columns.iter().map(|column| column.name()).collect()
+    } else {
+        options.header.iter().map(String::as_str).collect()
+    };
+    let mut line = String::new();
+    for (index, name) in header.into_iter().enumerate() {
+        options.push_field(&mut line, index, name);
+    }
+    line.push('\n');
+    writer.write_all(line.as_bytes())?;
+
+    let nominal = bond.nominal().unwrap_or_default();
+    for (date, value) in bond.series() {
+        line.clear();
+        for (index, column) in options.columns.iter().enumerate() {
+            let field = match column {
+                Column::Date => date.format(&options.date_format).to_string(),
+                Column::Value => options.number(value),
+                Column::Period => bond
+                    .period_on(date)
+                    .map(|period| period.to_string())
+                    .unwrap_or_default(),
+                Column::AccruedInterest => {
+                    options.number(((value - nominal) * 100.0).round() / 100.0)
+                }
+            };
+            options.push_field(&mut line, index, &field);
+        }
+        line.push('\n');
+        writer.write_all(line.as_bytes())?;
+    }
+    Ok(writer.flush()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::bond;
+    use pretty_assertions::assert_eq;
+
+    fn to_csv(bond: &Bond, options: &CsvOptions) -> Result<String> {
+        let mut csv = Vec::new();
+        write_csv(bond, options, &mut csv)?;
+        Ok(String::from_utf8(csv)?)
+    }
+
+    #[test]
+    fn test_default_options_match_bond_csv() {
+        let bond = bond();
+
+        assert_eq!(
+            to_csv(&bond, &CsvOptions::default()).unwrap(),
+            bond.to_csv()
+        );
+    }
+
+    #[test]
+    fn test_polish_spreadsheet_dialect() {
+        let options = CsvOptions {
+            delimiter: ';',
+            decimal_separator: ',',
+            date_format: "%d.%m.%Y".to_string(),
+            columns: vec![
+                Column::Period,
+                Column::Date,
+                Column::Value,
+                Column::AccruedInterest,
+            ],
+            header: ["okres", "data", "wartość", "odsetki; narosłe"]
+                .map(str::to_string)
+                .into(),
+        };
+
+        assert_eq!(
+            to_csv(&bond(), &options).unwrap(),
+            "okres;data;wartość;\"odsetki; narosłe\"\n\
+             1;01.08.2025;100;0\n\
+             1;02.08.2025;100,02;0,02\n\
+             1;03.08.2025;100,04;0,04\n"
+        );
+    }
+
+    #[test]
+    fn test_reject_invalid_options() {
+        let errors: Vec<_> = [
+            CsvOptions {
+                delimiter: ',',
+                decimal_separator: ',',
+                ..CsvOptions::default()
+            },
+            CsvOptions {
+                decimal_separator: ';',
+                ..CsvOptions::default()
+            },
+            CsvOptions {
+                date_format: "%Y-%m-%d %H:%M".to_string(),
+                ..CsvOptions::default()
+            },
+            CsvOptions {
+                date_format: "%Q".to_string(),
+                ..CsvOptions::default()
+            },
+            CsvOptions {
+                header: vec!["day".to_string()],
+                ..CsvOptions::default()
+            },
+        ]
+        .iter()
+        .map(|options| options.validate().unwrap_err().to_string())
+        .collect();
+
+        assert_eq!(
+            errors,
+            vec![
+                "Delimiter and decimal separator cannot both be ,",
+                "Decimal separator has to be . or , not ;",
+                "Invalid date format %Y-%m-%d %H:%M",
+                "Invalid date format %Q",
+                "Header has 1 names for 2 columns",
+            ]
+        );
+    }
+}
@@ -0,0 +1,247 @@
+mod bulk;
+mod columnar;
+mod csv;
+mod spreadsheet;
+
+pub use bulk::{Layout, export_all};
+pub use csv::{Column, CsvOptions};
+
+use anyhow::{Result, bail};
+use model::Bond;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use std::str::FromStr;
+
+/// File formats the daily values of a bond can be exported in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Format {
+    Csv,
+    Json,
+    Ndjson,
+    Parquet,
+    Arrow,
+    Xlsx,
+}
+
+impl Format {
+    /// Every format, the first one being the default when the client has no preference
+    pub const ALL: [Format; 6] = [
+        Format::Csv,
+        Format::Json,
+        Format::Ndjson,
+        Format::Parquet,
+        Format::Arrow,
+        Format::Xlsx,
+    ];
+
+    /// Name of the format in the `format` parameter and on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Json => "json",
+            Format::Ndjson => "ndjson",
+            Format::Parquet => "parquet",
+            Format::Arrow => "arrow",
+            Format::Xlsx => "xlsx",
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Format::Csv => "text/csv",
+            Format::Json => "application/json",
+            Format::Ndjson => "application/x-ndjson",
+            Format::Parquet => "application/vnd.apache.parquet",
+            Format::Arrow => "application/vnd.apache.arrow.file",
+            Format::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        }
+    }
+
+    /// Format with the highest weight in the `Accept` value, `None` when the client accepts none
+    /// of them
+    pub fn negotiate(accept: &str) -> Option<Self> {
+        let weights: Vec<(&str, f32)> = accept
+            .split(',')
+            .filter_map(|item| {
+                let mut parameters = item.split(';');
+                let range = parameters.next()?.trim();
+                let weight = parameters
+                    .find_map(|parameter| parameter.trim().strip_prefix("q="))
+                    .map_or(Some(1.0), |weight| weight.trim().parse().ok())?;
+                (!range.is_empty()).then_some((range, weight))
+            })
+            .collect();
+        // The most specific range matching the content type decides its weight
+        let weight = |content_type: &str| {
+            let (kind, _) = content_type.split_once('/').unwrap_or_default();
+            let matching = |range: &str| {
+                weights
+                    .iter()
+                    .find(|(accepted, _)| accepted.eq_ignore_ascii_case(range))
+            };
+            matching(content_type)
+                .or_else(|| matching(&format!("{kind}/*")))
+                .or_else(|| matching("*/*"))
+                .map_or(0.0, |(_, weight)| *weight)
+        };
+
+        let mut best = None;
+        let mut best_weight = 0.0;
+        for format in Format::ALL {
+            let weight = weight(format.content_type());
+            if weight > best_weight {
+                best = Some(format);
+                best_weight = weight;
+            }
+        }
+        best
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        match Format::ALL
+            .into_iter()
+            .find(|format| format.name().eq_ignore_ascii_case(name))
+        {
+            Some(format) => Ok(format),
+            None => bail!(
+                "Unknown format {name}, expected one of {}",
+                Format::ALL.map(Format::name).join(", ")
+            ),
+        }
+    }
+}
+
+/// Daily values of the bond in the given format, with a date and a value column. The columns of
+/// CSV exports are picked with `csv`, which is ignored by the other formats.
+pub fn export(bond: &Bond, format: Format, csv: &CsvOptions) -> Result<Vec<u8>> {
+    let mut exported = Vec::new();
+    export_to(bond, format, csv, &mut exported)?;
+    Ok(exported)
+}
+
+/// Writes what [`export`] returns. The text formats are written value by value, Parquet, Arrow
+/// and workbooks are put together in memory first.
+pub fn export_to<W: Write + Send>(
+    bond: &Bond,
+    format: Format,
+    csv: &CsvOptions,
+    mut writer: W,
+) -> Result<()> {
+    match format {
+        Format::Csv => csv::write_csv(bond, csv, writer),
+        Format::Json => write_json(series_json(bond), writer),
+        Format::Ndjson => write_ndjson(series_json(bond), writer),
+        Format::Parquet => Ok(writer.write_all(&columnar::to_parquet(bond)?)?),
+        Format::Arrow => Ok(writer.write_all(&columnar::to_arrow(bond)?)?),
+        Format::Xlsx => Ok(writer.write_all(&spreadsheet::to_xlsx(bond)?)?),
+    }
+}
+
+fn series_json(bond: &Bond) -> impl Iterator<Item = serde_json::Value> + '_ {
+    bond.series()
+        .map(|(date, value)| serde_json::json!({ "date": date, "value": value }))
+}
+
+/// Array of the values, written one at a time
+fn write_json(
+    values: impl Iterator<Item = serde_json::Value>,
+    mut writer: impl Write,
+) -> Result<()> {
+    writer.write_all(b"[")?;
+    for (index, value) in values.enumerate() {
+        if index > 0 {
+            writer.write_all(b",")?;
+        }
+        serde_json::to_writer(&mut writer, &value)?;
+    }
+    writer.write_all(b"]")?;
+    Ok(writer.flush()?)
+}
+
+fn write_ndjson(
+    values: impl Iterator<Item = serde_json::Value>,
+    mut writer: impl Write,
+) -> Result<()> {
+    for value in values {
+        serde_json::to_writer(&mut writer, &value)?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(writer.flush()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use model::BondId;
+    use pretty_assertions::assert_eq;
+
+    pub(crate) fn bond() -> Bond {
+        let date = "2025-08-01".parse::<NaiveDate>().unwrap();
+        Bond::builder()
+            .id(BondId::new("EDO0835").unwrap())
+            .initial_date(date)
+            .sale_end(date)
+            .buyout_date(date)
+            .values(vec![100.0, 100.02, 100.04])
+            .build()
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!("xlsx".parse::<Format>().unwrap(), Format::Xlsx);
+        assert_eq!("NDJSON".parse::<Format>().unwrap(), Format::Ndjson);
+        assert_eq!(
+            "xls".parse::<Format>().unwrap_err().to_string(),
+            "Unknown format xls, expected one of csv, json, ndjson, parquet, arrow, xlsx"
+        );
+    }
+
+    #[test]
+    fn test_negotiate_format() {
+        assert_eq!(Format::negotiate("*/*"), Some(Format::Csv));
+        assert_eq!(Format::negotiate("application/json"), Some(Format::Json));
+        assert_eq!(
+            Format::negotiate("text/csv;q=0.5, application/vnd.apache.parquet"),
+            Some(Format::Parquet)
+        );
+        assert_eq!(
+            Format::negotiate("application/*;q=0.5, application/x-ndjson"),
+            Some(Format::Ndjson)
+        );
+        assert_eq!(
+            Format::negotiate("text/csv;q=0, */*;q=0.1"),
+            Some(Format::Json)
+        );
+        assert_eq!(Format::negotiate("text/html, image/*"), None);
+    }
+
+    #[test]
+    fn test_export_text() {
+        let bond = bond();
+
+        assert_eq!(
+            String::from_utf8(export(&bond, Format::Json, &CsvOptions::default()).unwrap())
+                .unwrap(),
+            r#"[{"date":"2025-08-01","value":100.0},{"date":"2025-08-02","value":100.02},{"date":"2025-08-03","value":100.04}]"#
+        );
+        assert_eq!(
+            String::from_utf8(export(&bond, Format::Ndjson, &CsvOptions::default()).unwrap())
+                .unwrap(),
+            "{\"date\":\"2025-08-01\",\"value\":100.0}\n\
+             {\"date\":\"2025-08-02\",\"value\":100.02}\n\
+             {\"date\":\"2025-08-03\",\"value\":100.04}\n"
+        );
+    }
+}
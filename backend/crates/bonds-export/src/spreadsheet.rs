@@ -0,0 +1,34 @@
+use anyhow::Result;
+use model::Bond;
+use rust_xlsxwriter::{Format, Workbook};
+
+pub(crate) fn to_xlsx(bond: &Bond) -> Result<Vec<u8>> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name(bond.id.as_str())?;
+    let header = Format::new().set_bold();
+    worksheet.write_string_with_format(0, 0, "date", &header)?;
+    worksheet.write_string_with_format(0, 1, "value", &header)?;
+    worksheet.set_freeze_panes(1, 0)?;
+    worksheet.set_column_width(0, 12)?;
+
+    let date = Format::new().set_num_format("yyyy-mm-dd");
+    for (row, (day, value)) in (1..).zip(bond.series()) {
+        worksheet.write_datetime_with_format(row, 0, day, &date)?;
+        worksheet.write_number(row, 1, value)?;
+    }
+    Ok(workbook.save_to_buffer()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::bond;
+
+    #[test]
+    fn test_xlsx_is_zip_archive() {
+        let xlsx = to_xlsx(&bond()).unwrap();
+
+        assert!(xlsx.starts_with(b"PK\x03\x04"));
+    }
+}
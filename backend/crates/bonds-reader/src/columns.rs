@@ -0,0 +1,197 @@
+use anyhow::{Result, bail};
+use calamine::{Data, Range};
+use model::BondType;
+
+const SERIES_HEADER: &str = "Seria";
+const SALE_START_HEADER: &str = "Początek sprzedaży";
+const SALE_END_HEADER: &str = "Koniec sprzedaży";
+/// Merged over the yearly rate columns, which are named in the row below
+const RATES_HEADER: &str = "Oprocentowanie";
+
+/// Header rows are at the top of the sheet, anything further down is bond data
+const MAX_HEADER_ROW: usize = 5;
+
+/// Positions of the columns we read, located from the sheet's header rows
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ColumnMapping {
+    pub(crate) id: usize,
+    pub(crate) sale_start: usize,
+    pub(crate) sale_end: usize,
+    /// Column of the rate for every year of the term, in order
+    pub(crate) rates: Vec<usize>,
+}
+
+impl ColumnMapping {
+    /// Fails when any of the expected headers is missing, as reading shifted columns would produce garbage
+    pub(crate) fn locate(range: &Range<Data>, bond_type: BondType) -> Result<Self> {
+        let Some(header_row) = range
+            .rows()
+            .take(MAX_HEADER_ROW)
+            .position(|row| find(row, SERIES_HEADER).is_some())
+        else {
+            bail!("Header [{SERIES_HEADER}] not found on sheet [{bond_type}]");
+        };
+        let header = range.rows().nth(header_row).unwrap_or_default();
+        let column = |name: &str| {
+            find(header, name)
+                .ok_or_else(|| anyhow::anyhow!("Header [{name}] not found on sheet [{bond_type}]"))
+        };
+
+        let id = column(SERIES_HEADER)?;
+        let sale_start = column(SALE_START_HEADER)?;
+        let sale_end = column(SALE_END_HEADER)?;
+        let rates_start = column(RATES_HEADER)?;
+
+        let years = (bond_type.term_in_months() / 12) as usize;
+        let year_headers = range.rows().nth(header_row + 1).unwrap_or_default();
+        let mut rates = Vec::with_capacity(years);
+        for year in 1..=years {
+            match year_headers
+                .iter()
+                .enumerate()
+                .skip(rates_start)
+                .find(|(_, cell)| rate_year(cell) == Some(year))
+            {
+                Some((column, _)) => rates.push(column),
+                None => bail!(
+                    "Header for the rate in year {year} not found under [{RATES_HEADER}] on sheet [{bond_type}]"
+                ),
+            }
+        }
+
+        Ok(Self {
+            id,
+            sale_start,
+            sale_end,
+            rates,
+        })
+    }
+}
+
+fn find(row: &[Data], name: &str) -> Option<usize> {
+    row.iter().position(|cell| match cell {
+        Data::String(value) => value.trim().eq_ignore_ascii_case(name),
+        _ => false,
+    })
+}
+
+/// Year of a rate header, which reads like " w 1. roku"
+fn rate_year(cell: &Data) -> Option<usize> {
+    let Data::String(value) = cell else {
+        return None;
+    };
+    value
+        .trim()
+        .strip_prefix("w ")?
+        .strip_suffix(". roku")?
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn headers(rows: &[&[&str]]) -> Range<Data> {
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(1);
+        let mut range = Range::new((0, 0), (rows.len() as u32, width as u32 - 1));
+        for (row_id, row) in rows.iter().enumerate() {
+            for (column, value) in row.iter().enumerate() {
+                if !value.is_empty() {
+                    range.set_value(
+                        (row_id as u32, column as u32),
+                        Data::String(value.to_string()),
+                    );
+                }
+            }
+        }
+        range
+    }
+
+    const ISSUER_HEADER: &[&str] = &[
+        "Seria",
+        "Kod ISIN",
+        "Data wykupu",
+        "Początek sprzedaży",
+        "Koniec sprzedaży",
+        "Cena emisyjna",
+        "Cena zamiany",
+        "Sprzedaż łączna \n(mln zł)",
+        "w tym zamiana (mln zł)",
+        "Oprocentowanie",
+    ];
+
+    fn year_headers(skip: usize, years: usize) -> Vec<String> {
+        std::iter::repeat_n(String::new(), skip)
+            .chain((1..=years).map(|year| format!(" w {year}. roku")))
+            .collect()
+    }
+
+    #[test]
+    fn test_locate_issuer_layout() {
+        let years = year_headers(9, 10);
+        let years: Vec<&str> = years.iter().map(String::as_str).collect();
+        let range = headers(&[ISSUER_HEADER, &years]);
+
+        let mapping = ColumnMapping::locate(&range, BondType::EDO).expect("Should locate columns");
+
+        assert_eq!(
+            mapping,
+            ColumnMapping {
+                id: 0,
+                sale_start: 3,
+                sale_end: 4,
+                rates: (9..19).collect(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_locate_inserted_column() {
+        let mut header = ISSUER_HEADER.to_vec();
+        header.insert(2, "Nowa kolumna");
+        let years = year_headers(10, 12);
+        let years: Vec<&str> = years.iter().map(String::as_str).collect();
+        let range = headers(&[&["Obligacje rodzinne"], &header, &years]);
+
+        let mapping = ColumnMapping::locate(&range, BondType::ROD).expect("Should locate columns");
+
+        assert_eq!(
+            mapping,
+            ColumnMapping {
+                id: 0,
+                sale_start: 4,
+                sale_end: 5,
+                rates: (10..22).collect(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_locate_missing_headers() {
+        let years = year_headers(9, 10);
+        let years: Vec<&str> = years.iter().map(String::as_str).collect();
+        let mut header = ISSUER_HEADER.to_vec();
+        header[4] = "Koniec";
+
+        let missing_column = ColumnMapping::locate(&headers(&[&header, &years]), BondType::EDO);
+        let missing_year =
+            ColumnMapping::locate(&headers(&[ISSUER_HEADER, &years[..18]]), BondType::EDO);
+        let missing_header = ColumnMapping::locate(&headers(&[&years]), BondType::EDO);
+
+        assert_eq!(
+            missing_column.unwrap_err().to_string(),
+            "Header [Koniec sprzedaży] not found on sheet [EDO]"
+        );
+        assert_eq!(
+            missing_year.unwrap_err().to_string(),
+            "Header for the rate in year 10 not found under [Oprocentowanie] on sheet [EDO]"
+        );
+        assert_eq!(
+            missing_header.unwrap_err().to_string(),
+            "Header [Seria] not found on sheet [EDO]"
+        );
+    }
+}
@@ -0,0 +1,293 @@
+use anyhow::{Context, Result, bail};
+use chrono::NaiveDate;
+use model::{AllBonds, Bond, BondId};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Starts every dataset, so that a workbook or some other file is not mistaken for one
+const MAGIC: &[u8; 4] = b"BNDS";
+
+/// Version of the dataset layout.
+///
+/// Bump it whenever a change makes previously written datasets unreadable. Version 1 was the
+/// JSON document of [`AllBonds`].
+const DATASET_FORMAT_VERSION: u32 = 2;
+
+/// Everything about a bond but its daily values, which follow the header
+#[derive(Serialize, Deserialize)]
+struct BondHeader {
+    id: BondId,
+    initial_date: NaiveDate,
+    sale_end: NaiveDate,
+    buyout_date: NaiveDate,
+    rates: Vec<f64>,
+    /// First value of the series, the only one that may have more than two decimal places
+    nominal: f64,
+    /// Number of daily values
+    days: usize,
+}
+
+/// Writes already parsed bonds as a dataset artifact, which can be loaded much faster than the workbook.
+///
+/// The artifact starts with [`MAGIC`] and the format version as a little-endian `u32`, followed
+/// by the length of a JSON header describing every bond, sorted by ID, and the header itself.
+/// Then come the daily values of the bonds in the same order, from the second one on. Values are
+/// rounded to grosze, so they are kept as the difference in grosze from the previous value,
+/// zigzag and LEB128 encoded, which takes a byte for most days.
+pub fn write_dataset<P: AsRef<Path>>(all_bonds: &AllBonds, path: P) -> Result<()> {
+    replace_file(path.as_ref(), |writer| {
+        write_dataset_to_writer(all_bonds, writer)
+    })
+    .context("Failed to write dataset file")
+}
+
+/// Writes a file next to `path` and renames it into place, so that a crash while writing leaves
+/// the previous file rather than a truncated one
+pub(crate) fn replace_file(
+    path: &Path,
+    write: impl FnOnce(&mut dyn Write) -> Result<()>,
+) -> Result<()> {
+    let directory = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let mut file = tempfile::NamedTempFile::new_in(directory)?;
+    let mut writer = BufWriter::new(&mut file);
+    write(&mut writer)?;
+    writer.flush()?;
+    drop(writer);
+    file.as_file().sync_all()?;
+    file.persist(path)?;
+    Ok(())
+}
+
+fn write_dataset_to_writer<W: Write>(all_bonds: &AllBonds, mut writer: W) -> Result<()> {
+    let mut bonds: Vec<&Bond> = all_bonds.iter().collect();
+    bonds.sort_by(|a, b| a.id.cmp(&b.id));
+    let headers: Vec<BondHeader> = bonds
+        .iter()
+        .map(|bond| BondHeader {
+            id: bond.id.clone(),
+            initial_date: bond.initial_date,
+            sale_end: bond.sale_end,
+            buyout_date: bond.buyout_date,
+            rates: bond.rates.clone(),
+            nominal: bond.nominal().unwrap_or_default(),
+            days: bond.values.len(),
+        })
+        .collect();
+    let header = serde_json::to_vec(&headers).context("Failed to serialize dataset")?;
+
+    let mut encoded = Vec::new();
+    for bond in bonds {
+        let mut previous = 0;
+        for (day, &value) in bond.values.iter().enumerate().skip(1) {
+            let grosze = (value * 100.0).round() as i64;
+            if grosze as f64 / 100.0 != value {
+                bail!(
+                    "Value {value} of {} on day {day} has more than two decimal places",
+                    bond.id
+                );
+            }
+            write_varint(&mut encoded, zigzag(grosze - previous));
+            previous = grosze;
+        }
+    }
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&DATASET_FORMAT_VERSION.to_le_bytes())?;
+    writer.write_all(&u64::try_from(header.len())?.to_le_bytes())?;
+    writer.write_all(&header)?;
+    writer
+        .write_all(&encoded)
+        .context("Failed to write dataset file")?;
+    Ok(())
+}
+
+/// Reads a dataset artifact created by [`write_dataset`]
+pub fn read_dataset<P: AsRef<Path>>(path: P) -> Result<AllBonds> {
+    let file = File::open(path.as_ref()).context("Failed to open dataset file")?;
+    read_dataset_from_reader(BufReader::new(file))
+}
+
+/// Same as [`read_dataset`], for a dataset that is not a file, e.g. one embedded in the binary
+pub fn read_dataset_from_reader<R: Read>(mut reader: R) -> Result<AllBonds> {
+    let mut content = Vec::new();
+    reader
+        .read_to_end(&mut content)
+        .context("Failed to read dataset")?;
+    parse_dataset(&content).context("Failed to deserialize dataset")
+}
+
+fn parse_dataset(content: &[u8]) -> Result<AllBonds> {
+    let mut content = content.strip_prefix(MAGIC).context("Not a bonds dataset")?;
+    let format_version = u32::from_le_bytes(take(&mut content)?);
+    if format_version != DATASET_FORMAT_VERSION {
+        bail!(
+            "Unsupported dataset format version [{format_version}], expected [{DATASET_FORMAT_VERSION}]"
+        );
+    }
+    let header_length = usize::try_from(u64::from_le_bytes(take(&mut content)?))?;
+    if header_length > content.len() {
+        bail!("Truncated dataset header");
+    }
+    let (header, mut values) = content.split_at(header_length);
+    let headers: Vec<BondHeader> = serde_json::from_slice(header)?;
+
+    let mut all_bonds = AllBonds::default();
+    for header in headers {
+        let mut series = Vec::with_capacity(header.days);
+        if header.days > 0 {
+            series.push(header.nominal);
+        }
+        let mut grosze = 0;
+        for _ in 1..header.days {
+            grosze += unzigzag(read_varint(&mut values)?);
+            series.push(grosze as f64 / 100.0);
+        }
+        all_bonds.insert(
+            Bond::builder()
+                .id(header.id)
+                .initial_date(header.initial_date)
+                .sale_end(header.sale_end)
+                .buyout_date(header.buyout_date)
+                .rates(header.rates)
+                .values(series)
+                .build(),
+        );
+    }
+    if !values.is_empty() {
+        bail!("Unexpected {} bytes after the last value", values.len());
+    }
+    Ok(all_bonds)
+}
+
+fn take<const N: usize>(content: &mut &[u8]) -> Result<[u8; N]> {
+    let Some((bytes, rest)) = content.split_first_chunk::<N>() else {
+        bail!("Truncated dataset");
+    };
+    *content = rest;
+    Ok(*bytes)
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+fn write_varint(encoded: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        encoded.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    encoded.push(value as u8);
+}
+
+fn read_varint(content: &mut &[u8]) -> Result<u64> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let [byte] = take(content).context("Truncated dataset values")?;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    bail!("Invalid dataset value")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_bonds;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_dataset_roundtrip() {
+        let all_bonds =
+            read_bonds("../../tests/fixtures/bonds/test.xls").expect("Should read bonds");
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("bonds.dataset");
+
+        write_dataset(&all_bonds, &path).expect("Should write dataset");
+        let dataset = read_dataset(&path).expect("Should read dataset");
+
+        assert_eq!(dataset, all_bonds);
+    }
+
+    #[test]
+    fn test_keep_previous_file_when_write_fails() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("versions.json");
+        std::fs::write(&path, "[]").unwrap();
+
+        let result = replace_file(&path, |writer| {
+            writer.write_all(b"[{")?;
+            bail!("Interrupted")
+        });
+
+        assert!(result.is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[]");
+        assert_eq!(std::fs::read_dir(directory.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_dataset_takes_about_a_byte_per_value() {
+        let all_bonds =
+            read_bonds("../../tests/fixtures/bonds/test.xls").expect("Should read bonds");
+        let values: usize = all_bonds.iter().map(|bond| bond.values.len()).sum();
+
+        let mut dataset = Vec::new();
+        write_dataset_to_writer(&all_bonds, &mut dataset).expect("Should write dataset");
+
+        assert!(dataset.len() < values * 2, "{} bytes", dataset.len());
+    }
+
+    #[test]
+    fn test_varint_roundtrip() {
+        let values = [0, 1, -1, 63, -64, 64, 10_000, i64::MAX, i64::MIN];
+        let mut encoded = Vec::new();
+        for value in values {
+            write_varint(&mut encoded, zigzag(value));
+        }
+
+        let mut content = encoded.as_slice();
+        let decoded: Vec<i64> = values
+            .iter()
+            .map(|_| unzigzag(read_varint(&mut content).unwrap()))
+            .collect();
+
+        assert_eq!(decoded, values);
+        assert!(content.is_empty());
+    }
+
+    #[test]
+    fn test_reject_dataset_with_other_format_version() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("bonds.dataset");
+        std::fs::write(&path, b"BNDS\x01\x00\x00\x00").unwrap();
+
+        let error = read_dataset(&path).unwrap_err();
+
+        assert_eq!(
+            format!("{error:#}"),
+            "Failed to deserialize dataset: Unsupported dataset format version [1], expected [2]"
+        );
+    }
+
+    #[test]
+    fn test_reject_json_dataset() {
+        let dataset = br#"{"format_version":1,"bonds":[]}"#;
+
+        let error = read_dataset_from_reader(&dataset[..]).unwrap_err();
+
+        assert_eq!(
+            format!("{error:#}"),
+            "Failed to deserialize dataset: Not a bonds dataset"
+        );
+    }
+}
@@ -0,0 +1,367 @@
+use crate::sources::BondPatch;
+use crate::{DEFAULT_NOMINAL, SUPPORTED_BOND_TYPES, build_bond, table};
+use anyhow::{Context, Result, bail};
+use chrono::NaiveDate;
+use itertools::Itertools;
+use model::{AllBonds, Bond, BondId, BondType};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+const DEFINITION_COLUMNS: [&str; 6] =
+    ["id", "type", "initial_date", "sale_end", "nominal", "rates"];
+
+/// A series defined by hand instead of being read from the issuer workbook, e.g. one that was
+/// announced before the workbook got updated, or a what-if bond
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BondDefinition {
+    pub id: BondId,
+    /// Redundant with the ID, checked against it when given
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub bond_type: Option<BondType>,
+    /// First day of sale
+    pub initial_date: NaiveDate,
+    /// Last day of sale
+    pub sale_end: NaiveDate,
+    #[serde(default = "default_nominal")]
+    pub nominal: f64,
+    /// Interest rate for every year of the term as a fraction, later years may be left out
+    pub rates: Vec<f64>,
+}
+
+fn default_nominal() -> f64 {
+    DEFAULT_NOMINAL
+}
+
+/// Top level of TOML and JSON definition files
+#[derive(Deserialize)]
+struct DefinitionsFile {
+    bonds: Vec<BondDefinition>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DefinitionFormat {
+    Toml,
+    Json,
+    Csv,
+}
+
+impl DefinitionFormat {
+    /// Tells the format from the file extension
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Ok(DefinitionFormat::Toml),
+            Some("json") => Ok(DefinitionFormat::Json),
+            Some("csv") => Ok(DefinitionFormat::Csv),
+            _ => bail!(
+                "Unknown bond definitions format of [{}], expected a .toml, .json or .csv file",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl BondDefinition {
+    /// Checks the definition describes a bond the value generator can calculate
+    pub fn validate(&self) -> Result<()> {
+        let bond_type = self.id.bond_type();
+        if let Some(declared) = self.bond_type
+            && declared != bond_type
+        {
+            bail!("Series {} is of type {bond_type}, not {declared}", self.id);
+        }
+        if !SUPPORTED_BOND_TYPES.contains(&bond_type) {
+            bail!(
+                "Bond type {bond_type} of {} is not supported, expected one of {}",
+                self.id,
+                SUPPORTED_BOND_TYPES.iter().join(", ")
+            );
+        }
+        if self.sale_end < self.initial_date {
+            bail!(
+                "Last day of sale {} is before the first one {} for {}",
+                self.sale_end,
+                self.initial_date,
+                self.id
+            );
+        }
+        if self.nominal.is_nan() || self.nominal <= 0.0 {
+            bail!(
+                "Nominal of {} must be positive, got {}",
+                self.id,
+                self.nominal
+            );
+        }
+        let years = (bond_type.term_in_months() / 12) as usize;
+        if self.rates.is_empty() || self.rates.len() > years {
+            bail!(
+                "Series {} needs between 1 and {years} yearly rates, got {}",
+                self.id,
+                self.rates.len()
+            );
+        }
+        Ok(())
+    }
+
+    /// Calculates the daily values the same way as for the series read from the workbook
+    pub fn to_bond(&self) -> Bond {
+        build_bond(
+            self.id.clone(),
+            self.initial_date,
+            self.sale_end,
+            self.nominal,
+            self.rates.clone(),
+        )
+    }
+}
+
+impl From<&BondDefinition> for BondPatch {
+    fn from(definition: &BondDefinition) -> Self {
+        Self {
+            id: definition.id.clone(),
+            initial_date: Some(definition.initial_date),
+            sale_end: Some(definition.sale_end),
+            nominal: Some(definition.nominal),
+            rates: Some(definition.rates.clone()),
+        }
+    }
+}
+
+/// Reads and validates bond definitions.
+///
+/// TOML and JSON files hold a `bonds` array of [`BondDefinition`]s. CSV files have a header
+/// naming the same fields, with `type` and `nominal` being optional and the rates separated by `;`.
+pub fn read_definitions<R: Read>(
+    mut reader: R,
+    format: DefinitionFormat,
+) -> Result<Vec<BondDefinition>> {
+    let definitions = match format {
+        DefinitionFormat::Toml => {
+            let mut content = String::new();
+            reader
+                .read_to_string(&mut content)
+                .context("Failed to read bond definitions")?;
+            let mut document: toml::Value =
+                toml::from_str(&content).context("Failed to deserialize bond definitions")?;
+            dates_to_strings(&mut document);
+            document
+                .try_into::<DefinitionsFile>()
+                .context("Failed to deserialize bond definitions")?
+                .bonds
+        }
+        DefinitionFormat::Json => {
+            serde_json::from_reader::<_, DefinitionsFile>(reader)
+                .context("Failed to deserialize bond definitions")?
+                .bonds
+        }
+        DefinitionFormat::Csv => read_csv_definitions(reader)?,
+    };
+
+    for definition in &definitions {
+        definition.validate()?;
+    }
+    if let Some(duplicate) = definitions.iter().map(|d| &d.id).duplicates().next() {
+        bail!("Duplicate series {duplicate} in bond definitions");
+    }
+    Ok(definitions)
+}
+
+/// Reads a definitions file into bonds, telling its format from the extension
+pub fn read_bond_definitions<P: AsRef<Path>>(path: P) -> Result<AllBonds> {
+    let path = path.as_ref();
+    let format = DefinitionFormat::from_path(path)?;
+    let file = File::open(path).context("Failed to open bond definitions")?;
+    let definitions = read_definitions(BufReader::new(file), format)?;
+    Ok(definitions.iter().map(BondDefinition::to_bond).collect())
+}
+
+/// TOML dates are not strings, which is what [`NaiveDate`] deserializes from
+fn dates_to_strings(value: &mut toml::Value) {
+    match value {
+        toml::Value::Datetime(datetime) => *value = toml::Value::String(datetime.to_string()),
+        toml::Value::Array(values) => values.iter_mut().for_each(dates_to_strings),
+        toml::Value::Table(table) => table
+            .iter_mut()
+            .for_each(|(_, value)| dates_to_strings(value)),
+        _ => {}
+    }
+}
+
+fn read_csv_definitions<R: Read>(reader: R) -> Result<Vec<BondDefinition>> {
+    let rows = table::read_rows(
+        reader,
+        "bond definitions",
+        &DEFINITION_COLUMNS,
+        &["id", "initial_date", "sale_end", "rates"],
+    )?;
+
+    let mut definitions = Vec::new();
+    for row in rows {
+        let required = |column: &str| {
+            row.get(column)
+                .with_context(|| format!("Missing value of [{column}]"))
+        };
+        let parse = || -> Result<BondDefinition> {
+            Ok(BondDefinition {
+                id: BondId::new(required("id")?)?,
+                bond_type: row.get("type").map(str::parse).transpose()?,
+                initial_date: required("initial_date")?.parse()?,
+                sale_end: required("sale_end")?.parse()?,
+                nominal: row
+                    .get("nominal")
+                    .map(str::parse)
+                    .transpose()?
+                    .unwrap_or(DEFAULT_NOMINAL),
+                rates: table::parse_rates(required("rates")?)?,
+            })
+        };
+        definitions
+            .push(parse().with_context(|| format!("Invalid bond definitions line {}", row.line))?);
+    }
+    Ok(definitions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_bonds;
+    use pretty_assertions::assert_eq;
+
+    const FIXTURES: &str = "../../tests/fixtures/bonds";
+
+    fn definition() -> BondDefinition {
+        BondDefinition {
+            id: BondId::new("EDO1135").unwrap(),
+            bond_type: Some(BondType::EDO),
+            initial_date: "2025-11-01".parse().unwrap(),
+            sale_end: "2025-11-30".parse().unwrap(),
+            nominal: 100.0,
+            rates: vec![0.06],
+        }
+    }
+
+    #[test]
+    fn test_read_definitions_in_every_format() {
+        let expected = vec![
+            definition(),
+            BondDefinition {
+                id: BondId::new("ROD0837").unwrap(),
+                bond_type: None,
+                initial_date: "2025-08-01".parse().unwrap(),
+                sale_end: "2025-08-31".parse().unwrap(),
+                nominal: 1000.0,
+                rates: vec![0.0625, 0.058],
+            },
+        ];
+
+        for extension in ["toml", "json", "csv"] {
+            let path = format!("{FIXTURES}/definitions.{extension}");
+            let file = File::open(&path).unwrap();
+
+            let definitions = read_definitions(file, DefinitionFormat::from_path(&path).unwrap())
+                .unwrap_or_else(|e| panic!("Should read {path}: {e:#}"));
+
+            assert_eq!(definitions, expected, "{path}");
+        }
+    }
+
+    #[test]
+    fn test_definition_matches_workbook_bond() {
+        let workbook = read_bonds(format!("{FIXTURES}/test.xls")).expect("Should read bonds");
+        let bond_id = BondId::new("ROD0837").unwrap();
+        let expected = workbook.get(&bond_id).unwrap();
+
+        let definition = BondDefinition {
+            id: bond_id,
+            bond_type: None,
+            initial_date: expected.initial_date,
+            sale_end: expected.sale_end,
+            nominal: DEFAULT_NOMINAL,
+            rates: expected.rates.clone(),
+        };
+
+        assert_eq!(&definition.to_bond(), expected);
+    }
+
+    #[test]
+    fn test_read_bond_definitions() {
+        let all_bonds =
+            read_bond_definitions(format!("{FIXTURES}/definitions.toml")).expect("Should read");
+
+        let bond = all_bonds.get(&BondId::new("ROD0837").unwrap()).unwrap();
+        assert_eq!(bond.values[0], 1000.0);
+        assert_eq!(bond.buyout_date, "2037-08-01".parse().unwrap());
+    }
+
+    #[test]
+    fn test_invalid_definitions() {
+        let error = |change: fn(&mut BondDefinition)| {
+            let mut definition = definition();
+            change(&mut definition);
+            definition.validate().unwrap_err().to_string()
+        };
+
+        assert_eq!(
+            error(|d| d.bond_type = Some(BondType::ROD)),
+            "Series EDO1135 is of type EDO, not ROD"
+        );
+        assert_eq!(
+            error(|d| {
+                d.id = BondId::new("OTS0226").unwrap();
+                d.bond_type = None;
+            }),
+            "Bond type OTS of OTS0226 is not supported, expected one of EDO, ROD"
+        );
+        assert_eq!(
+            error(|d| d.sale_end = "2025-10-31".parse().unwrap()),
+            "Last day of sale 2025-10-31 is before the first one 2025-11-01 for EDO1135"
+        );
+        assert_eq!(
+            error(|d| d.nominal = 0.0),
+            "Nominal of EDO1135 must be positive, got 0"
+        );
+        assert_eq!(
+            error(|d| d.rates = vec![0.06; 11]),
+            "Series EDO1135 needs between 1 and 10 yearly rates, got 11"
+        );
+    }
+
+    #[test]
+    fn test_read_invalid_csv_definitions() {
+        let error = |csv: &str| {
+            format!(
+                "{:#}",
+                read_definitions(csv.as_bytes(), DefinitionFormat::Csv).unwrap_err()
+            )
+        };
+
+        assert_eq!(
+            error("id,initial_date,sale_end\n"),
+            "Missing bond definitions column [rates]"
+        );
+        assert_eq!(
+            error("id,initial_date,sale_end,rates\nEDO1135,2025-11-01,,0.06\n"),
+            "Invalid bond definitions line 2: Missing value of [sale_end]"
+        );
+        assert_eq!(
+            error(
+                "id,initial_date,sale_end,rates\n\
+                 EDO1135,2025-11-01,2025-11-30,0.06\n\
+                 EDO1135,2025-11-01,2025-11-30,0.06\n"
+            ),
+            "Duplicate series EDO1135 in bond definitions"
+        );
+    }
+
+    #[test]
+    fn test_unknown_definitions_format() {
+        assert_eq!(
+            DefinitionFormat::from_path("bonds.yaml")
+                .unwrap_err()
+                .to_string(),
+            "Unknown bond definitions format of [bonds.yaml], expected a .toml, .json or .csv file"
+        );
+    }
+}
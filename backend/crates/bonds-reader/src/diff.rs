@@ -0,0 +1,291 @@
+use model::{AllBonds, Bond, BondId};
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+
+/// What changed between two versions of the issuer workbook
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct BondsDiff {
+    pub added: Vec<BondId>,
+    pub removed: Vec<BondId>,
+    pub new_rates: Vec<NewRates>,
+    pub changed: Vec<HistoricalChange>,
+}
+
+/// Yearly rates published since the old version
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct NewRates {
+    pub id: BondId,
+    /// 1-based year of the first new rate
+    pub from_year: usize,
+    pub rates: Vec<f64>,
+}
+
+/// Previously published value that is different in the new version
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct HistoricalChange {
+    pub id: BondId,
+    pub field: String,
+    pub old: String,
+    pub new: String,
+}
+
+impl BondsDiff {
+    pub fn between(old: &AllBonds, new: &AllBonds) -> Self {
+        let mut diff = BondsDiff::default();
+
+        for new_bond in new.iter() {
+            match old.get(&new_bond.id) {
+                Some(old_bond) => diff.compare(old_bond, new_bond),
+                None => diff.added.push(new_bond.id.clone()),
+            }
+        }
+        diff.removed = old
+            .iter()
+            .filter(|bond| new.get(&bond.id).is_none())
+            .map(|bond| bond.id.clone())
+            .collect();
+
+        diff.added.sort();
+        diff.removed.sort();
+        diff.new_rates.sort_by(|a, b| a.id.cmp(&b.id));
+        diff.changed.sort_by(|a, b| a.id.cmp(&b.id));
+        diff
+    }
+
+    /// Series disappeared or values that were already published moved
+    pub fn historical_data_changed(&self) -> bool {
+        !self.removed.is_empty() || !self.changed.is_empty()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &BondsDiff::default()
+    }
+
+    fn compare(&mut self, old: &Bond, new: &Bond) {
+        let changes_before = self.changed.len();
+        let mut change = |field: &str, old_value: String, new_value: String| {
+            self.changed.push(HistoricalChange {
+                id: new.id.clone(),
+                field: field.to_string(),
+                old: old_value,
+                new: new_value,
+            });
+        };
+
+        for (field, old_date, new_date) in [
+            ("initial_date", old.initial_date, new.initial_date),
+            ("sale_end", old.sale_end, new.sale_end),
+            ("buyout_date", old.buyout_date, new.buyout_date),
+        ] {
+            if old_date != new_date {
+                change(field, old_date.to_string(), new_date.to_string());
+            }
+        }
+        for (year, (old_rate, new_rate)) in old.rates.iter().zip(&new.rates).enumerate() {
+            if (old_rate - new_rate).abs() > RATE_TOLERANCE {
+                change(
+                    &format!("rate in year {}", year + 1),
+                    format_rate(*old_rate),
+                    format_rate(*new_rate),
+                );
+            }
+        }
+        if new.rates.len() < old.rates.len() {
+            change(
+                "published rates",
+                old.rates.len().to_string(),
+                new.rates.len().to_string(),
+            );
+        }
+
+        // Values follow from the dates and rates, only report them when nothing else explains the change
+        if self.changed.len() == changes_before
+            && let Some(day) = old
+                .values
+                .iter()
+                .zip(&new.values)
+                .position(|(old_value, new_value)| old_value != new_value)
+        {
+            self.changed.push(HistoricalChange {
+                id: new.id.clone(),
+                field: format!(
+                    "value on {}",
+                    old.initial_date + chrono::Duration::days(day as i64)
+                ),
+                old: old.values[day].to_string(),
+                new: new.values[day].to_string(),
+            });
+        }
+
+        if new.rates.len() > old.rates.len() {
+            self.new_rates.push(NewRates {
+                id: new.id.clone(),
+                from_year: old.rates.len() + 1,
+                rates: new.rates[old.rates.len()..].to_vec(),
+            });
+        }
+    }
+}
+
+/// Rates read from different workbooks may differ by rounding errors, which are not changes
+const RATE_TOLERANCE: f64 = 1e-9;
+
+/// Percentage with at least two decimal places, and more when the rate has them
+fn format_rate(rate: f64) -> String {
+    let percent = format!("{:.6}", rate * 100.0);
+    let decimals = percent.trim_end_matches('0');
+    let (integer, fraction) = decimals.split_once('.').unwrap_or((decimals, ""));
+    format!("{integer}.{fraction:0<2}%")
+}
+
+impl Display for BondsDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes");
+        }
+
+        let ids = |ids: &[BondId]| match ids {
+            [] => "-".to_string(),
+            ids => ids
+                .iter()
+                .map(BondId::as_str)
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        writeln!(
+            f,
+            "Added series ({}): {}",
+            self.added.len(),
+            ids(&self.added)
+        )?;
+        writeln!(
+            f,
+            "Removed series ({}): {}",
+            self.removed.len(),
+            ids(&self.removed)
+        )?;
+
+        writeln!(f, "New yearly rates ({}):", self.new_rates.len())?;
+        for new_rates in &self.new_rates {
+            let rates = new_rates
+                .rates
+                .iter()
+                .map(|rate| format_rate(*rate))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(
+                f,
+                "  {} from year {}: {}",
+                new_rates.id, new_rates.from_year, rates
+            )?;
+        }
+
+        writeln!(f, "Changed historical data ({}):", self.changed.len())?;
+        for change in &self.changed {
+            writeln!(
+                f,
+                "  {} {}: {} -> {}",
+                change.id, change.field, change.old, change.new
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use insta::assert_snapshot;
+    use pretty_assertions::assert_eq;
+
+    fn bond(id: &str, rates: Vec<f64>) -> Bond {
+        let date = |d: &str| d.parse::<NaiveDate>().unwrap();
+        Bond::builder()
+            .id(BondId::new(id).unwrap())
+            .initial_date(date("2024-08-01"))
+            .sale_end(date("2024-08-31"))
+            .buyout_date(date("2034-08-01"))
+            .values(vec![100.0; rates.len() + 1])
+            .rates(rates)
+            .build()
+    }
+
+    #[test]
+    fn test_diff_unchanged() {
+        let bonds: AllBonds = [bond("EDO0834", vec![0.068])].into_iter().collect();
+
+        let diff = BondsDiff::between(&bonds, &bonds);
+
+        assert_eq!(diff, BondsDiff::default());
+        assert_eq!(diff.to_string(), "No changes\n");
+    }
+
+    #[test]
+    fn test_diff_new_series_and_rates() {
+        let old: AllBonds = [bond("EDO0834", vec![0.068])].into_iter().collect();
+        let new: AllBonds = [
+            bond("EDO0834", vec![0.068, 0.0595]),
+            bond("EDO0835", vec![0.0595]),
+        ]
+        .into_iter()
+        .collect();
+
+        let diff = BondsDiff::between(&old, &new);
+
+        assert!(!diff.historical_data_changed());
+        assert_snapshot!(diff.to_string());
+    }
+
+    #[test]
+    fn test_diff_changed_history() {
+        let old: AllBonds = [
+            bond("EDO0834", vec![0.068, 0.0595]),
+            bond("ROD0834", vec![0.07]),
+        ]
+        .into_iter()
+        .collect();
+        let new: AllBonds = [bond("EDO0834", vec![0.068, 0.06])].into_iter().collect();
+
+        let diff = BondsDiff::between(&old, &new);
+
+        assert!(diff.historical_data_changed());
+        assert_snapshot!(diff.to_string());
+        assert_eq!(
+            serde_json::to_value(&diff).unwrap(),
+            serde_json::json!({
+                "added": [],
+                "removed": ["ROD0834"],
+                "new_rates": [],
+                "changed": [
+                    {
+                        "id": "EDO0834",
+                        "field": "rate in year 2",
+                        "old": "5.95%",
+                        "new": "6.00%"
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_diff_rate_changed_in_third_decimal() {
+        let old: AllBonds = [bond("EDO0834", vec![0.068, 0.0595])].into_iter().collect();
+        let new: AllBonds = [bond("EDO0834", vec![0.068 + 1e-12, 0.05955])]
+            .into_iter()
+            .collect();
+
+        let diff = BondsDiff::between(&old, &new);
+
+        assert_eq!(
+            diff.changed,
+            vec![HistoricalChange {
+                id: BondId::new("EDO0834").unwrap(),
+                field: "rate in year 2".to_string(),
+                old: "5.95%".to_string(),
+                new: "5.955%".to_string(),
+            }]
+        );
+    }
+}
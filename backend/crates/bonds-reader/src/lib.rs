@@ -1,113 +1,220 @@
+mod columns;
+mod dataset;
+mod definitions;
+mod diff;
+mod sources;
+mod table;
+mod validation;
 mod value_generator;
+mod versions;
 
-use anyhow::{Context, Error, Result, bail};
+pub use dataset::{read_dataset, read_dataset_from_reader, write_dataset};
+pub use definitions::{BondDefinition, DefinitionFormat, read_bond_definitions, read_definitions};
+pub use diff::{BondsDiff, HistoricalChange, NewRates};
+pub use sources::{BondPatch, MergedBonds, Provenance, read_corrections};
+pub use validation::{
+    Diagnostic, Severity, ValidationReport, validate_workbook, validate_workbook_from_reader,
+};
+pub use versions::{DatasetVersion, DatasetVersions, bonds_hash, read_provenance};
+
+use anyhow::{Context, Error, Result};
 use calamine::Data::{Float, String};
-use calamine::{DataType, Reader, Xls};
-use chrono::Datelike;
-use model::{AllBonds, Bond, BondId};
+use calamine::{Data, DataType, Range, Reader, Sheets};
+use chrono::{Datelike, NaiveDate};
+use columns::ColumnMapping;
+use model::{AllBonds, Bond, BondId, BondType};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
-use std::collections::HashMap;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, Cursor, Read};
 use std::path::Path;
 
+/// Bond types with yearly capitalized interest, which are the ones the value generator supports
+pub const SUPPORTED_BOND_TYPES: [BondType; 2] = [BondType::EDO, BondType::ROD];
+
+/// Nominal value of a single bond in PLN, the same for every series in the issuer workbook
+pub const DEFAULT_NOMINAL: f64 = 100.0;
+
+/// Workbook in any of the formats the issuer or a spreadsheet application may save it in
+type Workbook = Sheets<Cursor<Vec<u8>>>;
+
+/// Number of bonds read from a single sheet and the range of their sale dates
+#[derive(Clone, Debug, PartialEq)]
+pub struct SheetStatistics {
+    pub bond_type: BondType,
+    pub bonds: usize,
+    pub first_sale: Option<NaiveDate>,
+    pub last_sale: Option<NaiveDate>,
+}
+
+impl SheetStatistics {
+    fn new(bond_type: BondType, bonds: &[Bond]) -> Self {
+        Self {
+            bond_type,
+            bonds: bonds.len(),
+            first_sale: bonds.iter().map(|bond| bond.initial_date).min(),
+            last_sale: bonds.iter().map(|bond| bond.sale_end).max(),
+        }
+    }
+}
+
+/// Opens an XLS, XLSX or ODS workbook, telling the format from the content rather than the file extension.
+///
+/// Telling the format takes several passes over the content, so the workbook is read into memory
+/// first and the reader only needs to be read once.
+fn open_workbook<R: Read>(mut reader: R) -> Result<Workbook> {
+    let mut content = Vec::new();
+    reader
+        .read_to_end(&mut content)
+        .context("Failed to open workbook")?;
+    calamine::open_workbook_auto_from_rs(Cursor::new(content)).context("Failed to open workbook")
+}
+
+fn open_workbook_file(path: &Path) -> Result<BufReader<File>> {
+    let file = File::open(path).context("Failed to open workbook")?;
+    Ok(BufReader::new(file))
+}
+
 pub fn read_bonds<P: AsRef<Path>>(path: P) -> Result<AllBonds> {
-    let mut workbook: Xls<_> =
-        calamine::open_workbook(path.as_ref()).context("Failed to open workbook")?;
+    read_bonds_from_reader(open_workbook_file(path.as_ref())?)
+}
 
-    let edo = extract_bond_type(&mut workbook, "EDO", 10)?;
-    let rod = extract_bond_type(&mut workbook, "ROD", 12)?;
+/// Same as [`read_bonds`], for a workbook that is not a file, e.g. an upload or embedded bytes
+pub fn read_bonds_from_reader<R: Read>(reader: R) -> Result<AllBonds> {
+    read_bonds_with_statistics_from_reader(reader).map(|(all_bonds, _)| all_bonds)
+}
+
+pub fn read_bonds_from_bytes(workbook: &[u8]) -> Result<AllBonds> {
+    read_bonds_from_reader(workbook)
+}
+
+/// Same as [`read_bonds`], additionally reporting what was found on every supported sheet
+pub fn read_bonds_with_statistics<P: AsRef<Path>>(
+    path: P,
+) -> Result<(AllBonds, Vec<SheetStatistics>)> {
+    read_bonds_with_statistics_from_reader(open_workbook_file(path.as_ref())?)
+}
+
+pub fn read_bonds_with_statistics_from_reader<R: Read>(
+    reader: R,
+) -> Result<(AllBonds, Vec<SheetStatistics>)> {
+    let mut workbook = open_workbook(reader)?;
 
-    let all_bonds = AllBonds { edo, rod };
+    let mut all_bonds = AllBonds::default();
+    let mut statistics = Vec::new();
+    for bond_type in SUPPORTED_BOND_TYPES {
+        let bonds = extract_bond_type(&mut workbook, bond_type)?;
+        statistics.push(SheetStatistics::new(bond_type, &bonds));
+        for bond in bonds {
+            all_bonds.insert(bond);
+        }
+    }
 
-    Ok(all_bonds)
+    Ok((all_bonds, statistics))
 }
 
-fn extract_bond_type(
-    workbook: &mut Xls<BufReader<File>>,
-    bond_type: &str,
-    bond_length_in_years: u8,
-) -> Result<HashMap<BondId, Bond>, Error> {
+fn extract_bond_type(workbook: &mut Workbook, bond_type: BondType) -> Result<Vec<Bond>, Error> {
     let range = workbook
-        .worksheet_range(bond_type)
+        .worksheet_range(bond_type.code())
         .context(format!("Failed to get worksheet [{}]", bond_type))?;
 
-    let mut bonds = HashMap::new();
+    let columns = ColumnMapping::locate(&range, bond_type)?;
+    let mut bonds = Vec::new();
 
-    for (row_id, row) in range.rows().enumerate() {
-        let first_cell = row.first();
-        if let Some(cell) = first_cell
-            && let String(value) = cell
-            && value.starts_with(bond_type)
-        {
-            let sale_start = if let Some(date_time) = row.get(3).and_then(|f| f.as_datetime()) {
-                date_time
-            } else {
-                bail!(
-                    "Cannot extract date from cell [{:?}], row id: [{}], column: 3",
-                    row.get(3),
-                    row_id
-                )
-            };
-
-            let sale_end = if let Some(date_time) = row.get(4).and_then(|f| f.as_datetime()) {
-                date_time
-            } else {
-                bail!(
-                    "Cannot extract date from cell [{:?}], row id: [{}], column: 4",
-                    row.get(4),
-                    row_id
-                )
-            };
-
-            let bond_id = if let Some(String(bond_id)) = row.first() {
-                BondId::new(bond_id.as_str())
-            } else {
-                bail!("Cannot extract bond ID from cell [{:?}]", row.first())
-            };
-
-            let buyout_date = sale_start
-                .with_year(sale_start.year() + bond_length_in_years as i32)
-                .unwrap();
-
-            let mut generator = value_generator::ValueGenerator::new(100f64);
-
-            for i in 9..(9 + bond_length_in_years) {
-                if let Some(cell) = row.get(i as usize)
-                    && let Float(value) = cell
-                {
-                    let d = Decimal::from_f64_retain(*value).unwrap().round_dp(5);
-                    generator.add_yearly_return(d.to_f64().unwrap())
-                }
-            }
+    for (row_id, row, bond_id) in series_rows(&range, &columns, bond_type) {
+        let date_in = |column: usize| match row.get(column).and_then(|f| f.as_date()) {
+            Some(date) => Ok(date),
+            None => Err(anyhow::anyhow!(
+                "Cannot extract date from cell [{:?}] at {}!{}",
+                row.get(column),
+                bond_type,
+                validation::cell_reference(&range, row_id, column)
+            )),
+        };
+        let sale_start = date_in(columns.sale_start)?;
+        let sale_end = date_in(columns.sale_end)?;
 
-            let bond = Bond::builder()
-                .id(bond_id.clone())
-                .initial_date(sale_start.date())
-                .buyout_date(buyout_date.date())
-                .sale_end(sale_end.date())
-                .values(generator.calculate_daily_bond_values(sale_start.clone().date()))
-                .build();
-
-            bonds.insert(bond_id, bond);
+        let mut rates = Vec::new();
+        for &i in &columns.rates {
+            if let Some(cell) = row.get(i)
+                && let Float(value) = cell
+            {
+                rates.push(*value);
+            }
         }
+
+        let bond = build_bond(bond_id, sale_start, sale_end, DEFAULT_NOMINAL, rates);
+        bonds.push(bond);
     }
     Ok(bonds)
 }
 
+/// Derives the buyout date and daily values of a series from its sale dates and yearly rates
+fn build_bond(
+    id: BondId,
+    sale_start: NaiveDate,
+    sale_end: NaiveDate,
+    nominal: f64,
+    rates: Vec<f64>,
+) -> Bond {
+    let bond_length_in_years = (id.bond_type().term_in_months() / 12) as i32;
+    let buyout_date = sale_start
+        .with_year(sale_start.year() + bond_length_in_years)
+        .unwrap();
+
+    let mut generator = value_generator::ValueGenerator::new(nominal);
+    let rates: Vec<f64> = rates
+        .into_iter()
+        .map(|rate| {
+            let d = Decimal::from_f64_retain(rate).unwrap().round_dp(5);
+            d.to_f64().unwrap()
+        })
+        .collect();
+    for &rate in &rates {
+        generator.add_yearly_return(rate);
+    }
+
+    Bond::builder()
+        .id(id)
+        .initial_date(sale_start)
+        .buyout_date(buyout_date)
+        .sale_end(sale_end)
+        .values(generator.calculate_daily_bond_values(sale_start))
+        .rates(rates)
+        .build()
+}
+
+/// Rows of a sheet with an ID of a series of the given type
+fn series_rows<'a>(
+    range: &'a Range<Data>,
+    columns: &ColumnMapping,
+    bond_type: BondType,
+) -> impl Iterator<Item = (usize, &'a [Data], BondId)> {
+    let id_column = columns.id;
+    range.rows().enumerate().filter_map(move |(row_id, row)| {
+        if let Some(String(value)) = row.get(id_column)
+            && let Ok(bond_id) = BondId::new(value.as_str())
+            && bond_id.bond_type() == bond_type
+        {
+            Some((row_id, row, bond_id))
+        } else {
+            None
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use insta::assert_debug_snapshot;
+    use pretty_assertions::assert_eq;
 
     #[test]
     fn test_read_rod1235bond() {
         let path = "../../assets/Dane_dotyczace_obligacji_detalicznych.xls";
         let result = read_bonds(path).expect("Should read bondss");
-        let rod1235bond_id = BondId::new("ROD1235");
+        let rod1235bond_id = BondId::new("ROD1235").unwrap();
         let rod1235bond = result
-            .rod
             .get(&rod1235bond_id)
             .expect("Should find ROD1235 bond");
 
@@ -119,9 +226,8 @@ mod tests {
     fn test_read_edo1224bond() {
         let path = "../../assets/Dane_dotyczace_obligacji_detalicznych.xls";
         let result = read_bonds(path).expect("Should read bonds");
-        let edo1224bond_id = BondId::new("EDO1224");
+        let edo1224bond_id = BondId::new("EDO1224").unwrap();
         let edo1224bond = result
-            .edo
             .get(&edo1224bond_id)
             .expect("Should find edo1224 bond");
 
@@ -132,11 +238,61 @@ mod tests {
     fn test_read_edo0125bind() {
         let path = "../../assets/Dane_dotyczace_obligacji_detalicznych.xls";
         let result = read_bonds(path).expect("Should read bonds");
-        let edo0125bond_id = BondId::new("EDO0125");
+        let edo0125bond_id = BondId::new("EDO0125").unwrap();
         let edo0125bond = result
-            .edo
             .get(&edo0125bond_id)
             .expect("Should find edo0125 bond");
         assert_debug_snapshot!(edo0125bond);
     }
+
+    #[test]
+    fn test_read_sheet_statistics() {
+        let path = "../../tests/fixtures/bonds/test.xls";
+        let (_, statistics) = read_bonds_with_statistics(path).expect("Should read bonds");
+
+        assert_debug_snapshot!(statistics);
+    }
+
+    #[test]
+    fn test_read_xlsx_and_ods() {
+        let xls = read_bonds("../../tests/fixtures/bonds/test.xls").expect("Should read xls");
+        let xlsx = read_bonds("../../tests/fixtures/bonds/test.xlsx").expect("Should read xlsx");
+        let ods = read_bonds("../../tests/fixtures/bonds/test.ods").expect("Should read ods");
+
+        assert_eq!(xlsx, xls);
+        assert_eq!(ods, xls);
+    }
+
+    #[test]
+    fn test_read_from_bytes() {
+        let workbook = include_bytes!("../../../tests/fixtures/bonds/test.xlsx");
+
+        let result = read_bonds_from_bytes(workbook).expect("Should read bonds");
+
+        assert_eq!(
+            result,
+            read_bonds("../../tests/fixtures/bonds/test.xls").expect("Should read bonds")
+        );
+    }
+
+    #[test]
+    fn test_read_format_regardless_of_extension() {
+        let renamed = tempfile::Builder::new()
+            .suffix(".xls")
+            .tempfile()
+            .expect("Should create temporary file");
+        std::fs::copy("../../tests/fixtures/bonds/test.ods", renamed.path())
+            .expect("Should copy workbook");
+
+        let result = read_bonds(renamed.path());
+
+        assert!(result.is_ok(), "{result:?}");
+    }
+
+    #[test]
+    fn test_read_unknown_format() {
+        let result = read_bonds("Cargo.toml");
+
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,370 @@
+use crate::{BondDefinition, DEFAULT_NOMINAL, table};
+use anyhow::{Context, Result, bail};
+use chrono::NaiveDate;
+use model::{AllBonds, Bond, BondId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+
+const CORRECTION_COLUMNS: [&str; 4] = ["id", "initial_date", "sale_end", "rates"];
+
+/// Name of the source that supplied every field of a bond
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Source that introduced the series
+    pub series: String,
+    pub initial_date: String,
+    pub sale_end: String,
+    pub nominal: String,
+    pub rates: String,
+}
+
+impl Provenance {
+    fn new(source: &str) -> Self {
+        Self {
+            series: source.to_string(),
+            initial_date: source.to_string(),
+            sale_end: source.to_string(),
+            nominal: source.to_string(),
+            rates: source.to_string(),
+        }
+    }
+}
+
+/// Data a source has about a series, `None` fields are left to the sources below it
+#[derive(Clone, Debug, PartialEq)]
+pub struct BondPatch {
+    pub id: BondId,
+    pub initial_date: Option<NaiveDate>,
+    pub sale_end: Option<NaiveDate>,
+    pub nominal: Option<f64>,
+    pub rates: Option<Vec<f64>>,
+}
+
+impl From<&Bond> for BondPatch {
+    fn from(bond: &Bond) -> Self {
+        Self {
+            id: bond.id.clone(),
+            initial_date: Some(bond.initial_date),
+            sale_end: Some(bond.sale_end),
+            nominal: bond.values.first().copied(),
+            rates: Some(bond.rates.clone()),
+        }
+    }
+}
+
+/// Bonds of several sources merged into one catalogue, each source taking precedence over the
+/// ones applied before it
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MergedBonds {
+    pub all_bonds: AllBonds,
+    pub provenance: HashMap<BondId, Provenance>,
+}
+
+impl MergedBonds {
+    /// Starts from the bonds of the source with the lowest precedence
+    pub fn new(source: &str, all_bonds: AllBonds) -> Self {
+        let provenance = all_bonds
+            .iter()
+            .map(|bond| (bond.id.clone(), Provenance::new(source)))
+            .collect();
+        Self {
+            all_bonds,
+            provenance,
+        }
+    }
+
+    /// Overrides what was merged so far with the fields the source has, values are recalculated.
+    ///
+    /// Every patched bond has to pass the same checks as the hand-written definitions.
+    pub fn apply(&mut self, source: &str, patches: Vec<BondPatch>) -> Result<()> {
+        for patch in patches {
+            let (definition, provenance) = match self.all_bonds.get(&patch.id) {
+                Some(bond) => {
+                    let mut provenance = self.provenance[&patch.id].clone();
+                    let field = |value: bool, name: &mut String| {
+                        if value {
+                            *name = source.to_string();
+                        }
+                    };
+                    field(patch.initial_date.is_some(), &mut provenance.initial_date);
+                    field(patch.sale_end.is_some(), &mut provenance.sale_end);
+                    field(patch.nominal.is_some(), &mut provenance.nominal);
+                    field(patch.rates.is_some(), &mut provenance.rates);
+
+                    let definition = BondDefinition {
+                        id: patch.id,
+                        bond_type: None,
+                        initial_date: patch.initial_date.unwrap_or(bond.initial_date),
+                        sale_end: patch.sale_end.unwrap_or(bond.sale_end),
+                        nominal: patch
+                            .nominal
+                            .or(bond.values.first().copied())
+                            .unwrap_or(DEFAULT_NOMINAL),
+                        rates: patch.rates.unwrap_or_else(|| bond.rates.clone()),
+                    };
+                    (definition, provenance)
+                }
+                None => {
+                    let (Some(initial_date), Some(sale_end)) = (patch.initial_date, patch.sale_end)
+                    else {
+                        bail!(
+                            "Series {} from [{source}] is not in any other source, it needs both sale dates",
+                            patch.id
+                        );
+                    };
+                    let definition = BondDefinition {
+                        id: patch.id,
+                        bond_type: None,
+                        initial_date,
+                        sale_end,
+                        nominal: patch.nominal.unwrap_or(DEFAULT_NOMINAL),
+                        rates: patch.rates.unwrap_or_default(),
+                    };
+                    (definition, Provenance::new(source))
+                }
+            };
+            definition
+                .validate()
+                .with_context(|| format!("Invalid series {} from [{source}]", definition.id))?;
+            let bond = definition.to_bond();
+            self.provenance.insert(bond.id.clone(), provenance);
+            self.all_bonds.insert(bond);
+        }
+        Ok(())
+    }
+}
+
+/// Reads a CSV of corrections with an `id` column and any of `initial_date`, `sale_end` and
+/// `rates`. Rates are yearly fractions separated by `;`, empty cells keep the merged value.
+pub fn read_corrections<R: Read>(reader: R) -> Result<Vec<BondPatch>> {
+    let rows = table::read_rows(reader, "corrections", &CORRECTION_COLUMNS, &["id"])?;
+
+    let mut patches = Vec::new();
+    for row in rows {
+        let parse = || -> Result<BondPatch> {
+            Ok(BondPatch {
+                id: BondId::new(row.get("id").unwrap_or_default())?,
+                initial_date: row.get("initial_date").map(str::parse).transpose()?,
+                sale_end: row.get("sale_end").map(str::parse).transpose()?,
+                nominal: None,
+                rates: row.get("rates").map(table::parse_rates).transpose()?,
+            })
+        };
+        patches.push(parse().with_context(|| format!("Invalid corrections line {}", row.line))?);
+    }
+    Ok(patches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build_bond;
+    use pretty_assertions::assert_eq;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    fn patch(id: &str) -> BondPatch {
+        BondPatch {
+            id: BondId::new(id).unwrap(),
+            initial_date: None,
+            sale_end: None,
+            nominal: None,
+            rates: None,
+        }
+    }
+
+    fn workbook() -> MergedBonds {
+        let bond = build_bond(
+            BondId::new("EDO0834").unwrap(),
+            date("2024-08-01"),
+            date("2024-08-31"),
+            DEFAULT_NOMINAL,
+            vec![0.068],
+        );
+        MergedBonds::new("workbook", [bond].into_iter().collect())
+    }
+
+    #[test]
+    fn test_read_corrections() {
+        let csv = "id,rates,sale_end\nEDO0834,0.068;0.0595,\n\nROD0820, ,2020-08-31\n";
+
+        let patches = read_corrections(csv.as_bytes()).expect("Should read corrections");
+
+        assert_eq!(
+            patches,
+            vec![
+                BondPatch {
+                    id: BondId::new("EDO0834").unwrap(),
+                    initial_date: None,
+                    sale_end: None,
+                    nominal: None,
+                    rates: Some(vec![0.068, 0.0595]),
+                },
+                BondPatch {
+                    id: BondId::new("ROD0820").unwrap(),
+                    initial_date: None,
+                    sale_end: Some(date("2020-08-31")),
+                    nominal: None,
+                    rates: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_quoted_corrections() {
+        let csv = "\"id\",\"rates\"\r\n\"EDO0834\",\" 0.068; 0.0595 \"\r\n";
+
+        let patches = read_corrections(csv.as_bytes()).expect("Should read corrections");
+
+        assert_eq!(patches[0].rates, Some(vec![0.068, 0.0595]));
+    }
+
+    #[test]
+    fn test_read_invalid_corrections() {
+        let error = |csv: &str| format!("{:#}", read_corrections(csv.as_bytes()).unwrap_err());
+
+        assert_eq!(error("id,margin\n"), "Unknown corrections column [margin]");
+        assert_eq!(error("rates\n0.06\n"), "Missing corrections column [id]");
+        assert_eq!(
+            error("id,sale_end\nEDO0834,31.08.2024\n"),
+            "Invalid corrections line 2: input contains invalid characters"
+        );
+    }
+
+    #[test]
+    fn test_merge_corrections() {
+        let mut merged = workbook();
+
+        merged
+            .apply(
+                "corrections",
+                vec![
+                    BondPatch {
+                        id: BondId::new("EDO0834").unwrap(),
+                        initial_date: None,
+                        sale_end: None,
+                        nominal: None,
+                        rates: Some(vec![0.068, 0.0595]),
+                    },
+                    BondPatch {
+                        id: BondId::new("EDO0104").unwrap(),
+                        initial_date: Some(date("2004-01-01")),
+                        sale_end: Some(date("2004-01-31")),
+                        nominal: None,
+                        rates: Some(vec![0.0675]),
+                    },
+                ],
+            )
+            .expect("Should merge");
+
+        let corrected = merged
+            .all_bonds
+            .get(&BondId::new("EDO0834").unwrap())
+            .unwrap();
+        assert_eq!(corrected.rates, vec![0.068, 0.0595]);
+        assert_eq!(corrected.sale_end, date("2024-08-31"));
+        assert_eq!(
+            merged.provenance[&corrected.id],
+            Provenance {
+                series: "workbook".to_string(),
+                initial_date: "workbook".to_string(),
+                sale_end: "workbook".to_string(),
+                nominal: "workbook".to_string(),
+                rates: "corrections".to_string(),
+            }
+        );
+        let historical = BondId::new("EDO0104").unwrap();
+        assert_eq!(
+            merged.all_bonds.get(&historical).unwrap().buyout_date,
+            date("2014-01-01")
+        );
+        assert_eq!(
+            merged.provenance[&historical],
+            Provenance::new("corrections")
+        );
+    }
+
+    #[test]
+    fn test_merge_new_series_without_dates() {
+        let mut merged = workbook();
+
+        let result = merged.apply(
+            "corrections",
+            vec![BondPatch {
+                id: BondId::new("EDO0104").unwrap(),
+                initial_date: None,
+                sale_end: None,
+                nominal: None,
+                rates: Some(vec![0.06]),
+            }],
+        );
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Series EDO0104 from [corrections] is not in any other source, it needs both sale dates"
+        );
+        assert_eq!(merged, workbook());
+    }
+
+    #[test]
+    fn test_reject_invalid_patched_series() {
+        let error = |patch: BondPatch| {
+            let mut merged = workbook();
+            format!(
+                "{:#}",
+                merged.apply("corrections", vec![patch]).unwrap_err()
+            )
+        };
+
+        assert_eq!(
+            error(BondPatch {
+                sale_end: Some(date("2024-07-31")),
+                ..patch("EDO0834")
+            }),
+            "Invalid series EDO0834 from [corrections]: Last day of sale 2024-07-31 is before the \
+             first one 2024-08-01 for EDO0834"
+        );
+        assert_eq!(
+            error(BondPatch {
+                rates: Some(vec![0.068; 11]),
+                ..patch("EDO0834")
+            }),
+            "Invalid series EDO0834 from [corrections]: Series EDO0834 needs between 1 and 10 \
+             yearly rates, got 11"
+        );
+        assert_eq!(
+            error(BondPatch {
+                initial_date: Some(date("2024-08-01")),
+                sale_end: Some(date("2024-08-31")),
+                rates: Some(vec![0.0655]),
+                ..patch("OTS1124")
+            }),
+            "Invalid series OTS1124 from [corrections]: Bond type OTS of OTS1124 is not \
+             supported, expected one of EDO, ROD"
+        );
+    }
+
+    #[test]
+    fn test_report_corrected_nominal() {
+        let mut merged = workbook();
+
+        merged
+            .apply(
+                "corrections",
+                vec![BondPatch {
+                    nominal: Some(99.9),
+                    ..patch("EDO0834")
+                }],
+            )
+            .expect("Should merge");
+
+        let id = BondId::new("EDO0834").unwrap();
+        assert_eq!(merged.all_bonds.get(&id).unwrap().values[0], 99.9);
+        assert_eq!(merged.provenance[&id].nominal, "corrections");
+        assert_eq!(merged.provenance[&id].rates, "workbook");
+    }
+}
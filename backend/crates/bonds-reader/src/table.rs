@@ -0,0 +1,83 @@
+use anyhow::{Context, Result, bail};
+use std::collections::HashMap;
+use std::io::Read;
+
+/// Line of a comma-separated file, with its cells keyed by the header
+pub(crate) struct Row {
+    /// 1-based, counting the header
+    pub(crate) line: usize,
+    cells: HashMap<String, String>,
+}
+
+impl Row {
+    /// Content of the column, `None` when it is empty or not in the file
+    pub(crate) fn get(&self, column: &str) -> Option<&str> {
+        self.cells
+            .get(column)
+            .map(String::as_str)
+            .filter(|cell| !cell.is_empty())
+    }
+}
+
+/// Reads a comma-separated file with a header naming any of the `known` columns, in any order.
+///
+/// Cells may be quoted like in RFC 4180, blank lines are skipped. `kind` names the file in errors.
+pub(crate) fn read_rows<R: Read>(
+    reader: R,
+    kind: &str,
+    known: &[&str],
+    required: &[&str],
+) -> Result<Vec<Row>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(reader);
+    let columns: Vec<String> = reader
+        .headers()
+        .with_context(|| format!("Failed to read {kind}"))?
+        .iter()
+        .map(str::to_string)
+        .collect();
+    if columns.iter().all(String::is_empty) {
+        bail!("Missing {kind} header");
+    }
+    if let Some(unknown) = columns
+        .iter()
+        .find(|column| !known.contains(&column.as_str()))
+    {
+        bail!("Unknown {kind} column [{unknown}]");
+    }
+    if let Some(missing) = required
+        .iter()
+        .find(|column| !columns.iter().any(|c| c == *column))
+    {
+        bail!("Missing {kind} column [{missing}]");
+    }
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.with_context(|| format!("Failed to read {kind}"))?;
+        let line = record
+            .position()
+            .map_or(0, |position| position.line() as usize);
+        let cells = columns
+            .iter()
+            .cloned()
+            .zip(record.iter().map(str::to_string))
+            .collect();
+        rows.push(Row { line, cells });
+    }
+    Ok(rows)
+}
+
+/// Yearly rates as fractions separated by `;`
+pub(crate) fn parse_rates(value: &str) -> Result<Vec<f64>> {
+    value
+        .split(';')
+        .map(|rate| {
+            rate.trim()
+                .parse()
+                .with_context(|| format!("Invalid rate [{}]", rate.trim()))
+        })
+        .collect()
+}
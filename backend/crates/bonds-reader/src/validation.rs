@@ -0,0 +1,380 @@
+use crate::columns::ColumnMapping;
+use crate::{SUPPORTED_BOND_TYPES, open_workbook, open_workbook_file, series_rows};
+use anyhow::Result;
+use calamine::{Data, DataType, Range, Reader};
+use model::{BondId, BondType};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::path::Path;
+
+/// Rates above this are most likely a percentage typed in as a whole number
+const MAX_PLAUSIBLE_RATE: f64 = 0.25;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Suspicious, but the bond can still be read
+    Warning,
+    /// The bond cannot be read correctly
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// A single problem found in the workbook
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub sheet: String,
+    /// A1 reference of the offending cell, `None` when the whole sheet is affected
+    pub cell: Option<String>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.cell {
+            Some(cell) => write!(f, "{}!{cell}", self.sheet)?,
+            None => write!(f, "{}", self.sheet)?,
+        }
+        write!(f, " {}: {}", self.severity, self.message)
+    }
+}
+
+/// Every problem found in the supported sheets of a workbook
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ValidationReport {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidationReport {
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+
+    pub fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{diagnostic}")?;
+        }
+        writeln!(
+            f,
+            "{} errors, {} warnings",
+            self.count(Severity::Error),
+            self.count(Severity::Warning)
+        )
+    }
+}
+
+/// Scans all supported sheets and collects every problem instead of stopping at the first one.
+///
+/// Only fails when the workbook itself cannot be opened.
+pub fn validate_workbook<P: AsRef<Path>>(path: P) -> Result<ValidationReport> {
+    validate_workbook_from_reader(open_workbook_file(path.as_ref())?)
+}
+
+/// Same as [`validate_workbook`], for a workbook that is not a file
+pub fn validate_workbook_from_reader<R: Read>(reader: R) -> Result<ValidationReport> {
+    let mut workbook = open_workbook(reader)?;
+
+    let mut report = ValidationReport::default();
+    for bond_type in SUPPORTED_BOND_TYPES {
+        match workbook.worksheet_range(bond_type.code()) {
+            Ok(range) => validate_sheet(&range, bond_type, &mut report),
+            Err(e) => report.diagnostics.push(Diagnostic {
+                sheet: bond_type.to_string(),
+                cell: None,
+                severity: Severity::Error,
+                message: format!("Cannot read sheet: {e}"),
+            }),
+        }
+    }
+    Ok(report)
+}
+
+fn validate_sheet(range: &Range<Data>, bond_type: BondType, report: &mut ValidationReport) {
+    let columns = match ColumnMapping::locate(range, bond_type) {
+        Ok(columns) => columns,
+        Err(e) => {
+            report.diagnostics.push(Diagnostic {
+                sheet: bond_type.to_string(),
+                cell: None,
+                severity: Severity::Error,
+                message: format!("{e:#}"),
+            });
+            return;
+        }
+    };
+    let mut diagnose = |row: usize, column: usize, severity: Severity, message: String| {
+        report.diagnostics.push(Diagnostic {
+            sheet: bond_type.to_string(),
+            cell: Some(cell_reference(range, row, column)),
+            severity,
+            message,
+        })
+    };
+
+    let mut seen: HashMap<BondId, usize> = HashMap::new();
+    let mut series_count = 0;
+    for (row_id, row, bond_id) in series_rows(range, &columns, bond_type) {
+        series_count += 1;
+        if let Some(first_row) = seen.insert(bond_id.clone(), row_id) {
+            diagnose(
+                row_id,
+                columns.id,
+                Severity::Error,
+                format!(
+                    "Duplicate series {bond_id}, already defined in {}",
+                    cell_reference(range, first_row, columns.id)
+                ),
+            );
+        }
+
+        let sale_start = date_cell(row, columns.sale_start);
+        let sale_end = date_cell(row, columns.sale_end);
+        if sale_start.is_none() {
+            diagnose(
+                row_id,
+                columns.sale_start,
+                Severity::Error,
+                format!("Missing first day of sale for {bond_id}"),
+            );
+        }
+        if sale_end.is_none() {
+            diagnose(
+                row_id,
+                columns.sale_end,
+                Severity::Error,
+                format!("Missing last day of sale for {bond_id}"),
+            );
+        }
+        if let (Some(start), Some(end)) = (sale_start, sale_end)
+            && end < start
+        {
+            diagnose(
+                row_id,
+                columns.sale_end,
+                Severity::Error,
+                format!("Last day of sale {end} is before the first one {start} for {bond_id}"),
+            );
+        }
+
+        let mut gap_since = None;
+        for (year, &column) in (1..).zip(&columns.rates) {
+            match row.get(column) {
+                None | Some(Data::Empty) => {
+                    gap_since.get_or_insert((year, column));
+                }
+                Some(Data::Float(rate)) => {
+                    if let Some((gap_year, gap_column)) = gap_since.take() {
+                        diagnose(
+                            row_id,
+                            gap_column,
+                            Severity::Error,
+                            format!(
+                                "Missing rate for year {gap_year} of {bond_id}, later years are filled in"
+                            ),
+                        );
+                    }
+                    if !(0.0..=MAX_PLAUSIBLE_RATE).contains(rate) {
+                        diagnose(
+                            row_id,
+                            column,
+                            Severity::Warning,
+                            format!("Rate {rate} for year {year} of {bond_id} is out of range"),
+                        );
+                    }
+                }
+                Some(other) => diagnose(
+                    row_id,
+                    column,
+                    Severity::Error,
+                    format!("Rate for year {year} of {bond_id} is not a number: [{other}]"),
+                ),
+            }
+        }
+    }
+
+    if series_count == 0 {
+        report.diagnostics.push(Diagnostic {
+            sheet: bond_type.to_string(),
+            cell: None,
+            severity: Severity::Warning,
+            message: "No series found".to_string(),
+        });
+    }
+}
+
+fn date_cell(row: &[Data], column: usize) -> Option<chrono::NaiveDate> {
+    row.get(column).and_then(|cell| cell.as_date())
+}
+
+/// A1 reference of a cell, given its position relative to the start of the range
+pub(crate) fn cell_reference(range: &Range<Data>, row: usize, column: usize) -> String {
+    let (start_row, start_column) = range.start().unwrap_or((0, 0));
+    let mut column = start_column as usize + column;
+    let mut letters = Vec::new();
+    loop {
+        letters.push(b'A' + (column % 26) as u8);
+        if column < 26 {
+            break;
+        }
+        column = column / 26 - 1;
+    }
+    letters.reverse();
+    format!(
+        "{}{}",
+        String::from_utf8(letters).expect("Column letters are ASCII"),
+        start_row as usize + row + 1
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+    use pretty_assertions::assert_eq;
+
+    fn sheet(rows: Vec<Vec<Data>>) -> Range<Data> {
+        let mut range = Range::new((0, 0), (rows.len() as u32 - 1, 20));
+        for (row_id, row) in rows.into_iter().enumerate() {
+            for (column, cell) in row.into_iter().enumerate() {
+                range.set_value((row_id as u32, column as u32), cell);
+            }
+        }
+        range
+    }
+
+    fn date(value: &str) -> Data {
+        let date = value.parse::<chrono::NaiveDate>().unwrap();
+        // Excel serial date, days since 1899-12-30
+        Data::Float(
+            (date - chrono::NaiveDate::from_ymd_opt(1899, 12, 30).unwrap()).num_days() as f64,
+        )
+    }
+
+    const RATES_START: usize = 9;
+
+    fn header_rows() -> [Vec<Data>; 2] {
+        let text = |value: &str| Data::String(value.to_string());
+        let mut header = vec![
+            text("Seria"),
+            Data::Empty,
+            Data::Empty,
+            text("Początek sprzedaży"),
+            text("Koniec sprzedaży"),
+        ];
+        header.resize(RATES_START, Data::Empty);
+        header.push(text("Oprocentowanie"));
+        let mut years = vec![Data::Empty; RATES_START];
+        years.extend((1..=10).map(|year| text(&format!(" w {year}. roku"))));
+        [header, years]
+    }
+
+    fn series(id: &str, start: Data, end: Data, rates: Vec<Data>) -> Vec<Data> {
+        let mut row = vec![
+            Data::String(id.to_string()),
+            Data::Empty,
+            Data::Empty,
+            start,
+            end,
+        ];
+        row.resize(RATES_START, Data::Empty);
+        row.extend(rates);
+        row
+    }
+
+    #[test]
+    fn test_cell_reference() {
+        let range = Range::<Data>::new((0, 0), (0, 0));
+
+        assert_eq!(
+            [
+                (0, 0),
+                (4, 3),
+                (0, 25),
+                (0, 26),
+                (9, 27),
+                (0, 701),
+                (0, 702)
+            ]
+            .map(|(row, column)| cell_reference(&range, row, column)),
+            ["A1", "D5", "Z1", "AA1", "AB10", "ZZ1", "AAA1"]
+        );
+    }
+
+    #[test]
+    fn test_validate_sheet() {
+        let [header, years] = header_rows();
+        let range = sheet(vec![
+            header,
+            years,
+            series(
+                "EDO0734",
+                date("2024-07-01"),
+                date("2024-07-31"),
+                vec![Data::Float(0.068), Data::Float(0.0595)],
+            ),
+            series(
+                "EDO0834",
+                date("2024-08-01"),
+                date("2024-07-31"),
+                vec![Data::Float(0.068), Data::Empty, Data::Float(0.0595)],
+            ),
+            series(
+                "EDO0734",
+                Data::Empty,
+                date("2024-07-31"),
+                vec![Data::Float(6.8), Data::String("n/a".to_string())],
+            ),
+        ]);
+        let mut report = ValidationReport::default();
+
+        validate_sheet(&range, BondType::EDO, &mut report);
+
+        assert!(report.has_errors());
+        assert_snapshot!(report.to_string());
+    }
+
+    #[test]
+    fn test_validate_fixture_workbook() {
+        let report =
+            validate_workbook("../../tests/fixtures/bonds/test.xls").expect("Should open workbook");
+
+        assert_eq!(report, ValidationReport::default());
+    }
+
+    #[test]
+    fn test_validate_issuer_workbook() {
+        let report = validate_workbook("../../assets/Dane_dotyczace_obligacji_detalicznych.xls")
+            .expect("Should open workbook");
+
+        // Errata of the issuer, whose later editions have to be corrected before they are uploaded
+        let cells: Vec<_> = report
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.cell.as_deref().unwrap_or_default())
+            .collect();
+        assert_eq!(cells, ["E71", "E98", "E99"], "{report}");
+        assert_eq!(report.count(Severity::Error), 3, "{report}");
+    }
+}
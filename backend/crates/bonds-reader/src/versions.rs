@@ -0,0 +1,236 @@
+use crate::dataset::{read_dataset, replace_file, write_dataset};
+use crate::{MergedBonds, Provenance};
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use model::{AllBonds, Bond, BondId};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+const INDEX_FILE: &str = "versions.json";
+
+/// A single ingested dataset, identified by the SHA-256 of its bonds
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DatasetVersion {
+    pub hash: String,
+    pub ingested_at: DateTime<Utc>,
+}
+
+impl DatasetVersion {
+    fn file_name(&self) -> String {
+        format!("{}.dataset", self.hash)
+    }
+
+    fn provenance_file_name(&self) -> String {
+        format!("{}.provenance.json", self.hash)
+    }
+}
+
+/// Directory keeping every ingested dataset, next to an index ordered by ingest time
+#[derive(Debug)]
+pub struct DatasetVersions {
+    directory: PathBuf,
+    versions: Vec<DatasetVersion>,
+}
+
+/// Identifies bonds by their content, whether they come from the workbook alone or from several
+/// sources merged together
+pub fn bonds_hash(all_bonds: &AllBonds) -> String {
+    let mut bonds: Vec<&Bond> = all_bonds.iter().collect();
+    bonds.sort_by(|a, b| a.id.cmp(&b.id));
+    let mut hasher = Sha256::new();
+    serde_json::to_writer(&mut hasher, &bonds).expect("Bonds always serialize to JSON");
+    hex::encode(hasher.finalize())
+}
+
+impl DatasetVersions {
+    /// Opens the version directory, creating it when it does not exist yet
+    pub fn open<P: AsRef<Path>>(directory: P) -> Result<Self> {
+        let directory = directory.as_ref().to_path_buf();
+        std::fs::create_dir_all(&directory).context("Failed to create versions directory")?;
+
+        let index = directory.join(INDEX_FILE);
+        let versions = if index.exists() {
+            let file = File::open(&index).context("Failed to open versions index")?;
+            serde_json::from_reader(BufReader::new(file))
+                .context("Failed to deserialize versions index")?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            directory,
+            versions,
+        })
+    }
+
+    /// Stores the bonds, with the source of each of their fields, as a new version, unless the
+    /// latest one has the same bonds
+    pub fn record(
+        &mut self,
+        merged: &MergedBonds,
+        ingested_at: DateTime<Utc>,
+    ) -> Result<&DatasetVersion> {
+        let hash = bonds_hash(&merged.all_bonds);
+
+        if self.latest().is_none_or(|latest| latest.hash != hash) {
+            let version = DatasetVersion { hash, ingested_at };
+            write_dataset(&merged.all_bonds, self.dataset_path(&version))?;
+            replace_file(&self.provenance_path(&version), |writer| {
+                serde_json::to_writer(writer, &merged.provenance)
+                    .context("Failed to serialize provenance")
+            })
+            .context("Failed to write provenance file")?;
+            self.versions.push(version);
+            self.write_index()?;
+        }
+
+        Ok(self.latest().expect("Version was just recorded"))
+    }
+
+    pub fn versions(&self) -> &[DatasetVersion] {
+        &self.versions
+    }
+
+    pub fn latest(&self) -> Option<&DatasetVersion> {
+        self.versions.last()
+    }
+
+    /// Version that was being served at the end of the given day
+    pub fn as_of(&self, date: NaiveDate) -> Option<&DatasetVersion> {
+        self.versions
+            .iter()
+            .rev()
+            .find(|version| version.ingested_at.date_naive() <= date)
+    }
+
+    pub fn load(&self, version: &DatasetVersion) -> Result<AllBonds> {
+        read_dataset(self.dataset_path(version))
+            .with_context(|| format!("Failed to load dataset version [{}]", version.hash))
+    }
+
+    /// Dataset of the version, for loading it without holding on to the versions
+    pub fn dataset_path(&self, version: &DatasetVersion) -> PathBuf {
+        self.directory.join(version.file_name())
+    }
+
+    /// Sources of the fields of the version's bonds, read with [`read_provenance`]
+    pub fn provenance_path(&self, version: &DatasetVersion) -> PathBuf {
+        self.directory.join(version.provenance_file_name())
+    }
+
+    fn write_index(&self) -> Result<()> {
+        replace_file(&self.directory.join(INDEX_FILE), |writer| {
+            serde_json::to_writer_pretty(writer, &self.versions)
+                .context("Failed to serialize versions index")
+        })
+        .context("Failed to write versions index")
+    }
+}
+
+/// Reads the sources of the bond fields recorded next to a dataset version
+pub fn read_provenance<P: AsRef<Path>>(path: P) -> Result<HashMap<BondId, Provenance>> {
+    let file = File::open(path.as_ref()).context("Failed to open provenance file")?;
+    serde_json::from_reader(BufReader::new(file)).context("Failed to deserialize provenance")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use model::BondId;
+    use pretty_assertions::assert_eq;
+
+    fn merged(id: &str) -> MergedBonds {
+        MergedBonds::new("workbook", bonds(id))
+    }
+
+    fn bonds(id: &str) -> AllBonds {
+        let date = "2025-08-01".parse::<NaiveDate>().unwrap();
+        [Bond::builder()
+            .id(BondId::new(id).unwrap())
+            .initial_date(date)
+            .sale_end(date)
+            .buyout_date(date)
+            .values(vec![100.0])
+            .build()]
+        .into_iter()
+        .collect()
+    }
+
+    fn at(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_record_versions() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut versions = DatasetVersions::open(directory.path().join("versions")).unwrap();
+
+        versions
+            .record(&merged("EDO0735"), at("2025-07-01T10:00:00Z"))
+            .unwrap();
+        // Same bonds are not recorded twice
+        versions
+            .record(&merged("EDO0735"), at("2025-07-15T10:00:00Z"))
+            .unwrap();
+        versions
+            .record(&merged("EDO0835"), at("2025-08-01T10:00:00Z"))
+            .unwrap();
+
+        let reopened = DatasetVersions::open(directory.path().join("versions")).unwrap();
+        assert_eq!(
+            reopened.versions(),
+            &[
+                DatasetVersion {
+                    hash: "ff3bfd52ab5986c7f8f40762821422fae9df6680f128d55c0bcd784ca0e90a3d"
+                        .to_string(),
+                    ingested_at: at("2025-07-01T10:00:00Z"),
+                },
+                DatasetVersion {
+                    hash: "5044c7c807e07e300d59ef0be283b157a1fdbd6c58b89baf7bcfc8bc32777a96"
+                        .to_string(),
+                    ingested_at: at("2025-08-01T10:00:00Z"),
+                },
+            ]
+        );
+        assert_eq!(
+            reopened.load(&reopened.versions()[0]).unwrap(),
+            bonds("EDO0735")
+        );
+        assert_eq!(
+            read_provenance(reopened.provenance_path(&reopened.versions()[0])).unwrap(),
+            merged("EDO0735").provenance
+        );
+    }
+
+    #[test]
+    fn test_find_version_as_of() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut versions = DatasetVersions::open(directory.path()).unwrap();
+        for (id, ingested_at) in [
+            ("EDO0735", "2025-07-01T10:00:00Z"),
+            ("EDO0835", "2025-08-01T10:00:00Z"),
+        ] {
+            versions.record(&merged(id), at(ingested_at)).unwrap();
+        }
+
+        let ingested_as_of = |date: &str| {
+            versions
+                .as_of(date.parse().unwrap())
+                .map(|version| version.ingested_at)
+        };
+
+        assert_eq!(ingested_as_of("2025-06-30"), None);
+        assert_eq!(
+            ingested_as_of("2025-07-31"),
+            Some(at("2025-07-01T10:00:00Z"))
+        );
+        assert_eq!(
+            ingested_as_of("2025-08-01"),
+            Some(at("2025-08-01T10:00:00Z"))
+        );
+    }
+}
@@ -3,6 +3,8 @@ mod otel;
 
 use crate::config::OtelConfig;
 use crate::otel::init;
+use axum::middleware::map_response;
+use axum::response::Response;
 use axum::routing::Router;
 use axum_otel_metrics::HttpMetricsLayerBuilder;
 use axum_tracing_opentelemetry::middleware::{OtelAxumLayer, OtelInResponseLayer};
@@ -10,10 +12,23 @@ use loco_rs::app::AppContext;
 use loco_rs::prelude::{Initializer, async_trait};
 use loco_rs::{Error, Result};
 use tracing::log::warn;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 #[derive(Default)]
 pub struct OtelInitializer {}
 
+/// Attributes the application wants on the request span, e.g. the authenticated client.
+///
+/// Put into the response extensions, they are recorded once the response leaves the router.
+#[derive(Clone, Debug, Default)]
+pub struct SpanAttributes(Vec<(&'static str, String)>);
+
+impl SpanAttributes {
+    pub fn insert(&mut self, key: &'static str, value: impl Into<String>) {
+        self.0.push((key, value.into()));
+    }
+}
+
 impl OtelInitializer {
     pub fn new() -> Self {
         Self {}
@@ -50,9 +65,21 @@ impl Initializer for OtelInitializer {
 
     async fn after_routes(&self, router: Router, _ctx: &AppContext) -> Result<Router> {
         let router = router
+            .layer(map_response(record_span_attributes))
             .layer(OtelInResponseLayer)
             .layer(OtelAxumLayer::default())
             .layer(HttpMetricsLayerBuilder::new().build());
         Ok(router)
     }
 }
+
+/// Runs within the span of `OtelAxumLayer`, so the attributes end up on the request span
+async fn record_span_attributes(response: Response) -> Response {
+    if let Some(attributes) = response.extensions().get::<SpanAttributes>() {
+        let span = tracing::Span::current();
+        for (key, value) in &attributes.0 {
+            span.set_attribute(*key, value.clone());
+        }
+    }
+    response
+}
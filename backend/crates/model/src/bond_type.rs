@@ -0,0 +1,92 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Retail treasury bond types, named after the series code prefix used by the issuer
+#[allow(clippy::upper_case_acronyms)]
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+pub enum BondType {
+    OTS,
+    ROR,
+    DOR,
+    TOS,
+    COI,
+    EDO,
+    ROS,
+    ROD,
+    DOS,
+}
+
+impl BondType {
+    pub const ALL: [BondType; 9] = [
+        BondType::OTS,
+        BondType::ROR,
+        BondType::DOR,
+        BondType::TOS,
+        BondType::COI,
+        BondType::EDO,
+        BondType::ROS,
+        BondType::ROD,
+        BondType::DOS,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            BondType::OTS => "OTS",
+            BondType::ROR => "ROR",
+            BondType::DOR => "DOR",
+            BondType::TOS => "TOS",
+            BondType::COI => "COI",
+            BondType::EDO => "EDO",
+            BondType::ROS => "ROS",
+            BondType::ROD => "ROD",
+            BondType::DOS => "DOS",
+        }
+    }
+
+    /// Time from purchase to buyout
+    pub fn term_in_months(&self) -> u32 {
+        match self {
+            BondType::OTS => 3,
+            BondType::ROR => 12,
+            BondType::DOR => 24,
+            BondType::TOS => 36,
+            BondType::COI => 48,
+            BondType::EDO => 120,
+            BondType::ROS => 72,
+            BondType::ROD => 144,
+            BondType::DOS => 24,
+        }
+    }
+}
+
+impl Display for BondType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnknownBondType(pub String);
+
+impl Display for UnknownBondType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown bond type [{}]", self.0)
+    }
+}
+
+impl std::error::Error for UnknownBondType {}
+
+impl FromStr for BondType {
+    type Err = UnknownBondType;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BondType::ALL
+            .into_iter()
+            .find(|bond_type| bond_type.code() == s)
+            .ok_or_else(|| UnknownBondType(s.to_string()))
+    }
+}
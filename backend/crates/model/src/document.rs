@@ -0,0 +1,75 @@
+use crate::{AllBonds, Bond};
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+
+/// Version of the serialized [`AllBonds`] representation.
+///
+/// Bump it whenever a change to the model makes previously written documents unreadable.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// JSON Schema of the serialized [`AllBonds`] document
+pub fn json_schema() -> Schema {
+    schemars::schema_for!(AllBonds)
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(title = "AllBonds")]
+pub(crate) struct BondsDocument {
+    /// Always equal to the format version the document was written with
+    format_version: u32,
+    /// Bonds sorted by their ID
+    bonds: Vec<Bond>,
+}
+
+impl From<AllBonds> for BondsDocument {
+    fn from(value: AllBonds) -> Self {
+        let mut bonds: Vec<_> = value
+            .bonds
+            .into_values()
+            .flat_map(|b| b.into_values())
+            .collect();
+        bonds.sort_by(|a, b| a.id.cmp(&b.id));
+        BondsDocument {
+            format_version: FORMAT_VERSION,
+            bonds,
+        }
+    }
+}
+
+impl TryFrom<BondsDocument> for AllBonds {
+    type Error = UnsupportedFormatVersion;
+
+    fn try_from(value: BondsDocument) -> Result<Self, Self::Error> {
+        if value.format_version != FORMAT_VERSION {
+            return Err(UnsupportedFormatVersion(value.format_version));
+        }
+        Ok(value.bonds.into_iter().collect())
+    }
+}
+
+impl JsonSchema for AllBonds {
+    fn schema_name() -> Cow<'static, str> {
+        "AllBonds".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        BondsDocument::json_schema(generator)
+    }
+}
+
+#[derive(Debug)]
+pub struct UnsupportedFormatVersion(u32);
+
+impl Display for UnsupportedFormatVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Unsupported bonds format version [{}], expected [{}]",
+            self.0, FORMAT_VERSION
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedFormatVersion {}
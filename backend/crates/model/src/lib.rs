@@ -1,33 +1,201 @@
-use chrono::NaiveDate;
-use std::collections::HashMap;
+mod bond_type;
+mod document;
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
-pub struct BondId(String);
+pub use bond_type::{BondType, UnknownBondType};
+use chrono::{Months, NaiveDate};
+pub use document::{FORMAT_VERSION, UnsupportedFormatVersion, json_schema};
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+/// Series identifier such as `EDO0732`: bond type followed by maturity month and year
+#[derive(Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct BondId {
+    id: String,
+    bond_type: BondType,
+    maturity: Maturity,
+}
+
+/// Month in which the series is bought out
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Maturity {
+    pub year: i32,
+    pub month: u32,
+}
 
 impl BondId {
-    pub fn new<S: Into<String>>(id: S) -> Self {
-        BondId(id.into())
+    pub fn new<S: Into<String>>(id: S) -> Result<Self, InvalidBondId> {
+        let id = id.into();
+        let invalid = |reason: &'static str| InvalidBondId {
+            id: id.clone(),
+            reason,
+        };
+
+        if id.len() != 7 || !id.is_ascii() {
+            return Err(invalid("expected bond type followed by MMYY"));
+        }
+        let bond_type = BondType::from_str(&id[..3]).map_err(|_| invalid("unknown bond type"))?;
+        let month =
+            parse_digits(&id[3..5]).ok_or_else(|| invalid("maturity month is not a number"))?;
+        let year =
+            parse_digits(&id[5..7]).ok_or_else(|| invalid("maturity year is not a number"))?;
+        if !(1..=12).contains(&month) {
+            return Err(invalid("maturity month out of range"));
+        }
+
+        Ok(BondId {
+            bond_type,
+            maturity: Maturity {
+                year: 2000 + year as i32,
+                month,
+            },
+            id,
+        })
     }
     pub fn value(self) -> String {
-        self.0
+        self.id
+    }
+    pub fn as_str(&self) -> &str {
+        &self.id
+    }
+    pub fn bond_type(&self) -> BondType {
+        self.bond_type
+    }
+    pub fn maturity(&self) -> Maturity {
+        self.maturity
+    }
+}
+
+fn parse_digits(value: &str) -> Option<u32> {
+    value
+        .bytes()
+        .all(|b| b.is_ascii_digit())
+        .then(|| value.parse().ok())
+        .flatten()
+}
+
+impl Debug for BondId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("BondId").field(&self.id).finish()
+    }
+}
+
+impl Display for BondId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.id)
+    }
+}
+
+impl FromStr for BondId {
+    type Err = InvalidBondId;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BondId::new(s)
+    }
+}
+
+impl TryFrom<String> for BondId {
+    type Error = InvalidBondId;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        BondId::new(value)
+    }
+}
+
+impl From<BondId> for String {
+    fn from(value: BondId) -> Self {
+        value.id
+    }
+}
+
+impl JsonSchema for BondId {
+    fn schema_name() -> Cow<'static, str> {
+        "BondId".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        let bond_types = BondType::ALL.map(|bond_type| bond_type.code()).join("|");
+        json_schema!({
+            "description": "Series identifier: bond type followed by maturity month and year (MMYY)",
+            "type": "string",
+            "pattern": format!("^({bond_types})(0[1-9]|1[0-2])[0-9]{{2}}$"),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidBondId {
+    pub id: String,
+    pub reason: &'static str,
+}
+
+impl Display for InvalidBondId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid bond ID [{}]: {}", self.id, self.reason)
     }
 }
 
-#[derive(Clone, Debug, PartialOrd, PartialEq, bon::Builder)]
+impl std::error::Error for InvalidBondId {}
+
+#[derive(Clone, Debug, PartialOrd, PartialEq, bon::Builder, Serialize, Deserialize, JsonSchema)]
 pub struct Bond {
     pub id: BondId,
+    /// First day of sale, the values series starts on this day
     pub initial_date: NaiveDate,
+    /// Last day of sale
     pub sale_end: NaiveDate,
     pub buyout_date: NaiveDate,
+    /// Yearly interest rates published so far, as fractions (`0.0725` is 7.25%)
+    #[builder(default)]
+    #[serde(default)]
+    pub rates: Vec<f64>,
+    /// Value of a single bond for every day starting from `initial_date`
     pub values: Vec<f64>,
 }
 
 impl Bond {
+    /// Daily values paired with their dates
+    pub fn series(&self) -> impl Iterator<Item = (NaiveDate, f64)> + '_ {
+        self.values.iter().enumerate().map(|(index, value)| {
+            (
+                self.initial_date + chrono::Duration::days(index as i64),
+                *value,
+            )
+        })
+    }
+
+    /// Value of a single bond on the given date, `None` outside of the known series
+    pub fn value_on(&self, date: NaiveDate) -> Option<f64> {
+        let index = usize::try_from((date - self.initial_date).num_days()).ok()?;
+        self.values.get(index).copied()
+    }
+
+    /// Value at purchase, the first one of the series
+    pub fn nominal(&self) -> Option<f64> {
+        self.values.first().copied()
+    }
+
+    /// Yearly interest period the date falls in, starting from 1. Interest is capitalized on the
+    /// anniversary of `initial_date`, so the anniversary still belongs to the period it closes.
+    pub fn period_on(&self, date: NaiveDate) -> Option<u32> {
+        self.value_on(date)?;
+        let years = date.years_since(self.initial_date)?;
+        let anniversary = self.initial_date + Months::new(12 * years);
+        if years > 0 && date == anniversary {
+            Some(years)
+        } else {
+            Some(years + 1)
+        }
+    }
+
     pub fn to_csv(&self) -> String {
         let mut csv = String::from("date,value\n");
 
-        for (index, value) in self.values.iter().enumerate() {
-            let date = self.initial_date + chrono::Duration::days(index as i64);
+        for (date, value) in self.series() {
             csv.push_str(&format!("{},{}\n", date.format("%Y-%m-%d"), value));
         }
 
@@ -35,7 +203,240 @@ impl Bond {
     }
 }
 
+/// Bonds grouped by their type
+///
+/// Serialized as a versioned document, see [`FORMAT_VERSION`] and [`json_schema`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "document::BondsDocument", into = "document::BondsDocument")]
 pub struct AllBonds {
-    pub edo: HashMap<BondId, Bond>,
-    pub rod: HashMap<BondId, Bond>,
+    bonds: BTreeMap<BondType, HashMap<BondId, Bond>>,
+}
+
+impl AllBonds {
+    pub fn insert(&mut self, bond: Bond) -> Option<Bond> {
+        self.bonds
+            .entry(bond.id.bond_type())
+            .or_default()
+            .insert(bond.id.clone(), bond)
+    }
+
+    pub fn get(&self, id: &BondId) -> Option<&Bond> {
+        self.bonds.get(&id.bond_type())?.get(id)
+    }
+
+    pub fn of_type(&self, bond_type: BondType) -> impl Iterator<Item = &Bond> {
+        self.bonds
+            .get(&bond_type)
+            .into_iter()
+            .flat_map(|bonds| bonds.values())
+    }
+
+    /// Types for which at least one bond is present
+    pub fn types(&self) -> impl Iterator<Item = BondType> {
+        self.bonds.keys().copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Bond> {
+        self.bonds.values().flat_map(|bonds| bonds.values())
+    }
+
+    pub fn len(&self) -> usize {
+        self.bonds.values().map(HashMap::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl FromIterator<Bond> for AllBonds {
+    fn from_iter<T: IntoIterator<Item = Bond>>(iter: T) -> Self {
+        let mut all_bonds = AllBonds::default();
+        for bond in iter {
+            all_bonds.insert(bond);
+        }
+        all_bonds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_bond_id() {
+        let bond_id = BondId::new("EDO0732").expect("Should parse bond ID");
+
+        assert_eq!(
+            (bond_id.bond_type(), bond_id.maturity()),
+            (
+                BondType::EDO,
+                Maturity {
+                    year: 2032,
+                    month: 7
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_period_on() {
+        let date = |value: &str| value.parse::<NaiveDate>().unwrap();
+        let bond = Bond::builder()
+            .id(BondId::new("EDO0835").unwrap())
+            .initial_date(date("2025-08-01"))
+            .sale_end(date("2025-08-31"))
+            .buyout_date(date("2035-08-01"))
+            .values(vec![100.0; 800])
+            .build();
+
+        let periods: Vec<_> = [
+            "2025-07-31",
+            "2025-08-01",
+            "2026-07-31",
+            "2026-08-01",
+            "2026-08-02",
+            "2027-08-01",
+            "2027-08-02",
+            "2027-10-10",
+        ]
+        .map(|day| bond.period_on(date(day)))
+        .into();
+
+        assert_eq!(
+            periods,
+            vec![
+                None,
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(2),
+                Some(2),
+                Some(3),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reject_invalid_bond_ids() {
+        let errors: Vec<_> = ["EDO1332", "XYZ0732", "EDO07", "EDOAB32", "NONEXISTENT"]
+            .into_iter()
+            .map(|id| BondId::new(id).unwrap_err().to_string())
+            .collect();
+
+        assert_eq!(
+            errors,
+            vec![
+                "Invalid bond ID [EDO1332]: maturity month out of range",
+                "Invalid bond ID [XYZ0732]: unknown bond type",
+                "Invalid bond ID [EDO07]: expected bond type followed by MMYY",
+                "Invalid bond ID [EDOAB32]: maturity month is not a number",
+                "Invalid bond ID [NONEXISTENT]: expected bond type followed by MMYY",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_serialize_all_bonds() {
+        let date = |d: &str| d.parse::<NaiveDate>().unwrap();
+        let all_bonds: AllBonds = [
+            Bond::builder()
+                .id(BondId::new("ROD0837").unwrap())
+                .initial_date(date("2025-08-01"))
+                .sale_end(date("2025-08-31"))
+                .buyout_date(date("2037-08-01"))
+                .rates(vec![0.0595])
+                .values(vec![100.0, 100.02])
+                .build(),
+            Bond::builder()
+                .id(BondId::new("EDO0835").unwrap())
+                .initial_date(date("2025-08-01"))
+                .sale_end(date("2025-08-31"))
+                .buyout_date(date("2035-08-01"))
+                .values(vec![100.0])
+                .build(),
+        ]
+        .into_iter()
+        .collect();
+
+        let serialized = serde_json::to_value(&all_bonds).unwrap();
+        let deserialized: AllBonds = serde_json::from_value(serialized.clone()).unwrap();
+
+        assert_eq!(
+            serialized,
+            json!({
+                "format_version": 1,
+                "bonds": [
+                    {
+                        "id": "EDO0835",
+                        "initial_date": "2025-08-01",
+                        "sale_end": "2025-08-31",
+                        "buyout_date": "2035-08-01",
+                        "rates": [],
+                        "values": [100.0]
+                    },
+                    {
+                        "id": "ROD0837",
+                        "initial_date": "2025-08-01",
+                        "sale_end": "2025-08-31",
+                        "buyout_date": "2037-08-01",
+                        "rates": [0.0595],
+                        "values": [100.0, 100.02]
+                    }
+                ]
+            })
+        );
+        assert_eq!(deserialized, all_bonds);
+    }
+
+    #[test]
+    fn test_reject_unsupported_format_version() {
+        let error = serde_json::from_value::<AllBonds>(json!({
+            "format_version": 2,
+            "bonds": []
+        }))
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Unsupported bonds format version [2], expected [1]"
+        );
+    }
+
+    #[test]
+    fn test_reject_invalid_bond_id_in_document() {
+        let error = serde_json::from_value::<BondId>(json!("EDO1332")).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid bond ID [EDO1332]: maturity month out of range"
+        );
+    }
+
+    #[test]
+    fn test_value_on() {
+        let date = |d: &str| d.parse::<NaiveDate>().unwrap();
+        let bond = Bond::builder()
+            .id(BondId::new("EDO0835").unwrap())
+            .initial_date(date("2025-08-01"))
+            .sale_end(date("2025-08-31"))
+            .buyout_date(date("2035-08-01"))
+            .values(vec![100.0, 100.02, 100.03])
+            .build();
+
+        assert_eq!(
+            ["2025-07-31", "2025-08-01", "2025-08-03", "2025-08-04"]
+                .map(|d| bond.value_on(date(d))),
+            [None, Some(100.0), Some(100.03), None]
+        );
+    }
+
+    #[test]
+    fn test_json_schema() {
+        assert_snapshot!(serde_json::to_string_pretty(&json_schema()).unwrap());
+    }
 }
@@ -58,13 +58,17 @@ impl Hooks for App {
             .context("Failed to parse openapi.yaml")
             .map_err(|e| loco_rs::errors::Error::from(e.into_boxed_dyn_error()))?;
 
-        Ok(vec![
+        #[allow(unused_mut)]
+        let mut initializers: Vec<Box<dyn Initializer>> = vec![
             Box::new(loco_openapi::OpenapiInitializerWithSetup::new(
                 move |_| openapi.clone(),
                 None,
             )),
             Box::new(OtelInitializer::new()),
-        ])
+        ];
+        #[cfg(feature = "sqlite")]
+        initializers.push(Box::new(crate::initializers::database::DatabaseInitializer));
+        Ok(initializers)
     }
 
     fn routes(ctx: &AppContext) -> AppRoutes {
@@ -75,7 +79,6 @@ impl Hooks for App {
         Ok(())
     }
 
-    #[allow(unused_variables)]
     fn register_tasks(tasks: &mut Tasks) {
         // tasks-inject (do not remove)
     }
@@ -1,7 +1,235 @@
-use loco_rs::cli;
-use myapp::app::App;
+//! Offline command line access to the bonds data, without starting the API
 
-#[tokio::main]
-async fn main() -> loco_rs::Result<()> {
-    cli::main::<App>().await
+use anyhow::{Context, Result, bail};
+use bonds_export::{Format, Layout};
+use bonds_reader::BondsDiff;
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
+use model::{AllBonds, Bond, BondId, BondType};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Parser)]
+#[command(about = "Query retail treasury bonds from the issuer workbook")]
+struct Cli {
+    /// Issuer workbook to read bonds from
+    #[arg(
+        long,
+        global = true,
+        default_value = "assets/Dane_dotyczace_obligacji_detalicznych.xls"
+    )]
+    workbook: PathBuf,
+    /// Dataset compiled with `tool compile`, used instead of the workbook
+    #[arg(long, global = true)]
+    dataset: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List bond IDs
+    List {
+        /// Only list bonds of the given type
+        #[arg(long = "type")]
+        bond_type: Option<BondType>,
+        /// Only list bonds on sale on the given date
+        #[arg(long)]
+        on_sale: Option<NaiveDate>,
+    },
+    /// Print the terms of a bond
+    Terms { id: BondId },
+    /// Export the daily values of a bond
+    Export {
+        id: BondId,
+        #[arg(long, value_enum, default_value_t = Format::Csv)]
+        format: Format,
+        /// File to write to, standard output when omitted
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Export the daily values of every bond, into a single file or a ZIP archive
+    ExportAll {
+        #[arg(long, value_enum, default_value_t = Layout::Long)]
+        layout: Layout,
+        #[arg(long, value_enum, default_value_t = Format::Csv)]
+        format: Format,
+        /// File to write to, standard output when omitted
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Print the value of a single bond on the given date
+    Value { id: BondId, date: NaiveDate },
+    /// Check a workbook for problems, printing every one with its cell and what was found on every sheet
+    Validate {
+        workbook: PathBuf,
+        /// Print the problems as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Compile the workbook into a dataset, which loads much faster
+    Compile {
+        /// Dataset file to write
+        output: PathBuf,
+    },
+    /// Report what changed between two versions of the workbook, failing when published data changed
+    Diff {
+        old: PathBuf,
+        new: PathBuf,
+        /// Print the changes as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    match &cli.command {
+        Command::List { bond_type, on_sale } => {
+            let all_bonds = load(&cli)?;
+            let mut ids: Vec<_> = all_bonds
+                .iter()
+                .filter(|bond| bond_type.is_none_or(|t| bond.id.bond_type() == t))
+                .filter(|bond| {
+                    on_sale.is_none_or(|date| bond.initial_date <= date && date <= bond.sale_end)
+                })
+                .map(|bond| &bond.id)
+                .collect();
+            ids.sort();
+            for id in ids {
+                println!("{id}");
+            }
+        }
+        Command::Terms { id } => {
+            let all_bonds = load(&cli)?;
+            let bond = find(&all_bonds, id)?;
+            print_terms(bond);
+        }
+        Command::Export { id, format, output } => {
+            let all_bonds = load(&cli)?;
+            let bond = find(&all_bonds, id)?;
+            let content = bonds_export::export(bond, *format, &Default::default())?;
+            match output {
+                Some(path) => std::fs::write(path, content)
+                    .with_context(|| format!("Failed to write {}", path.display()))?,
+                None => std::io::stdout().write_all(&content)?,
+            }
+        }
+        Command::ExportAll {
+            layout,
+            format,
+            output,
+        } => {
+            let all_bonds = load(&cli)?;
+            let (layout, format) = (*layout, *format);
+            // Bonds are written one at a time, the export is never held in memory as a whole
+            match output {
+                Some(path) => {
+                    let file = File::create(path)
+                        .with_context(|| format!("Failed to create {}", path.display()))?;
+                    bonds_export::export_all(&all_bonds, layout, format, BufWriter::new(file))?
+                }
+                None => bonds_export::export_all(
+                    &all_bonds,
+                    layout,
+                    format,
+                    BufWriter::new(std::io::stdout()),
+                )?,
+            }
+        }
+        Command::Value { id, date } => {
+            let all_bonds = load(&cli)?;
+            let bond = find(&all_bonds, id)?;
+            match bond.value_on(*date) {
+                Some(value) => println!("{value:.2}"),
+                None => bail!("No value of {id} on {date}"),
+            }
+        }
+        Command::Validate { workbook, json } => {
+            let report = bonds_reader::validate_workbook(workbook)
+                .with_context(|| format!("Invalid workbook: {}", workbook.display()))?;
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print!("{report}");
+            }
+            if report.has_errors() {
+                bail!("Workbook {} has errors", workbook.display());
+            }
+            if *json {
+                return Ok(());
+            }
+
+            let (all_bonds, statistics) = bonds_reader::read_bonds_with_statistics(workbook)
+                .with_context(|| format!("Invalid workbook: {}", workbook.display()))?;
+            for sheet in statistics {
+                let sale_range = match (sheet.first_sale, sheet.last_sale) {
+                    (Some(first), Some(last)) => format!("sold {first} to {last}"),
+                    _ => "no sales".to_string(),
+                };
+                println!("{}: {} bonds, {sale_range}", sheet.bond_type, sheet.bonds);
+            }
+            println!("OK, {} bonds", all_bonds.len());
+        }
+        Command::Compile { output } => {
+            let all_bonds = read_workbook(&cli.workbook)?;
+            bonds_reader::write_dataset(&all_bonds, output)
+                .with_context(|| format!("Failed to write dataset: {}", output.display()))?;
+            println!(
+                "Compiled {} bonds into {}",
+                all_bonds.len(),
+                output.display()
+            );
+        }
+        Command::Diff { old, new, json } => {
+            let diff = BondsDiff::between(&read_workbook(old)?, &read_workbook(new)?);
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&diff)?);
+            } else {
+                print!("{diff}");
+            }
+            if diff.historical_data_changed() {
+                bail!(
+                    "Historical data changed between {} and {}",
+                    old.display(),
+                    new.display()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn load(cli: &Cli) -> Result<AllBonds> {
+    match &cli.dataset {
+        Some(dataset) => bonds_reader::read_dataset(dataset)
+            .with_context(|| format!("Failed to read Bonds from dataset: {}", dataset.display())),
+        None => read_workbook(&cli.workbook),
+    }
+}
+
+fn read_workbook(workbook: &Path) -> Result<AllBonds> {
+    bonds_reader::read_bonds(workbook)
+        .with_context(|| format!("Failed to read Bonds from workbook: {}", workbook.display()))
+}
+
+fn find<'a>(all_bonds: &'a AllBonds, id: &BondId) -> Result<&'a Bond> {
+    all_bonds
+        .get(id)
+        .with_context(|| format!("Bond with ID {id} not found"))
+}
+
+fn print_terms(bond: &Bond) {
+    let bond_type = bond.id.bond_type();
+    println!("ID:          {}", bond.id);
+    println!("Type:        {bond_type}");
+    println!("Term:        {} months", bond_type.term_in_months());
+    println!("Sale:        {} to {}", bond.initial_date, bond.sale_end);
+    println!("Buyout:      {}", bond.buyout_date);
+    for (year, rate) in bond.rates.iter().enumerate() {
+        println!("Year {:>2}:     {:.2}%", year + 1, rate * 100.0);
+    }
 }
@@ -0,0 +1,20 @@
+//! Bonds data bundled into the binary by the `embedded-dataset` feature, used when no
+//! `bonds_location` is configured
+
+use include_dir::{Dir, include_dir};
+
+static ASSETS: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/assets");
+
+const WORKBOOK: &str = "Dane_dotyczace_obligacji_detalicznych.xls";
+/// Written by `tool compile` before building, see the README
+const DATASET: &str = "bonds.dataset";
+
+/// The issuer workbook from `assets/`
+pub fn workbook() -> Option<&'static [u8]> {
+    ASSETS.get_file(WORKBOOK).map(|file| file.contents())
+}
+
+/// The dataset compiled into `assets/` before building, if there was one
+pub fn dataset() -> Option<&'static [u8]> {
+    ASSETS.get_file(DATASET).map(|file| file.contents())
+}
@@ -0,0 +1,3 @@
+#[cfg(feature = "embedded-dataset")]
+pub mod embedded;
+pub mod settings;
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+/// Application specific configuration, read from the `settings` section of the config file
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Settings {
+    /// Path to the issuer workbook. Overrides the data embedded with the `embedded-dataset`
+    /// feature, and is required without it.
+    #[serde(default)]
+    pub bonds_location: Option<String>,
+    /// Path to a binary dataset compiled with `tool compile`. When it cannot be loaded,
+    /// bonds are read from `bonds_location` instead.
+    #[serde(default)]
+    pub dataset_location: Option<String>,
+    /// Key expected in the `X-Admin-Key` header of admin requests, admin endpoints reject
+    /// every request when it is missing or empty
+    #[serde(default)]
+    pub admin_api_key: Option<String>,
+    /// Directory keeping a dataset for every ingested workbook, enables `as_of` queries
+    #[serde(default)]
+    pub versions_location: Option<String>,
+    /// SQLite database holding the bonds and dataset versions instead of memory, e.g.
+    /// `sqlite://bonds.sqlite`. Requires the `sqlite` feature, ignored when empty.
+    #[serde(default)]
+    pub database_url: Option<String>,
+    /// Supplementary sources merged on top of the workbook, each one taking precedence over
+    /// the workbook and the sources listed before it
+    #[serde(default)]
+    pub sources: Vec<SourceSettings>,
+    /// Keys accepted in the `X-API-Key` header. Once any is configured, only `/bonds` can be
+    /// requested without one.
+    #[serde(default)]
+    pub api_keys: Vec<ApiKeySettings>,
+}
+
+/// Bonds data besides the issuer workbook, e.g. our own corrections
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SourceSettings {
+    /// Reported as the provenance of the fields the source supplies
+    pub name: String,
+    pub location: String,
+    pub format: SourceFormat,
+}
+
+/// API client allowed a quota of requests
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ApiKeySettings {
+    /// Identifies the client in traces and errors, unlike the key itself
+    pub id: String,
+    /// Ignored when empty
+    pub key: String,
+    pub requests_per_minute: u32,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceFormat {
+    /// Workbook in the issuer's layout, every series in it overrides all of its fields
+    Workbook,
+    /// Corrections CSV, see [`bonds_reader::read_corrections`]
+    Csv,
+    /// Bonds defined by hand in TOML, JSON or CSV, told apart by the file extension, see
+    /// [`bonds_reader::read_definitions`]
+    Definitions,
+}
+
+impl Settings {
+    pub fn from_json(value: &serde_json::Value) -> anyhow::Result<Self> {
+        Ok(serde_json::from_value(value.clone())?)
+    }
+
+    pub fn database_url(&self) -> Option<&str> {
+        self.database_url.as_deref().filter(|url| !url.is_empty())
+    }
+
+    pub fn api_keys(&self) -> impl Iterator<Item = &ApiKeySettings> {
+        self.api_keys
+            .iter()
+            .filter(|api_key| !api_key.key.is_empty())
+    }
+}
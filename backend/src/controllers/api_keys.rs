@@ -0,0 +1,63 @@
+use crate::services::api_keys::{Access, ApiKeys};
+use axum::Json;
+use axum::extract::{Request, State};
+use axum::http::header::RETRY_AFTER;
+use axum::http::{HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use loco_rs_otel::SpanAttributes;
+use openapi::models::ErrorResponse;
+use std::sync::Arc;
+
+pub(crate) const API_KEY_HEADER: &str = "X-API-Key";
+
+/// Rejects requests without a valid key and over the quota of their key, except for the
+/// bonds catalogue that stays open to anonymous clients and for the accounts, which users
+/// reach with their token
+pub(crate) async fn check_api_key(
+    State(api_keys): State<Arc<ApiKeys>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let key = request
+        .headers()
+        .get(API_KEY_HEADER)
+        .map(HeaderValue::as_bytes);
+
+    match api_keys.check(key) {
+        Access::Anonymous if allows_anonymous(request.uri().path()) => next.run(request).await,
+        Access::Anonymous => error(StatusCode::UNAUTHORIZED, "Missing API key".to_string()),
+        Access::UnknownKey => error(StatusCode::UNAUTHORIZED, "Invalid API key".to_string()),
+        Access::Granted(id) => with_key_id(next.run(request).await, id),
+        Access::QuotaExceeded { id, retry_after } => {
+            let mut response = error(
+                StatusCode::TOO_MANY_REQUESTS,
+                format!("Quota of API key [{id}] exceeded"),
+            );
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(retry_after.as_secs() + 1));
+            with_key_id(response, id)
+        }
+    }
+}
+
+fn allows_anonymous(path: &str) -> bool {
+    ["/bonds", "/auth", "/portfolios"].iter().any(|prefix| {
+        path.strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    })
+}
+
+fn error(status: StatusCode, message: String) -> Response {
+    (status, Json(ErrorResponse::new(message))).into_response()
+}
+
+/// Has the key ID recorded on the request span by the `OtelInitializer` layers
+fn with_key_id(mut response: Response, id: String) -> Response {
+    response
+        .extensions_mut()
+        .get_or_insert_default::<SpanAttributes>()
+        .insert("api_key.id", id);
+    response
+}
@@ -0,0 +1,35 @@
+use bonds_reader::DatasetVersion;
+use chrono::{DateTime, Utc};
+
+/// Caches may keep the data, but have to check that it is still current before using it
+pub(crate) const CACHE_CONTROL: &str = "public, no-cache";
+
+/// Changes with every dataset version, even when the content of the resource stays the same.
+/// Every representation of a bond, e.g. `EDO0835.parquet`, and every bulk export, e.g.
+/// `export.zip.csv`, is a resource of its own.
+pub(crate) fn etag(resource: &str, version: &DatasetVersion) -> String {
+    let hash = version.hash.get(..16).unwrap_or(&version.hash);
+    format!("\"{resource}-{hash}-{}\"", version.ingested_at.timestamp())
+}
+
+/// Whether the client already has the current data, `If-None-Match` taking precedence over
+/// `If-Modified-Since` like in RFC 9110
+pub(crate) fn is_not_modified(
+    if_none_match: Option<&str>,
+    if_modified_since: Option<DateTime<Utc>>,
+    etag: &str,
+    last_modified: DateTime<Utc>,
+) -> bool {
+    if let Some(if_none_match) = if_none_match {
+        return if_none_match.trim() == "*"
+            || if_none_match
+                .split(',')
+                .any(|tag| weak_eq(tag.trim(), etag));
+    }
+    if_modified_since.is_some_and(|since| last_modified.timestamp() <= since.timestamp())
+}
+
+/// Validators are compared ignoring the weakness indicator
+fn weak_eq(a: &str, b: &str) -> bool {
+    a.trim_start_matches("W/") == b.trim_start_matches("W/")
+}
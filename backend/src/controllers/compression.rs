@@ -0,0 +1,487 @@
+use crate::services::encoding::Encoding;
+use axum::body::{Body, Bytes, HttpBody};
+use axum::extract::{Request, State};
+use axum::http::header::{
+    ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE,
+    IF_NONE_MATCH, VARY,
+};
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use bonds_reader::DatasetVersion;
+use std::collections::{HashMap, VecDeque};
+use std::io::{ErrorKind, Write};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::Instrument;
+
+/// Smaller bodies are sent as they are, compressing them saves next to nothing
+const MIN_COMPRESSED_SIZE: usize = 1024;
+/// Bytes of encoded payloads kept at most, the ones cached first are dropped first
+const MAX_PAYLOAD_BYTES: usize = 64 * 1024 * 1024;
+/// Request URIs whose last `ETag` is remembered at most, forgotten all at once
+const MAX_URIS: usize = 16 * 1024;
+/// Streamed bodies are only kept when their encoding stays this small, e.g. the CSV of a single
+/// bond but not a bulk export
+const MAX_STREAMED_PAYLOAD: usize = 1024 * 1024;
+
+/// `ETag` of the resource and the coding negotiated for it, so that a resource requested under
+/// several URIs is kept once
+type PayloadKey = (HeaderValue, Encoding);
+
+/// Encoded bodies of responses with an `ETag`, ready to be sent again while the dataset version
+/// they were rendered from is served
+#[derive(Default)]
+pub(crate) struct Payloads {
+    state: Mutex<CachedPayloads>,
+}
+
+#[derive(Default)]
+struct CachedPayloads {
+    /// Hash of the dataset version being served when the payloads were kept
+    version: Option<String>,
+    /// Last `ETag` answered for a request URI
+    etags: HashMap<String, HeaderValue>,
+    payloads: HashMap<PayloadKey, Payload>,
+    /// Keys in the order they were cached
+    order: VecDeque<PayloadKey>,
+    /// Bytes of all the kept bodies
+    size: usize,
+}
+
+#[derive(Clone)]
+struct Payload {
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl Payloads {
+    fn state(&self) -> std::sync::MutexGuard<'_, CachedPayloads> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Drops every payload once another dataset version is served
+    pub(crate) fn serve_version(&self, version: &DatasetVersion) {
+        let mut state = self.state();
+        if state.version.as_ref() != Some(&version.hash) {
+            *state = CachedPayloads {
+                version: Some(version.hash.clone()),
+                ..CachedPayloads::default()
+            };
+        }
+    }
+
+    /// Payload last sent for the URI, with its `ETag`
+    fn get(&self, uri: &str, encoding: Encoding) -> Option<(HeaderValue, Payload)> {
+        let state = self.state();
+        let etag = state.etags.get(uri)?;
+        let payload = state.payloads.get(&(etag.clone(), encoding))?;
+        Some((etag.clone(), payload.clone()))
+    }
+
+    fn insert(&self, uri: String, encoding: Encoding, etag: HeaderValue, payload: Payload) {
+        if payload.body.len() > MAX_PAYLOAD_BYTES {
+            return;
+        }
+        let mut state = self.state();
+        if state.etags.len() == MAX_URIS && !state.etags.contains_key(&uri) {
+            state.etags.clear();
+        }
+        state.etags.insert(uri, etag.clone());
+
+        let key = (etag, encoding);
+        if state.payloads.contains_key(&key) {
+            return;
+        }
+        state.size += payload.body.len();
+        state.payloads.insert(key.clone(), payload);
+        state.order.push_back(key);
+        while state.size > MAX_PAYLOAD_BYTES
+            && let Some(oldest) = state.order.pop_front()
+        {
+            if let Some(payload) = state.payloads.remove(&oldest) {
+                state.size -= payload.body.len();
+            }
+        }
+    }
+}
+
+/// Compresses responses with the coding preferred by the client.
+///
+/// Bodies of responses with an `ETag` are kept encoded. Later requests for them are turned into
+/// conditional ones, and the kept body is sent when the handler answers `304 Not Modified`, so
+/// that bonds are neither rendered nor compressed again until the dataset version changes.
+pub(crate) async fn compress_response(
+    State(payloads): State<Arc<Payloads>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let encoding = request
+        .headers()
+        .get(ACCEPT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .map_or(Encoding::Identity, Encoding::negotiate);
+    if request.method() != Method::GET {
+        return encode(next.run(request).await, encoding, None).await;
+    }
+
+    let uri = request.uri().to_string();
+    // Conditional requests of the client are left for the handler to answer
+    let is_conditional = request.headers().contains_key(IF_NONE_MATCH)
+        || request.headers().contains_key(IF_MODIFIED_SINCE);
+    let cached = if is_conditional {
+        None
+    } else {
+        payloads.get(&uri, encoding)
+    };
+    if let Some((etag, _)) = &cached {
+        request.headers_mut().insert(IF_NONE_MATCH, etag.clone());
+    }
+
+    let response = next.run(request).await;
+    if let Some((etag, cached)) = cached
+        && response.status() == StatusCode::NOT_MODIFIED
+        && response.headers().get(ETAG) == Some(&etag)
+    {
+        let mut response = Response::new(Body::from(cached.body));
+        *response.headers_mut() = cached.headers;
+        return response;
+    }
+    encode(response, encoding, Some((&payloads, uri))).await
+}
+
+/// Where an encoded body is kept, with the URI it was requested with
+type Cache<'a> = Option<(&'a Arc<Payloads>, String)>;
+
+/// Encodes the body of the response, keeping it in `cache` when the response has an `ETag`
+async fn encode(response: Response, encoding: Encoding, cache: Cache<'_>) -> Response {
+    if !is_compressible(response.headers()) {
+        return response;
+    }
+    // Bodies of unknown size are streamed, they are encoded chunk by chunk as they come
+    if response.body().size_hint().exact().is_none() {
+        return encode_stream(response, encoding, cache);
+    }
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::error!(error = ?e, "Failed to read response body");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    parts
+        .headers
+        .append(VARY, HeaderValue::from_static("accept-encoding"));
+    let body = match encoding {
+        Encoding::Identity => bytes,
+        _ if bytes.len() < MIN_COMPRESSED_SIZE => bytes,
+        encoding => match encode_blocking(encoding, bytes.clone()).await {
+            Ok(encoded) => {
+                parts
+                    .headers
+                    .insert(CONTENT_ENCODING, HeaderValue::from_static(encoding.name()));
+                parts.headers.remove(CONTENT_LENGTH);
+                Bytes::from(encoded)
+            }
+            Err(e) => {
+                tracing::warn!(error = ?e, encoding = encoding.name(), "Failed to encode response");
+                bytes
+            }
+        },
+    };
+
+    if let Some((payloads, uri)) = cache
+        && parts.status == StatusCode::OK
+        && let Some(etag) = parts.headers.get(ETAG)
+    {
+        let payload = Payload {
+            headers: parts.headers.clone(),
+            body: body.clone(),
+        };
+        payloads.insert(uri, encoding, etag.clone(), payload);
+    }
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Compresses a whole body on a blocking thread, brotli and zstd taking milliseconds for the
+/// larger ones
+async fn encode_blocking(encoding: Encoding, bytes: Bytes) -> std::io::Result<Vec<u8>> {
+    tokio::task::spawn_blocking(move || encoding.encode(&bytes))
+        .await
+        .map_err(std::io::Error::other)?
+}
+
+/// Encodes the streamed body while it is sent. Unlike with bodies of known size, small ones are
+/// encoded as well.
+fn encode_stream(response: Response, encoding: Encoding, cache: Cache<'_>) -> Response {
+    let (mut parts, body) = response.into_parts();
+    parts
+        .headers
+        .append(VARY, HeaderValue::from_static("accept-encoding"));
+    if encoding != Encoding::Identity {
+        parts
+            .headers
+            .insert(CONTENT_ENCODING, HeaderValue::from_static(encoding.name()));
+        parts.headers.remove(CONTENT_LENGTH);
+    }
+
+    let cache = cache
+        .filter(|_| parts.status == StatusCode::OK)
+        .and_then(|(payloads, uri)| {
+            let etag = parts.headers.get(ETAG)?.clone();
+            let payload = Payload {
+                headers: parts.headers.clone(),
+                body: Bytes::new(),
+            };
+            Some((payloads.clone(), uri, etag, payload))
+        });
+    let (sender, receiver) = mpsc::channel(1);
+    let span = tracing::Span::current();
+    tokio::spawn(
+        async move {
+            let kept = cache.is_some().then(Vec::new);
+            match encode_chunks(body, encoding, &sender, kept).await {
+                Ok(Some(body)) => {
+                    if let Some((payloads, uri, etag, mut payload)) = cache {
+                        payload.body = Bytes::from(body);
+                        payloads.insert(uri, encoding, etag, payload);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) if !sender.is_closed() => {
+                    tracing::error!(error = ?e, "Failed to encode response body");
+                    let _ = sender.send(Err(e)).await;
+                }
+                Err(_) => {}
+            }
+        }
+        .instrument(span),
+    );
+    Response::from_parts(parts, Body::from_stream(ReceiverStream::new(receiver)))
+}
+
+/// Sends the body encoded chunk by chunk, each one compressed on the blocking pool so that no
+/// thread waits there for the next chunk. Returns the whole encoded body when `kept` is given and
+/// it stays under [`MAX_STREAMED_PAYLOAD`].
+async fn encode_chunks(
+    body: Body,
+    encoding: Encoding,
+    sender: &mpsc::Sender<std::io::Result<Bytes>>,
+    mut kept: Option<Vec<u8>>,
+) -> std::io::Result<Option<Vec<u8>>> {
+    let mut chunks = body.into_data_stream();
+    let mut encoder = Some(encoding.encoder(Vec::new())?);
+    while let Some(mut current) = encoder.take() {
+        let chunk = chunks
+            .next()
+            .await
+            .transpose()
+            .map_err(std::io::Error::other)?;
+        let encoded: Vec<u8>;
+        (encoder, encoded) = tokio::task::spawn_blocking(move || match chunk {
+            Some(chunk) => {
+                current.write_all(&chunk)?;
+                let encoded = std::mem::take(current.get_mut());
+                Ok::<_, std::io::Error>((Some(current), encoded))
+            }
+            None => Ok((None, current.finish()?)),
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+
+        if encoded.is_empty() {
+            continue;
+        }
+        if let Some(body) = &mut kept {
+            body.extend_from_slice(&encoded);
+            if body.len() > MAX_STREAMED_PAYLOAD {
+                kept = None;
+            }
+        }
+        sender.send(Ok(Bytes::from(encoded))).await.map_err(|_| {
+            std::io::Error::new(ErrorKind::BrokenPipe, "Client closed the connection")
+        })?;
+    }
+    Ok(kept)
+}
+
+/// Text responses that are not encoded yet
+fn is_compressible(headers: &HeaderMap) -> bool {
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    !headers.contains_key(CONTENT_ENCODING)
+        && (content_type.starts_with("application/json")
+            || content_type.starts_with("application/x-ndjson")
+            || content_type.starts_with("text/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::routing::get;
+    use axum_test::TestServer;
+    use pretty_assertions::assert_eq;
+    use std::io::Read;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const VALUES_ETAG: &str = "\"values-1\"";
+
+    /// Serves values with an `ETag` behind the middleware, at once and streamed, counting how many
+    /// times they are rendered
+    fn server(renders: Arc<AtomicUsize>, payloads: Arc<Payloads>) -> TestServer {
+        let render = move |headers: HeaderMap| {
+            let not_modified = headers
+                .get(IF_NONE_MATCH)
+                .is_some_and(|etag| etag == VALUES_ETAG);
+            if !not_modified {
+                renders.fetch_add(1, Ordering::SeqCst);
+            }
+            not_modified
+        };
+        let streamed = render.clone();
+        let values = move |headers: HeaderMap| async move {
+            if render(headers) {
+                return (StatusCode::NOT_MODIFIED, [(ETAG, VALUES_ETAG)]).into_response();
+            }
+            let headers = [(CONTENT_TYPE, "text/csv"), (ETAG, VALUES_ETAG)];
+            (headers, "2025-08-01,100.00\n".repeat(100)).into_response()
+        };
+        let stream = move |headers: HeaderMap| async move {
+            if streamed(headers) {
+                return (StatusCode::NOT_MODIFIED, [(ETAG, VALUES_ETAG)]).into_response();
+            }
+            let lines = (0..100).map(|_| Ok::<_, std::io::Error>("2025-08-01,100.00\n"));
+            let headers = [(CONTENT_TYPE, "text/csv"), (ETAG, VALUES_ETAG)];
+            (headers, Body::from_stream(tokio_stream::iter(lines))).into_response()
+        };
+        let app = Router::new()
+            .route("/values", get(values))
+            .route("/stream", get(stream))
+            .layer(axum::middleware::from_fn_with_state(
+                payloads,
+                compress_response,
+            ));
+        TestServer::new(app).unwrap()
+    }
+
+    fn version(hash: &str) -> DatasetVersion {
+        DatasetVersion {
+            hash: hash.to_string(),
+            ingested_at: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_kept_payload_without_rendering_again() {
+        let renders = Arc::new(AtomicUsize::new(0));
+        let server = server(renders.clone(), Arc::default());
+
+        let res = server
+            .get("/values")
+            .add_header("Accept-Encoding", "br")
+            .await;
+        let cached = server
+            .get("/values")
+            .add_header("Accept-Encoding", "br")
+            .await;
+
+        assert_eq!(renders.load(Ordering::SeqCst), 1);
+        assert_eq!(cached.status_code(), 200);
+        assert_eq!(cached.header("Content-Encoding"), "br");
+        assert_eq!(cached.as_bytes(), res.as_bytes());
+
+        // Every coding is kept on its own
+        server
+            .get("/values")
+            .add_header("Accept-Encoding", "gzip")
+            .await;
+        assert_eq!(renders.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_leave_conditional_requests_to_handler() {
+        let renders = Arc::new(AtomicUsize::new(0));
+        let server = server(renders.clone(), Arc::default());
+        server
+            .get("/values")
+            .add_header("Accept-Encoding", "br")
+            .await;
+
+        let res = server
+            .get("/values")
+            .add_header("Accept-Encoding", "br")
+            .add_header("If-None-Match", VALUES_ETAG)
+            .await;
+
+        assert_eq!(res.status_code(), 304);
+        assert!(res.as_bytes().is_empty());
+        assert_eq!(renders.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_keep_streamed_payload() {
+        let renders = Arc::new(AtomicUsize::new(0));
+        let server = server(renders.clone(), Arc::default());
+
+        let res = server
+            .get("/stream")
+            .add_header("Accept-Encoding", "br")
+            .await;
+        let cached = server
+            .get("/stream")
+            .add_header("Accept-Encoding", "br")
+            .await;
+
+        assert_eq!(res.header("Content-Encoding"), "br");
+        let mut csv = String::new();
+        brotli::Decompressor::new(res.as_bytes().as_ref(), 4096)
+            .read_to_string(&mut csv)
+            .unwrap();
+        assert_eq!(csv, "2025-08-01,100.00\n".repeat(100));
+        assert_eq!(renders.load(Ordering::SeqCst), 1);
+        assert_eq!(cached.as_bytes(), res.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_share_payload_of_same_etag() {
+        let payloads = Arc::new(Payloads::default());
+        let server = server(Arc::default(), payloads.clone());
+
+        for uri in ["/values", "/values?delimiter=,"] {
+            server.get(uri).add_header("Accept-Encoding", "br").await;
+        }
+
+        let state = payloads.state();
+        assert_eq!(state.etags.len(), 2);
+        assert_eq!(state.payloads.len(), 1);
+        assert_eq!(
+            state.size,
+            state.payloads.values().next().unwrap().body.len()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_forget_payloads_of_previous_version() {
+        let renders = Arc::new(AtomicUsize::new(0));
+        let payloads = Arc::new(Payloads::default());
+        let server = server(renders.clone(), payloads.clone());
+        let get = || server.get("/values").add_header("Accept-Encoding", "br");
+
+        payloads.serve_version(&version("1"));
+        get().await;
+        payloads.serve_version(&version("1"));
+        get().await;
+        assert_eq!(renders.load(Ordering::SeqCst), 1);
+
+        payloads.serve_version(&version("2"));
+        get().await;
+        assert_eq!(renders.load(Ordering::SeqCst), 2);
+    }
+}
@@ -1 +1,5 @@
+pub(crate) mod api_keys;
+pub(crate) mod caching;
+pub(crate) mod compression;
 pub(crate) mod openapi;
+pub(crate) mod streaming;
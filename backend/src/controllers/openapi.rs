@@ -1,18 +1,72 @@
-use crate::services::bonds::{BondsService, BondsServiceImpl};
+use crate::common::settings::Settings;
+use crate::controllers::api_keys::{API_KEY_HEADER, check_api_key};
+use crate::controllers::caching;
+use crate::controllers::compression::{Payloads, compress_response};
+use crate::controllers::streaming::stream_body;
+use crate::services::accounts::{Accounts, LoginUnavailable, Registration, create_account_store};
+use crate::services::api_keys::{ApiKeys, constant_time_eq};
+use crate::services::bonds::{
+    BondsService, NoVersionAsOf, WorkbookProblem, WorkbookUpload, create_bonds_service,
+};
+use crate::services::catalogue::{BondsQuery, Pagination, SaleMonth};
+use crate::services::portfolios::{
+    InvalidLot, Lot, Portfolio, PortfolioValuation, check_lots, value_portfolio,
+};
 use anyhow::{Context, Error};
 use async_trait::async_trait;
-use axum::http::Method;
+use axum::extract::Multipart;
+use axum::extract::multipart::MultipartError;
+use axum::http::header::ACCEPT;
+use axum::http::{HeaderMap, Method, StatusCode};
 use axum_extra::extract::{CookieJar, Host};
+use bonds_export::{Column, CsvOptions, Format, Layout};
+use bonds_reader::Severity;
+use chrono::NaiveDate;
 use loco_rs::app::AppContext;
 use loco_rs::controller::Routes;
-use model::BondId;
-use openapi::apis::ErrorHandler;
-use openapi::apis::default::GetBondsResponse::Status200_AJSONArrayOfBondNames;
-use openapi::apis::default::{GetBondCsvResponse, GetBondResponse, GetBondsResponse};
-use openapi::models::{GetBond404Response, GetBondCsvPathParams, GetBondPathParams};
+use model::{BondId, BondType};
+use openapi::apis::admin::{Admin, UploadWorkbookResponse};
+use openapi::apis::auth::{Auth, CurrentUserResponse, LoginResponse, RegisterResponse};
+use openapi::apis::default::GetBondsResponse::{
+    Status200_AJSONArrayOfBondNames, Status400_InvalidQueryParameters,
+    Status404_NoDatasetVersionPublishedAsOfTheGivenDate,
+};
+use openapi::apis::default::{
+    ExportBondsResponse, GetBondCsvResponse, GetBondResponse, GetBondsResponse,
+    ListVersionsResponse,
+};
+use openapi::apis::portfolios::{
+    CreatePortfolioResponse, DeletePortfolioResponse, GetPortfolioResponse, ListPortfoliosResponse,
+    Portfolios, UpdatePortfolioResponse, ValuePortfolioResponse,
+};
+use openapi::apis::{ApiAuthBasic, ApiKeyAuthHeader, BasicAuthKind, ErrorHandler};
+use openapi::models::{
+    self, BondProvenance, CurrentUser, DatasetVersion, DeletePortfolioPathParams, ErrorResponse,
+    ExportBondsHeaderParams, ExportBondsQueryParams, GetBond200Response, GetBondCsvHeaderParams,
+    GetBondCsvPathParams, GetBondCsvQueryParams, GetBondHeaderParams, GetBondPathParams,
+    GetBondQueryParams, GetBondsQueryParams, GetPortfolioPathParams, LoginParams, LoginToken,
+    LotValuation, PortfolioParams, RegisterParams, SheetStatistics, UpdatePortfolioPathParams,
+    ValuePortfolioPathParams, ValuePortfolioQueryParams, WorkbookDiagnostic, WorkbookReport,
+};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
 
 struct ServerImpl {
-    bonds_service: Box<dyn BondsService + Send + Sync>,
+    bonds_service: Arc<dyn BondsService + Send + Sync>,
+    accounts: Accounts,
+    admin_api_key: Option<String>,
+    /// Encoded bodies kept by `compress_response`
+    payloads: Arc<Payloads>,
+}
+
+/// Authenticated caller
+enum Claims {
+    /// Caller with the admin key
+    Admin,
+    /// Caller let through by `check_api_key`, with a valid key or while none are configured
+    Client,
+    /// Logged in user, by pid
+    User(String),
 }
 
 impl AsRef<ServerImpl> for ServerImpl {
@@ -22,10 +76,48 @@ impl AsRef<ServerImpl> for ServerImpl {
 }
 
 impl ServerImpl {
-    fn new(bonds_service: impl BondsService + Send + Sync + 'static) -> Self {
+    fn new(
+        bonds_service: Arc<dyn BondsService + Send + Sync>,
+        accounts: Accounts,
+        admin_api_key: Option<String>,
+        payloads: Arc<Payloads>,
+    ) -> Self {
         Self {
-            bonds_service: Box::new(bonds_service),
+            bonds_service,
+            accounts,
+            admin_api_key: admin_api_key.filter(|key| !key.is_empty()),
+            payloads,
+        }
+    }
+
+    /// Dataset version served on the day, the current one without `as_of`. The kept payloads
+    /// are dropped once the current version changes.
+    async fn version_as_of(
+        &self,
+        as_of: Option<NaiveDate>,
+    ) -> anyhow::Result<bonds_reader::DatasetVersion> {
+        let version = self.bonds_service.version_as_of(as_of).await?;
+        if as_of.is_none() {
+            self.payloads.serve_version(&version);
         }
+        Ok(version)
+    }
+
+    /// Lots of the request, failing with [`InvalidLot`] when one of them can not have been bought
+    async fn lots(&self, lots: &[models::Lot]) -> anyhow::Result<Vec<Lot>> {
+        let lots = lots
+            .iter()
+            .map(|lot| {
+                Ok(Lot {
+                    bond_id: BondId::new(lot.bond_id.clone())
+                        .map_err(|e| InvalidLot(e.to_string()))?,
+                    quantity: lot.quantity,
+                    purchase_date: lot.purchase_date,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        check_lots(self.bonds_service.as_ref(), &lots).await?;
+        Ok(lots)
     }
 }
 
@@ -34,6 +126,71 @@ impl ErrorHandler<Error> for ServerImpl {}
 #[allow(unused_variables)]
 #[async_trait]
 impl openapi::apis::default::Default<Error> for ServerImpl {
+    type Claims = Claims;
+
+    #[tracing::instrument(
+        err(Debug),
+        skip(self, method, host, cookies, claims),
+        name = "export_bonds"
+    )]
+    async fn export_bonds(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+        header_params: &ExportBondsHeaderParams,
+        query_params: &ExportBondsQueryParams,
+    ) -> Result<ExportBondsResponse, Error> {
+        let (layout, format) = match export_layout(query_params) {
+            Ok(export) => export,
+            Err(e) => {
+                return Ok(ExportBondsResponse::Status400_InvalidLayoutOrFormat(
+                    ErrorResponse::new(format!("{e:#}")),
+                ));
+            }
+        };
+        let version = match self.version_as_of(query_params.as_of).await {
+            Ok(version) => version,
+            Err(e) if e.is::<NoVersionAsOf>() => {
+                return Ok(
+                    ExportBondsResponse::Status404_NoDatasetVersionPublishedAsOfTheGivenDate(
+                        ErrorResponse::new(e.to_string()),
+                    ),
+                );
+            }
+            Err(e) => return Err(e),
+        };
+
+        let resource = format!("export.{layout}.{format}");
+        let e_tag = caching::etag(&resource, &version);
+        if caching::is_not_modified(
+            header_params.if_none_match.as_deref(),
+            header_params.if_modified_since,
+            &e_tag,
+            version.ingested_at,
+        ) {
+            return Ok(ExportBondsResponse::Status304_NotModified {
+                e_tag,
+                cache_control: caching::CACHE_CONTROL.to_string(),
+            });
+        }
+        let (all_bonds, version) = self.bonds_service.get_all_bonds(query_params.as_of).await?;
+        Ok(ExportBondsResponse::Status200_ValuesOfEveryBond {
+            body: stream_body(move |writer| {
+                bonds_export::export_all(&all_bonds, layout, format, writer)
+            }),
+            content_type: layout.content_type(format).to_string(),
+            content_disposition: format!(
+                "attachment; filename=\"bonds.{}\"",
+                layout.extension(format)
+            ),
+            e_tag: caching::etag(&resource, &version),
+            last_modified: version.ingested_at,
+            cache_control: caching::CACHE_CONTROL.to_string(),
+        })
+    }
+
     // Instrument and skip everything except for path_params
     #[tracing::instrument(err(Debug), skip(self, method, host, cookies), name = "get_bond")]
     async fn get_bond(
@@ -41,9 +198,70 @@ impl openapi::apis::default::Default<Error> for ServerImpl {
         method: &Method,
         host: &Host,
         cookies: &CookieJar,
+        header_params: &GetBondHeaderParams,
         path_params: &GetBondPathParams,
+        query_params: &GetBondQueryParams,
     ) -> Result<GetBondResponse, Error> {
-        Err(anyhow::anyhow!("TEST").context("Failed to get bond"))
+        let not_found = || {
+            GetBondResponse::Status404_BondNotFound(ErrorResponse::new(format!(
+                "Bond with ID {} not found",
+                path_params.id.clone()
+            )))
+        };
+        let Ok(bond_id) = BondId::new(path_params.id.clone()) else {
+            return Ok(not_found());
+        };
+
+        let version = match self.version_as_of(query_params.as_of).await {
+            Ok(version) => version,
+            Err(e) if e.is::<NoVersionAsOf>() => {
+                return Ok(GetBondResponse::Status404_BondNotFound(ErrorResponse::new(
+                    e.to_string(),
+                )));
+            }
+            Err(e) => return Err(e),
+        };
+        let e_tag = caching::etag(bond_id.as_str(), &version);
+        if caching::is_not_modified(
+            header_params.if_none_match.as_deref(),
+            header_params.if_modified_since,
+            &e_tag,
+            version.ingested_at,
+        ) {
+            return Ok(GetBondResponse::Status304_NotModified {
+                e_tag,
+                cache_control: caching::CACHE_CONTROL.to_string(),
+            });
+        }
+
+        let Some((bond, provenance, version)) = self
+            .bonds_service
+            .get_bond_with_provenance(&bond_id, query_params.as_of)
+            .await?
+        else {
+            return Ok(not_found());
+        };
+        Ok(GetBondResponse::Status200_ASingleBondObject {
+            body: GetBond200Response::new(
+                bond.id.to_string(),
+                bond.id.to_string(),
+                bond.initial_date,
+                bond.sale_end,
+                bond.buyout_date,
+                bond.nominal().unwrap_or_default(),
+                bond.rates.clone(),
+                BondProvenance::new(
+                    provenance.series,
+                    provenance.initial_date,
+                    provenance.sale_end,
+                    provenance.nominal,
+                    provenance.rates,
+                ),
+            ),
+            e_tag: caching::etag(bond_id.as_str(), &version),
+            last_modified: version.ingested_at,
+            cache_control: caching::CACHE_CONTROL.to_string(),
+        })
     }
 
     #[tracing::instrument(err(Debug), skip(self, method, host, cookies), name = "get_bond_csv")]
@@ -52,22 +270,91 @@ impl openapi::apis::default::Default<Error> for ServerImpl {
         method: &Method,
         host: &Host,
         cookies: &CookieJar,
+        header_params: &GetBondCsvHeaderParams,
         path_params: &GetBondCsvPathParams,
+        query_params: &GetBondCsvQueryParams,
     ) -> Result<GetBondCsvResponse, Error> {
-        let bond_id = BondId::new(path_params.id.clone());
+        let format = match series_format(header_params, query_params) {
+            Ok(Some(format)) => format,
+            Ok(None) => {
+                return Ok(
+                    GetBondCsvResponse::Status406_NoneOfTheAcceptedMediaTypesCanBeProduced(
+                        ErrorResponse::new(format!(
+                            "None of {} can be produced, expected one of {}",
+                            header_params.accept.as_deref().unwrap_or_default(),
+                            Format::ALL.map(Format::content_type).join(", ")
+                        )),
+                    ),
+                );
+            }
+            Err(e) => {
+                return Ok(GetBondCsvResponse::Status400_InvalidFormatOrCSVOptions(
+                    ErrorResponse::new(format!("{e:#}")),
+                ));
+            }
+        };
+        let csv = match to_csv_options(query_params) {
+            Ok(csv) => csv,
+            Err(e) => {
+                return Ok(GetBondCsvResponse::Status400_InvalidFormatOrCSVOptions(
+                    ErrorResponse::new(format!("{e:#}")),
+                ));
+            }
+        };
+        let not_found = || {
+            GetBondCsvResponse::Status404_BondNotFound(ErrorResponse::new(format!(
+                "Bond with ID {} not found",
+                path_params.id.clone()
+            )))
+        };
+        let Ok(bond_id) = BondId::new(path_params.id.clone()) else {
+            return Ok(not_found());
+        };
 
-        match self.bonds_service.get_bond(&bond_id) {
-            Some(bond) => {
-                let csv_data = bond.to_csv();
-                Ok(GetBondCsvResponse::Status200_BondDataInCSVFormat(csv_data))
+        // Answered before the bond is read, its values being the costliest to render
+        let version = match self.version_as_of(query_params.as_of).await {
+            Ok(version) => version,
+            Err(e) if e.is::<NoVersionAsOf>() => {
+                return Ok(GetBondCsvResponse::Status404_BondNotFound(
+                    ErrorResponse::new(e.to_string()),
+                ));
             }
-            None => Ok(GetBondCsvResponse::Status404_BondNotFound(
-                GetBond404Response::new(format!(
-                    "Bond with ID {} not found",
-                    path_params.id.clone()
-                )),
-            )),
+            Err(e) => return Err(e),
+        };
+        let resource = series_resource(&bond_id, format, &csv);
+        let e_tag = caching::etag(&resource, &version);
+        if caching::is_not_modified(
+            header_params.if_none_match.as_deref(),
+            header_params.if_modified_since,
+            &e_tag,
+            version.ingested_at,
+        ) {
+            return Ok(GetBondCsvResponse::Status304_NotModified {
+                e_tag,
+                cache_control: caching::CACHE_CONTROL.to_string(),
+            });
         }
+
+        let Some((bond, version)) = self
+            .bonds_service
+            .get_bond_with_version(&bond_id, query_params.as_of)
+            .await?
+        else {
+            return Ok(not_found());
+        };
+        Ok(
+            GetBondCsvResponse::Status200_BondValuesInTheRequestedFormat {
+                body: stream_body(move |writer| {
+                    bonds_export::export_to(&bond, format, &csv, writer)
+                }),
+                content_type: format.content_type().to_string(),
+                // Another version may have been published since the check
+                e_tag: caching::etag(&resource, &version),
+                last_modified: version.ingested_at,
+                cache_control: caching::CACHE_CONTROL.to_string(),
+                vary: ACCEPT.to_string(),
+            },
+        )
     }
 
     #[tracing::instrument(err(Debug), ret, skip(self, method, host, cookies), name = "get_bonds")]
@@ -76,17 +363,608 @@ impl openapi::apis::default::Default<Error> for ServerImpl {
         method: &Method,
         host: &Host,
         cookies: &CookieJar,
+        query_params: &GetBondsQueryParams,
     ) -> Result<GetBondsResponse, Error> {
-        Ok(Status200_AJSONArrayOfBondNames(
-            self.bonds_service
-                .get_bonds()
+        let query = match to_bonds_query(query_params) {
+            Ok(query) => query,
+            Err(e) => {
+                return Ok(Status400_InvalidQueryParameters(ErrorResponse::new(
+                    format!("{e:#}"),
+                )));
+            }
+        };
+        let pagination = Pagination {
+            page: query_params.page.unwrap_or(1) as usize,
+            per_page: query_params.per_page.map(usize::from),
+        };
+
+        let page = match self
+            .bonds_service
+            .get_bonds(&query, &pagination, query_params.as_of)
+            .await
+        {
+            Ok(page) => page,
+            Err(e) if e.is::<NoVersionAsOf>() => {
+                return Ok(Status404_NoDatasetVersionPublishedAsOfTheGivenDate(
+                    ErrorResponse::new(e.to_string()),
+                ));
+            }
+            Err(e) => return Err(e),
+        };
+
+        Ok(Status200_AJSONArrayOfBondNames {
+            body: page
+                .items
                 .into_iter()
                 .map(|bond_id| bond_id.value())
                 .collect(),
+            x_total_count: i32::try_from(page.total)?,
+        })
+    }
+
+    #[tracing::instrument(err(Debug), skip_all, name = "list_versions")]
+    async fn list_versions(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+    ) -> Result<ListVersionsResponse, Error> {
+        Ok(ListVersionsResponse::Status200_DatasetVersions(
+            self.bonds_service
+                .versions()
+                .await?
+                .into_iter()
+                .map(|version| DatasetVersion::new(version.hash, version.ingested_at))
+                .collect(),
         ))
     }
 }
 
+#[async_trait]
+impl ApiKeyAuthHeader for ServerImpl {
+    type Claims = Claims;
+
+    async fn extract_claims_from_header(
+        &self,
+        headers: &HeaderMap,
+        key: &str,
+    ) -> Option<Self::Claims> {
+        // API keys are checked, and counted against their quota, in front of every operation
+        if key.eq_ignore_ascii_case(API_KEY_HEADER) {
+            return Some(Claims::Client);
+        }
+        let expected = self.admin_api_key.as_deref()?;
+        let provided = headers.get(key)?.as_bytes();
+        constant_time_eq(provided, expected.as_bytes()).then_some(Claims::Admin)
+    }
+}
+
+#[async_trait]
+impl ApiAuthBasic for ServerImpl {
+    type Claims = Claims;
+
+    async fn extract_claims_from_auth_header(
+        &self,
+        kind: BasicAuthKind,
+        headers: &HeaderMap,
+        key: &str,
+    ) -> Option<Self::Claims> {
+        let value = headers.get(key)?.to_str().ok()?;
+        let token = match kind {
+            BasicAuthKind::Bearer => value.strip_prefix("Bearer ")?,
+            BasicAuthKind::Basic => return None,
+        };
+        self.accounts.authenticate(token).map(Claims::User)
+    }
+}
+
+#[allow(unused_variables)]
+#[async_trait]
+impl Admin<Error> for ServerImpl {
+    type Claims = Claims;
+
+    #[tracing::instrument(err(Debug), skip_all, name = "upload_workbook")]
+    async fn upload_workbook(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+        mut body: Multipart,
+    ) -> Result<UploadWorkbookResponse, Error> {
+        let workbook = match read_file_field(&mut body).await {
+            Ok(workbook) => workbook,
+            Err(e)
+                if e.downcast_ref::<MultipartError>()
+                    .is_some_and(|e| e.status() == StatusCode::PAYLOAD_TOO_LARGE) =>
+            {
+                return Ok(UploadWorkbookResponse::Status413_UploadLargerThanTheLimit(
+                    ErrorResponse::new(format!("{e:#}")),
+                ));
+            }
+            Err(e) => {
+                return Ok(UploadWorkbookResponse::Status400_MissingOrUnreadableUpload(
+                    ErrorResponse::new(format!("{e:#}")),
+                ));
+            }
+        };
+
+        let upload = self.bonds_service.replace_workbook(workbook).await?;
+
+        Ok(match upload {
+            WorkbookUpload::Activated(statistics) => {
+                UploadWorkbookResponse::Status200_WorkbookActivated(WorkbookReport::new(
+                    true,
+                    statistics
+                        .into_iter()
+                        .map(to_sheet_statistics)
+                        .collect::<anyhow::Result<_>>()?,
+                    Vec::new(),
+                ))
+            }
+            WorkbookUpload::Rejected(problems) => {
+                UploadWorkbookResponse::Status422_WorkbookRejected(WorkbookReport::new(
+                    false,
+                    Vec::new(),
+                    problems.into_iter().map(to_workbook_diagnostic).collect(),
+                ))
+            }
+        })
+    }
+}
+
+#[allow(unused_variables)]
+#[async_trait]
+impl Auth<Error> for ServerImpl {
+    type Claims = Claims;
+
+    #[tracing::instrument(err(Debug), skip_all, name = "current_user")]
+    async fn current_user(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+    ) -> Result<CurrentUserResponse, Error> {
+        let Claims::User(pid) = claims else {
+            return Ok(CurrentUserResponse::Status401_MissingOrInvalidToken);
+        };
+
+        Ok(match self.accounts.store().find_user(pid).await? {
+            Some(user) => CurrentUserResponse::Status200_TheLoggedInUser(CurrentUser::new(
+                user.pid, user.email, user.name,
+            )),
+            None => CurrentUserResponse::Status401_MissingOrInvalidToken,
+        })
+    }
+
+    #[tracing::instrument(err(Debug), skip_all, name = "login")]
+    async fn login(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        body: &LoginParams,
+    ) -> Result<LoginResponse, Error> {
+        Ok(
+            match self.accounts.login(&body.email, &body.password).await {
+                Ok(Some((user, token))) => {
+                    LoginResponse::Status200_LoggedIn(LoginToken::new(token, user.pid, user.name))
+                }
+                Ok(None) => LoginResponse::Status401_InvalidEmailOrPassword(ErrorResponse::new(
+                    "Invalid email or password".to_string(),
+                )),
+                Err(e) if e.is::<LoginUnavailable>() => {
+                    LoginResponse::Status503_LoggingInIsDisabled(ErrorResponse::new(e.to_string()))
+                }
+                Err(e) => return Err(e),
+            },
+        )
+    }
+
+    #[tracing::instrument(err(Debug), skip_all, name = "register")]
+    async fn register(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        body: &RegisterParams,
+    ) -> Result<RegisterResponse, Error> {
+        let registration = self
+            .accounts
+            .register(&body.email, &body.password, &body.name)
+            .await?;
+
+        Ok(match registration {
+            Registration::Created(user) => RegisterResponse::Status200_AccountCreated(
+                CurrentUser::new(user.pid, user.email, user.name),
+            ),
+            Registration::Invalid(reason) => {
+                RegisterResponse::Status400_InvalidRegistration(ErrorResponse::new(reason))
+            }
+            Registration::EmailTaken => RegisterResponse::Status409_EmailAlreadyRegistered(
+                ErrorResponse::new(format!("Email [{}] is already registered", body.email)),
+            ),
+        })
+    }
+}
+
+#[allow(unused_variables)]
+#[async_trait]
+impl Portfolios<Error> for ServerImpl {
+    type Claims = Claims;
+
+    #[tracing::instrument(
+        err(Debug),
+        skip(self, method, host, cookies, claims),
+        name = "create_portfolio"
+    )]
+    async fn create_portfolio(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+        body: &PortfolioParams,
+    ) -> Result<CreatePortfolioResponse, Error> {
+        let Claims::User(pid) = claims else {
+            return Ok(CreatePortfolioResponse::Status401_MissingOrInvalidToken);
+        };
+        let lots = match self.lots(&body.lots).await {
+            Ok(lots) => lots,
+            Err(e) if e.is::<InvalidLot>() => {
+                return Ok(CreatePortfolioResponse::Status400_InvalidPortfolio(
+                    ErrorResponse::new(e.to_string()),
+                ));
+            }
+            Err(e) => return Err(e),
+        };
+
+        let portfolio = self
+            .accounts
+            .store()
+            .insert_portfolio(pid, &body.name, &lots)
+            .await?;
+        Ok(CreatePortfolioResponse::Status201_PortfolioCreated(
+            to_portfolio(portfolio),
+        ))
+    }
+
+    #[tracing::instrument(
+        err(Debug),
+        skip(self, method, host, cookies, claims),
+        name = "delete_portfolio"
+    )]
+    async fn delete_portfolio(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+        path_params: &DeletePortfolioPathParams,
+    ) -> Result<DeletePortfolioResponse, Error> {
+        let Claims::User(pid) = claims else {
+            return Ok(DeletePortfolioResponse::Status401_MissingOrInvalidToken);
+        };
+
+        Ok(
+            if self
+                .accounts
+                .store()
+                .delete_portfolio(pid, path_params.id)
+                .await?
+            {
+                DeletePortfolioResponse::Status204_PortfolioDeleted
+            } else {
+                DeletePortfolioResponse::Status404_PortfolioNotFound(portfolio_not_found(
+                    path_params.id,
+                ))
+            },
+        )
+    }
+
+    #[tracing::instrument(
+        err(Debug),
+        skip(self, method, host, cookies, claims),
+        name = "get_portfolio"
+    )]
+    async fn get_portfolio(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+        path_params: &GetPortfolioPathParams,
+    ) -> Result<GetPortfolioResponse, Error> {
+        let Claims::User(pid) = claims else {
+            return Ok(GetPortfolioResponse::Status401_MissingOrInvalidToken);
+        };
+
+        Ok(
+            match self.accounts.store().portfolio(pid, path_params.id).await? {
+                Some(portfolio) => {
+                    GetPortfolioResponse::Status200_ASinglePortfolio(to_portfolio(portfolio))
+                }
+                None => GetPortfolioResponse::Status404_PortfolioNotFound(portfolio_not_found(
+                    path_params.id,
+                )),
+            },
+        )
+    }
+
+    #[tracing::instrument(err(Debug), skip_all, name = "list_portfolios")]
+    async fn list_portfolios(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+    ) -> Result<ListPortfoliosResponse, Error> {
+        let Claims::User(pid) = claims else {
+            return Ok(ListPortfoliosResponse::Status401_MissingOrInvalidToken);
+        };
+
+        Ok(ListPortfoliosResponse::Status200_PortfoliosOfTheUser(
+            self.accounts
+                .store()
+                .portfolios(pid)
+                .await?
+                .into_iter()
+                .map(to_portfolio)
+                .collect(),
+        ))
+    }
+
+    #[tracing::instrument(
+        err(Debug),
+        skip(self, method, host, cookies, claims),
+        name = "update_portfolio"
+    )]
+    async fn update_portfolio(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+        path_params: &UpdatePortfolioPathParams,
+        body: &PortfolioParams,
+    ) -> Result<UpdatePortfolioResponse, Error> {
+        let Claims::User(pid) = claims else {
+            return Ok(UpdatePortfolioResponse::Status401_MissingOrInvalidToken);
+        };
+        let lots = match self.lots(&body.lots).await {
+            Ok(lots) => lots,
+            Err(e) if e.is::<InvalidLot>() => {
+                return Ok(UpdatePortfolioResponse::Status400_InvalidPortfolio(
+                    ErrorResponse::new(e.to_string()),
+                ));
+            }
+            Err(e) => return Err(e),
+        };
+
+        let portfolio = Portfolio {
+            id: path_params.id,
+            name: body.name.clone(),
+            lots,
+        };
+        Ok(
+            if self
+                .accounts
+                .store()
+                .update_portfolio(pid, &portfolio)
+                .await?
+            {
+                UpdatePortfolioResponse::Status200_PortfolioUpdated(to_portfolio(portfolio))
+            } else {
+                UpdatePortfolioResponse::Status404_PortfolioNotFound(portfolio_not_found(
+                    path_params.id,
+                ))
+            },
+        )
+    }
+
+    #[tracing::instrument(
+        err(Debug),
+        skip(self, method, host, cookies, claims),
+        name = "value_portfolio"
+    )]
+    async fn value_portfolio(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        claims: &Self::Claims,
+        path_params: &ValuePortfolioPathParams,
+        query_params: &ValuePortfolioQueryParams,
+    ) -> Result<ValuePortfolioResponse, Error> {
+        let Claims::User(pid) = claims else {
+            return Ok(ValuePortfolioResponse::Status401_MissingOrInvalidToken);
+        };
+        let Some(portfolio) = self.accounts.store().portfolio(pid, path_params.id).await? else {
+            return Ok(ValuePortfolioResponse::Status404_PortfolioNotFound(
+                portfolio_not_found(path_params.id),
+            ));
+        };
+
+        let date = query_params
+            .date
+            .unwrap_or_else(|| chrono::Utc::now().date_naive());
+        let valuation = value_portfolio(self.bonds_service.as_ref(), &portfolio, date).await?;
+        Ok(ValuePortfolioResponse::Status200_ValueOfThePortfolio(
+            to_portfolio_valuation(valuation),
+        ))
+    }
+}
+
+fn portfolio_not_found(id: i64) -> ErrorResponse {
+    ErrorResponse::new(format!("Portfolio with ID {id} not found"))
+}
+
+fn to_lot(lot: Lot) -> models::Lot {
+    models::Lot::new(lot.bond_id.value(), lot.quantity, lot.purchase_date)
+}
+
+fn to_portfolio(portfolio: Portfolio) -> models::Portfolio {
+    models::Portfolio::new(
+        portfolio.id,
+        portfolio.name,
+        portfolio.lots.into_iter().map(to_lot).collect(),
+    )
+}
+
+fn to_portfolio_valuation(valuation: PortfolioValuation) -> models::PortfolioValuation {
+    models::PortfolioValuation::new(
+        valuation.date,
+        valuation.total,
+        valuation
+            .lots
+            .into_iter()
+            .map(|(lot, value)| LotValuation {
+                value,
+                ..LotValuation::new(lot.bond_id.value(), lot.quantity, lot.purchase_date)
+            })
+            .collect(),
+    )
+}
+
+async fn read_file_field(body: &mut Multipart) -> anyhow::Result<Vec<u8>> {
+    while let Some(field) = body.next_field().await? {
+        if field.name() == Some("file") {
+            return Ok(field.bytes().await?.to_vec());
+        }
+    }
+    anyhow::bail!("Missing multipart field [file]")
+}
+
+fn to_sheet_statistics(
+    statistics: bonds_reader::SheetStatistics,
+) -> anyhow::Result<SheetStatistics> {
+    Ok(SheetStatistics {
+        sheet: statistics.bond_type.to_string(),
+        bonds: i32::try_from(statistics.bonds)?,
+        first_sale: statistics.first_sale,
+        last_sale: statistics.last_sale,
+    })
+}
+
+fn to_workbook_diagnostic(problem: WorkbookProblem) -> WorkbookDiagnostic {
+    match problem {
+        WorkbookProblem::Diagnostic(diagnostic) => WorkbookDiagnostic {
+            sheet: Some(diagnostic.sheet),
+            cell: diagnostic.cell,
+            severity: diagnostic.severity.to_string(),
+            message: diagnostic.message,
+        },
+        WorkbookProblem::Unreadable(message) => {
+            WorkbookDiagnostic::new(Severity::Error.to_string(), message)
+        }
+    }
+}
+
+fn to_bonds_query(query_params: &GetBondsQueryParams) -> anyhow::Result<BondsQuery> {
+    Ok(BondsQuery {
+        bond_type: query_params
+            .r#type
+            .as_deref()
+            .map(str::parse::<BondType>)
+            .transpose()?,
+        on_sale: query_params.on_sale,
+        sale_month: query_params
+            .sale_month
+            .as_deref()
+            .map(SaleMonth::parse)
+            .transpose()?,
+        maturity_from: query_params.maturity_from,
+        maturity_to: query_params.maturity_to,
+    })
+}
+
+/// Format named by the `format` parameter, or else the one negotiated with the `Accept` header.
+/// `None` when the client accepts none of them.
+fn series_format(
+    header_params: &GetBondCsvHeaderParams,
+    query_params: &GetBondCsvQueryParams,
+) -> anyhow::Result<Option<Format>> {
+    if let Some(format) = &query_params.format {
+        return format.parse().map(Some);
+    }
+    match header_params.accept.as_deref() {
+        Some(accept) if !accept.trim().is_empty() => Ok(Format::negotiate(accept)),
+        _ => Ok(Some(Format::Csv)),
+    }
+}
+
+/// Layout and format of a bulk export, long CSV by default
+fn export_layout(query_params: &ExportBondsQueryParams) -> anyhow::Result<(Layout, Format)> {
+    let layout = match &query_params.layout {
+        Some(layout) => layout.parse()?,
+        None => Layout::Long,
+    };
+    let format = match &query_params.format {
+        Some(format) => format.parse()?,
+        None => Format::Csv,
+    };
+    layout.validate(format)?;
+    Ok((layout, format))
+}
+
+fn to_csv_options(query_params: &GetBondCsvQueryParams) -> anyhow::Result<CsvOptions> {
+    let defaults = CsvOptions::default();
+    let options = CsvOptions {
+        delimiter: query_params
+            .delimiter
+            .as_deref()
+            .map(|delimiter| single_char("Delimiter", delimiter))
+            .transpose()?
+            .unwrap_or(defaults.delimiter),
+        decimal_separator: query_params
+            .decimal_separator
+            .as_deref()
+            .map(|separator| single_char("Decimal separator", separator))
+            .transpose()?
+            .unwrap_or(defaults.decimal_separator),
+        date_format: query_params
+            .date_format
+            .clone()
+            .unwrap_or(defaults.date_format),
+        columns: match &query_params.columns {
+            Some(columns) => columns
+                .split(',')
+                .map(|column| column.trim().parse::<Column>())
+                .collect::<anyhow::Result<_>>()?,
+            None => defaults.columns,
+        },
+        header: query_params
+            .header
+            .as_deref()
+            .map_or(defaults.header, |header| {
+                header.split(',').map(str::to_string).collect()
+            }),
+    };
+    options.validate()?;
+    Ok(options)
+}
+
+fn single_char(name: &str, value: &str) -> anyhow::Result<char> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(character), None) => Ok(character),
+        _ => anyhow::bail!("{name} has to be a single character, not {value}"),
+    }
+}
+
+/// Every format of the values of a bond, and every CSV dialect, is validated by an `ETag` of its own
+fn series_resource(id: &BondId, format: Format, csv: &CsvOptions) -> String {
+    let resource = format!("{}.{format}", id.as_str());
+    if format != Format::Csv || *csv == CsvOptions::default() {
+        return resource;
+    }
+    let mut hasher = DefaultHasher::new();
+    csv.hash(&mut hasher);
+    format!("{resource}-{:016x}", hasher.finish())
+}
+
 impl ErrorHandler for ServerImpl {}
 
 pub(crate) fn get_routes(ctx: &AppContext) -> loco_rs::Result<Routes> {
@@ -97,20 +975,41 @@ pub(crate) fn get_routes(ctx: &AppContext) -> loco_rs::Result<Routes> {
         .context("Setting key in settings not found")
         .map_err(|e| loco_rs::Error::from(e.into_boxed_dyn_error()))?;
 
-    let settings = settings
-        .get("bonds_location")
-        .context("Setting->bonds_location setting not found")
+    let settings = Settings::from_json(settings)
+        .context("Failed to parse settings")
         .map_err(|e| loco_rs::Error::from(e.into_boxed_dyn_error()))?;
 
-    let settings = settings
-        .as_str()
-        .context("Setting->bonds_location is not a string")
+    let bonds_service = create_bonds_service(&settings)
+        .context("Failed to create BondsService")
         .map_err(|e| loco_rs::Error::from(e.into_boxed_dyn_error()))?;
 
-    let bonds_service = BondsServiceImpl::new(settings)
-        .context("Failed to create BondsService")
+    let account_store = create_account_store(&settings)
+        .context("Failed to create AccountStore")
         .map_err(|e| loco_rs::Error::from(e.into_boxed_dyn_error()))?;
+    let jwt = ctx.config.get_jwt_config()?;
+    let accounts = Accounts::new(account_store, &jwt.secret, jwt.expiration);
 
-    let app = openapi::server::new(ctx, ServerImpl::new(bonds_service));
-    Ok(app)
+    let payloads = Arc::new(Payloads::default());
+    let app = openapi::server::new(
+        ctx,
+        ServerImpl::new(
+            bonds_service,
+            accounts,
+            settings.admin_api_key.clone(),
+            payloads.clone(),
+        ),
+    )
+    .layer(axum::middleware::from_fn_with_state(
+        payloads,
+        compress_response,
+    ));
+
+    let api_keys = ApiKeys::new(settings.api_keys());
+    if api_keys.is_empty() {
+        return Ok(app);
+    }
+    Ok(app.layer(axum::middleware::from_fn_with_state(
+        Arc::new(api_keys),
+        check_api_key,
+    )))
 }
@@ -0,0 +1,69 @@
+use axum::body::{Body, Bytes};
+use std::io::{Error, ErrorKind, Result, Write};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+/// Bytes written before they are sent as a chunk of the body
+const CHUNK_SIZE: usize = 64 * 1024;
+/// Chunks the writer can be ahead of the client before it has to wait
+const CHUNKS_AHEAD: usize = 4;
+
+/// Body written by `write` on a blocking thread while it is being sent, so that at most a few
+/// chunks of it are held in memory.
+///
+/// The status and headers are sent before the body is written, so a failure can only cut the body
+/// short. Writing stops as soon as the client goes away.
+pub(crate) fn stream_body<F>(write: F) -> Body
+where
+    F: FnOnce(&mut ChunkWriter) -> anyhow::Result<()> + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel(CHUNKS_AHEAD);
+    let span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || {
+        let _span = span.enter();
+        let mut writer = ChunkWriter {
+            sender,
+            buffer: Vec::with_capacity(CHUNK_SIZE),
+        };
+        let written = write(&mut writer).and_then(|()| Ok(writer.flush()?));
+        if let Err(e) = written
+            && !writer.sender.is_closed()
+        {
+            tracing::error!(error = ?e, "Failed to write response body");
+            let _ = writer.sender.blocking_send(Err(Error::other(e)));
+        }
+    });
+    Body::from_stream(ReceiverStream::new(receiver))
+}
+
+pub(crate) struct ChunkWriter {
+    sender: mpsc::Sender<Result<Bytes>>,
+    buffer: Vec<u8>,
+}
+
+impl ChunkWriter {
+    fn send(&mut self) -> Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let chunk = std::mem::replace(&mut self.buffer, Vec::with_capacity(CHUNK_SIZE));
+        self.sender
+            .blocking_send(Ok(Bytes::from(chunk)))
+            .map_err(|_| Error::new(ErrorKind::BrokenPipe, "Client closed the connection"))
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if self.buffer.len() >= CHUNK_SIZE {
+            self.send()?;
+        }
+        Ok(buf.len())
+    }
+
+    /// Sends what was written so far, even when it is less than a chunk
+    fn flush(&mut self) -> Result<()> {
+        self.send()
+    }
+}
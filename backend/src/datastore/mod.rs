@@ -1 +1,2 @@
-
+#[cfg(feature = "sqlite")]
+pub(crate) mod sqlite;
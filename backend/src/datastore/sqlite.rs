@@ -0,0 +1,671 @@
+use crate::common::settings::{Settings, SourceSettings};
+use crate::services::accounts::{AccountStore, User};
+use crate::services::bonds::{
+    BondsService, NoVersionAsOf, ParsedUpload, SharedBond, WorkbookUpload, merge_sources,
+    parse_upload, persist_upload, read_workbook,
+};
+use crate::services::catalogue::{BondsQuery, Page, Pagination};
+use crate::services::portfolios::{Lot, Portfolio};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use bonds_reader::{BondDefinition, DEFAULT_NOMINAL, DatasetVersion, MergedBonds, Provenance};
+use chrono::{NaiveDate, Utc};
+use model::{AllBonds, BondId};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions, SqliteRow};
+use sqlx::{Row, SqliteConnection};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Every ingested version keeps its own copy of the bonds, so that `as_of` requests are plain queries.
+///
+/// Daily values are not stored, they are calculated from the rates like for the workbook.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS dataset_versions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- SHA-256 of the bonds merged from the workbook and the sources
+    hash TEXT NOT NULL,
+    ingested_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS bonds (
+    version INTEGER NOT NULL REFERENCES dataset_versions (id),
+    id TEXT NOT NULL,
+    bond_type TEXT NOT NULL,
+    initial_date TEXT NOT NULL,
+    sale_end TEXT NOT NULL,
+    buyout_date TEXT NOT NULL,
+    nominal REAL NOT NULL,
+    -- Sources that supplied the fields, see `Provenance`
+    series_source TEXT NOT NULL,
+    initial_date_source TEXT NOT NULL,
+    sale_end_source TEXT NOT NULL,
+    nominal_source TEXT NOT NULL,
+    rates_source TEXT NOT NULL,
+    PRIMARY KEY (version, id)
+);
+
+CREATE TABLE IF NOT EXISTS rates (
+    version INTEGER NOT NULL,
+    bond_id TEXT NOT NULL,
+    -- 1-based year of the term
+    year INTEGER NOT NULL,
+    rate REAL NOT NULL,
+    PRIMARY KEY (version, bond_id, year),
+    FOREIGN KEY (version, bond_id) REFERENCES bonds (version, id)
+);
+";
+
+/// Lots refer to the bonds by ID only, as the bonds are stored per dataset version
+const ACCOUNTS_SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS users (
+    pid TEXT PRIMARY KEY,
+    email TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    password_hash TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS portfolios (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_pid TEXT NOT NULL REFERENCES users (pid),
+    name TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS lots (
+    portfolio_id INTEGER NOT NULL REFERENCES portfolios (id) ON DELETE CASCADE,
+    -- 0-based position of the lot in the portfolio
+    position INTEGER NOT NULL,
+    bond_id TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    purchase_date TEXT NOT NULL,
+    PRIMARY KEY (portfolio_id, position)
+);
+";
+
+const BOND_COLUMNS: &str = "id, initial_date, sale_end, buyout_date, nominal, \
+    series_source, initial_date_source, sale_end_source, nominal_source, rates_source";
+
+/// Serves the bonds from SQLite, the latest ingested version being the current one
+pub(crate) struct SqliteBondsService {
+    pool: SqlitePool,
+    bonds_location: String,
+    sources: Vec<SourceSettings>,
+}
+
+/// A bond as it is stored
+#[derive(Clone, Debug, PartialEq)]
+struct StoredBond {
+    definition: BondDefinition,
+    buyout_date: NaiveDate,
+    provenance: Provenance,
+}
+
+impl SqliteBondsService {
+    /// Serves the database prepared by [`prepare_database`], which the app runs before serving
+    pub(crate) fn open(database_url: &str, settings: &Settings) -> Result<Self> {
+        let options = connect_options(database_url)?;
+        let bonds_location = settings
+            .bonds_location
+            .clone()
+            .context("Missing bonds_location, required by the SQLite store")?;
+
+        Ok(Self {
+            pool: SqlitePoolOptions::new().connect_lazy_with(options),
+            bonds_location,
+            sources: settings.sources.clone(),
+        })
+    }
+
+    /// Re-reads the workbook and the sources and ingests them as a new version when they changed,
+    /// the current version is kept when any of them fails to parse
+    #[tracing::instrument(err(Debug), skip(self), fields(bonds_location = %self.bonds_location))]
+    pub(crate) async fn reload(&self) -> Result<usize> {
+        let merged = read_merged(self.bonds_location.clone(), self.sources.clone()).await?;
+        ingest(&self.pool, &merged).await?;
+
+        tracing::info!(count = merged.all_bonds.len(), "Reloaded bonds");
+        Ok(merged.all_bonds.len())
+    }
+}
+
+fn connect_options(database_url: &str) -> Result<SqliteConnectOptions> {
+    Ok(SqliteConnectOptions::from_str(database_url)
+        .with_context(|| format!("Invalid database_url [{database_url}]"))?
+        .create_if_missing(true)
+        .foreign_keys(true))
+}
+
+/// Creates the schema and ingests the configured workbook, unless it is already being served
+pub(crate) async fn prepare_database(database_url: &str, settings: &Settings) -> Result<()> {
+    let bonds_location = settings
+        .bonds_location
+        .clone()
+        .context("Missing bonds_location, required by the SQLite store")?;
+    let merged = read_merged(bonds_location, settings.sources.clone()).await?;
+
+    let pool = SqlitePool::connect_with(connect_options(database_url)?)
+        .await
+        .context("Failed to open database")?;
+    for schema in [SCHEMA, ACCOUNTS_SCHEMA] {
+        sqlx::raw_sql(schema)
+            .execute(&pool)
+            .await
+            .context("Failed to create database schema")?;
+    }
+    ingest(&pool, &merged).await?;
+    pool.close().await;
+    Ok(())
+}
+
+/// Reads the workbook at `bonds_location` and merges the sources on top of it
+async fn read_merged(bonds_location: String, sources: Vec<SourceSettings>) -> Result<MergedBonds> {
+    tokio::task::spawn_blocking(move || {
+        let workbook = std::fs::read(&bonds_location)
+            .with_context(|| format!("Failed to read workbook: {bonds_location}"))?;
+        merge_sources(&sources, &read_workbook(&workbook)?)
+    })
+    .await?
+}
+
+/// Stores the bonds as a new version, unless the latest one has the same bonds and provenance
+async fn ingest(pool: &SqlitePool, merged: &MergedBonds) -> Result<()> {
+    let hash = bonds_reader::bonds_hash(&merged.all_bonds);
+    let bonds = stored_bonds(merged);
+
+    let mut tx = pool.begin().await?;
+    if let Some((version, latest_hash)) = latest_version(&mut tx).await?
+        && latest_hash == hash
+        && load_bonds(&mut tx, version).await? == bonds
+    {
+        tracing::info!(hash, version, "Serving dataset version");
+        return Ok(());
+    }
+
+    let version = sqlx::query("INSERT INTO dataset_versions (hash, ingested_at) VALUES (?, ?)")
+        .bind(&hash)
+        .bind(Utc::now())
+        .execute(&mut *tx)
+        .await?
+        .last_insert_rowid();
+    for bond in &bonds {
+        let definition = &bond.definition;
+        sqlx::query(
+            "INSERT INTO bonds (version, bond_type, id, initial_date, sale_end, buyout_date, \
+             nominal, series_source, initial_date_source, sale_end_source, nominal_source, \
+             rates_source) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(version)
+        .bind(definition.id.bond_type().code())
+        .bind(definition.id.as_str())
+        .bind(definition.initial_date)
+        .bind(definition.sale_end)
+        .bind(bond.buyout_date)
+        .bind(definition.nominal)
+        .bind(&bond.provenance.series)
+        .bind(&bond.provenance.initial_date)
+        .bind(&bond.provenance.sale_end)
+        .bind(&bond.provenance.nominal)
+        .bind(&bond.provenance.rates)
+        .execute(&mut *tx)
+        .await?;
+        for (year, rate) in (1..).zip(&definition.rates) {
+            sqlx::query("INSERT INTO rates (version, bond_id, year, rate) VALUES (?, ?, ?, ?)")
+                .bind(version)
+                .bind(definition.id.as_str())
+                .bind(year)
+                .bind(rate)
+                .execute(&mut *tx)
+                .await?;
+        }
+    }
+    tx.commit()
+        .await
+        .context("Failed to store dataset version")?;
+
+    tracing::info!(
+        hash,
+        version,
+        count = bonds.len(),
+        "Serving dataset version"
+    );
+    Ok(())
+}
+
+fn stored_bonds(merged: &MergedBonds) -> Vec<StoredBond> {
+    let mut bonds: Vec<_> = merged
+        .all_bonds
+        .iter()
+        .map(|bond| StoredBond {
+            definition: BondDefinition {
+                id: bond.id.clone(),
+                bond_type: None,
+                initial_date: bond.initial_date,
+                sale_end: bond.sale_end,
+                nominal: bond.values.first().copied().unwrap_or(DEFAULT_NOMINAL),
+                rates: bond.rates.clone(),
+            },
+            buyout_date: bond.buyout_date,
+            provenance: merged.provenance[&bond.id].clone(),
+        })
+        .collect();
+    bonds.sort_by(|a, b| a.definition.id.cmp(&b.definition.id));
+    bonds
+}
+
+/// Version served on the given day, the latest one without a day
+async fn serving_version(conn: &mut SqliteConnection, as_of: Option<NaiveDate>) -> Result<i64> {
+    let version = match as_of {
+        Some(as_of) => sqlx::query_scalar(
+            "SELECT id FROM dataset_versions WHERE substr(ingested_at, 1, 10) <= ? \
+             ORDER BY id DESC LIMIT 1",
+        )
+        .bind(as_of)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or(NoVersionAsOf(as_of))?,
+        None => latest_version(conn)
+            .await?
+            .map(|(version, _)| version)
+            .context("No dataset version ingested")?,
+    };
+    Ok(version)
+}
+
+async fn load_version(conn: &mut SqliteConnection, version: i64) -> Result<DatasetVersion> {
+    let row = sqlx::query("SELECT hash, ingested_at FROM dataset_versions WHERE id = ?")
+        .bind(version)
+        .fetch_one(conn)
+        .await?;
+    dataset_version(&row)
+}
+
+async fn latest_version(conn: &mut SqliteConnection) -> Result<Option<(i64, String)>> {
+    Ok(
+        sqlx::query_as("SELECT id, hash FROM dataset_versions ORDER BY id DESC LIMIT 1")
+            .fetch_optional(conn)
+            .await?,
+    )
+}
+
+/// Every bond of a version, sorted by ID
+async fn load_bonds(conn: &mut SqliteConnection, version: i64) -> Result<Vec<StoredBond>> {
+    let rows = sqlx::query("SELECT bond_id, rate FROM rates WHERE version = ? ORDER BY year")
+        .bind(version)
+        .fetch_all(&mut *conn)
+        .await?;
+    let mut rates: HashMap<String, Vec<f64>> = HashMap::new();
+    for row in rows {
+        rates
+            .entry(row.try_get("bond_id")?)
+            .or_default()
+            .push(row.try_get("rate")?);
+    }
+
+    sqlx::query(&format!(
+        "SELECT {BOND_COLUMNS} FROM bonds WHERE version = ? ORDER BY id"
+    ))
+    .bind(version)
+    .fetch_all(conn)
+    .await?
+    .iter()
+    .map(|row| {
+        let id: String = row.try_get("id")?;
+        let rates = rates.remove(&id).unwrap_or_default();
+        stored_bond(row, rates)
+    })
+    .collect()
+}
+
+async fn load_bond(
+    conn: &mut SqliteConnection,
+    version: i64,
+    id: &BondId,
+) -> Result<Option<StoredBond>> {
+    let Some(row) = sqlx::query(&format!(
+        "SELECT {BOND_COLUMNS} FROM bonds WHERE version = ? AND id = ?"
+    ))
+    .bind(version)
+    .bind(id.as_str())
+    .fetch_optional(&mut *conn)
+    .await?
+    else {
+        return Ok(None);
+    };
+    let rates = sqlx::query_scalar(
+        "SELECT rate FROM rates WHERE version = ? AND bond_id = ? ORDER BY year",
+    )
+    .bind(version)
+    .bind(id.as_str())
+    .fetch_all(conn)
+    .await?;
+    stored_bond(&row, rates).map(Some)
+}
+
+fn stored_bond(row: &SqliteRow, rates: Vec<f64>) -> Result<StoredBond> {
+    Ok(StoredBond {
+        definition: BondDefinition {
+            id: BondId::new(row.try_get::<String, _>("id")?)?,
+            bond_type: None,
+            initial_date: row.try_get("initial_date")?,
+            sale_end: row.try_get("sale_end")?,
+            nominal: row.try_get("nominal")?,
+            rates,
+        },
+        buyout_date: row.try_get("buyout_date")?,
+        provenance: Provenance {
+            series: row.try_get("series_source")?,
+            initial_date: row.try_get("initial_date_source")?,
+            sale_end: row.try_get("sale_end_source")?,
+            nominal: row.try_get("nominal_source")?,
+            rates: row.try_get("rates_source")?,
+        },
+    })
+}
+
+#[async_trait]
+impl BondsService for SqliteBondsService {
+    async fn get_bonds(
+        &self,
+        query: &BondsQuery,
+        pagination: &Pagination,
+        as_of: Option<NaiveDate>,
+    ) -> Result<Page<BondId>> {
+        let mut conn = self.pool.acquire().await?;
+        let version = serving_version(&mut conn, as_of).await?;
+
+        // Same filters as `BondsQuery::matches`, a missing one matches every bond
+        let ids: Vec<String> = sqlx::query_scalar(
+            "SELECT id FROM bonds WHERE version = ?1 \
+             AND (?2 IS NULL OR bond_type = ?2) \
+             AND (?3 IS NULL OR (initial_date <= ?3 AND ?3 <= sale_end)) \
+             AND (?4 IS NULL OR (initial_date <= ?5 AND ?4 <= sale_end)) \
+             AND (?6 IS NULL OR ?6 <= buyout_date) \
+             AND (?7 IS NULL OR buyout_date <= ?7) \
+             ORDER BY id",
+        )
+        .bind(version)
+        .bind(query.bond_type.map(|bond_type| bond_type.code()))
+        .bind(query.on_sale)
+        .bind(query.sale_month.as_ref().map(|month| month.first_day()))
+        .bind(query.sale_month.as_ref().map(|month| month.last_day()))
+        .bind(query.maturity_from)
+        .bind(query.maturity_to)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let ids = ids
+            .into_iter()
+            .map(BondId::new)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(pagination.apply(ids))
+    }
+
+    async fn get_bond(&self, id: &BondId, as_of: Option<NaiveDate>) -> Result<Option<SharedBond>> {
+        let mut conn = self.pool.acquire().await?;
+        let version = serving_version(&mut conn, as_of).await?;
+        let bond = load_bond(&mut conn, version, id).await?;
+        Ok(bond.map(|bond| bond.definition.to_bond().into()))
+    }
+
+    async fn get_bond_with_version(
+        &self,
+        id: &BondId,
+        as_of: Option<NaiveDate>,
+    ) -> Result<Option<(SharedBond, DatasetVersion)>> {
+        let mut conn = self.pool.acquire().await?;
+        let version = serving_version(&mut conn, as_of).await?;
+        let Some(bond) = load_bond(&mut conn, version, id).await? else {
+            return Ok(None);
+        };
+        let version = load_version(&mut conn, version).await?;
+        Ok(Some((bond.definition.to_bond().into(), version)))
+    }
+
+    async fn get_all_bonds(
+        &self,
+        as_of: Option<NaiveDate>,
+    ) -> Result<(Arc<AllBonds>, DatasetVersion)> {
+        let mut conn = self.pool.acquire().await?;
+        let version = serving_version(&mut conn, as_of).await?;
+        let bonds = load_bonds(&mut conn, version).await?;
+        let version = load_version(&mut conn, version).await?;
+        let all_bonds = bonds
+            .into_iter()
+            .map(|bond| bond.definition.to_bond())
+            .collect();
+        Ok((Arc::new(all_bonds), version))
+    }
+
+    async fn version_as_of(&self, as_of: Option<NaiveDate>) -> Result<DatasetVersion> {
+        let mut conn = self.pool.acquire().await?;
+        let version = serving_version(&mut conn, as_of).await?;
+        load_version(&mut conn, version).await
+    }
+
+    async fn get_bond_with_provenance(
+        &self,
+        id: &BondId,
+        as_of: Option<NaiveDate>,
+    ) -> Result<Option<(SharedBond, Provenance, DatasetVersion)>> {
+        let mut conn = self.pool.acquire().await?;
+        let version = serving_version(&mut conn, as_of).await?;
+        let Some(bond) = load_bond(&mut conn, version, id).await? else {
+            return Ok(None);
+        };
+        let version = load_version(&mut conn, version).await?;
+        Ok(Some((
+            bond.definition.to_bond().into(),
+            bond.provenance,
+            version,
+        )))
+    }
+
+    #[tracing::instrument(err(Debug), skip_all, fields(bonds_location = %self.bonds_location))]
+    async fn replace_workbook(&self, workbook: Vec<u8>) -> Result<WorkbookUpload> {
+        let parsed = match parse_upload(workbook, &self.sources).await? {
+            ParsedUpload::Valid(parsed) => parsed,
+            ParsedUpload::Rejected(problems) => return Ok(WorkbookUpload::Rejected(problems)),
+        };
+        let bonds_location = self.bonds_location.clone();
+        let workbook = parsed.workbook;
+        tokio::task::spawn_blocking(move || persist_upload(Some(&bonds_location), &workbook))
+            .await??;
+        ingest(&self.pool, &parsed.merged).await?;
+
+        tracing::info!(
+            count = parsed.merged.all_bonds.len(),
+            "Activated uploaded workbook"
+        );
+        Ok(WorkbookUpload::Activated(parsed.statistics))
+    }
+
+    async fn versions(&self) -> Result<Vec<DatasetVersion>> {
+        let rows = sqlx::query("SELECT hash, ingested_at FROM dataset_versions ORDER BY id")
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(dataset_version).collect()
+    }
+}
+
+fn dataset_version(row: &SqliteRow) -> Result<DatasetVersion> {
+    Ok(DatasetVersion {
+        hash: row.try_get("hash")?,
+        ingested_at: row.try_get("ingested_at")?,
+    })
+}
+
+/// Keeps the users and their portfolios in the same database as the bonds
+pub(crate) struct SqliteAccountStore {
+    pool: SqlitePool,
+}
+
+impl SqliteAccountStore {
+    /// Uses the schema created by [`prepare_database`]
+    pub(crate) fn open(database_url: &str) -> Result<Self> {
+        let options = connect_options(database_url)?;
+
+        Ok(Self {
+            pool: SqlitePoolOptions::new().connect_lazy_with(options),
+        })
+    }
+
+    async fn lots(&self, portfolio_id: i64) -> Result<Vec<Lot>> {
+        sqlx::query(
+            "SELECT bond_id, quantity, purchase_date FROM lots WHERE portfolio_id = ? \
+             ORDER BY position",
+        )
+        .bind(portfolio_id)
+        .fetch_all(&self.pool)
+        .await?
+        .iter()
+        .map(|row| {
+            Ok(Lot {
+                bond_id: BondId::new(row.try_get::<String, _>("bond_id")?)?,
+                quantity: row.try_get("quantity")?,
+                purchase_date: row.try_get("purchase_date")?,
+            })
+        })
+        .collect()
+    }
+}
+
+async fn insert_lots(conn: &mut SqliteConnection, portfolio_id: i64, lots: &[Lot]) -> Result<()> {
+    for (position, lot) in (0_i64..).zip(lots) {
+        sqlx::query(
+            "INSERT INTO lots (portfolio_id, position, bond_id, quantity, purchase_date) \
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(portfolio_id)
+        .bind(position)
+        .bind(lot.bond_id.as_str())
+        .bind(lot.quantity)
+        .bind(lot.purchase_date)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+fn user(row: &SqliteRow) -> Result<User> {
+    Ok(User {
+        pid: row.try_get("pid")?,
+        email: row.try_get("email")?,
+        name: row.try_get("name")?,
+        password_hash: row.try_get("password_hash")?,
+    })
+}
+
+#[async_trait]
+impl AccountStore for SqliteAccountStore {
+    async fn insert_user(&self, user: &User) -> Result<bool> {
+        let inserted = sqlx::query(
+            "INSERT INTO users (pid, email, name, password_hash) VALUES (?, ?, ?, ?) \
+             ON CONFLICT (email) DO NOTHING",
+        )
+        .bind(&user.pid)
+        .bind(&user.email)
+        .bind(&user.name)
+        .bind(&user.password_hash)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+        Ok(inserted == 1)
+    }
+
+    async fn find_user(&self, pid: &str) -> Result<Option<User>> {
+        sqlx::query("SELECT pid, email, name, password_hash FROM users WHERE pid = ?")
+            .bind(pid)
+            .fetch_optional(&self.pool)
+            .await?
+            .as_ref()
+            .map(user)
+            .transpose()
+    }
+
+    async fn find_user_by_email(&self, email: &str) -> Result<Option<User>> {
+        sqlx::query("SELECT pid, email, name, password_hash FROM users WHERE email = ?")
+            .bind(email)
+            .fetch_optional(&self.pool)
+            .await?
+            .as_ref()
+            .map(user)
+            .transpose()
+    }
+
+    async fn portfolios(&self, pid: &str) -> Result<Vec<Portfolio>> {
+        let rows: Vec<(i64, String)> =
+            sqlx::query_as("SELECT id, name FROM portfolios WHERE user_pid = ? ORDER BY id")
+                .bind(pid)
+                .fetch_all(&self.pool)
+                .await?;
+        let mut portfolios = Vec::with_capacity(rows.len());
+        for (id, name) in rows {
+            let lots = self.lots(id).await?;
+            portfolios.push(Portfolio { id, name, lots });
+        }
+        Ok(portfolios)
+    }
+
+    async fn portfolio(&self, pid: &str, id: i64) -> Result<Option<Portfolio>> {
+        let name: Option<String> =
+            sqlx::query_scalar("SELECT name FROM portfolios WHERE user_pid = ? AND id = ?")
+                .bind(pid)
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await?;
+        let Some(name) = name else {
+            return Ok(None);
+        };
+        let lots = self.lots(id).await?;
+        Ok(Some(Portfolio { id, name, lots }))
+    }
+
+    async fn insert_portfolio(&self, pid: &str, name: &str, lots: &[Lot]) -> Result<Portfolio> {
+        let mut tx = self.pool.begin().await?;
+        let id = sqlx::query("INSERT INTO portfolios (user_pid, name) VALUES (?, ?)")
+            .bind(pid)
+            .bind(name)
+            .execute(&mut *tx)
+            .await?
+            .last_insert_rowid();
+        insert_lots(&mut tx, id, lots).await?;
+        tx.commit().await.context("Failed to store portfolio")?;
+
+        Ok(Portfolio {
+            id,
+            name: name.to_string(),
+            lots: lots.to_vec(),
+        })
+    }
+
+    async fn update_portfolio(&self, pid: &str, portfolio: &Portfolio) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+        let updated = sqlx::query("UPDATE portfolios SET name = ? WHERE user_pid = ? AND id = ?")
+            .bind(&portfolio.name)
+            .bind(pid)
+            .bind(portfolio.id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        if updated == 0 {
+            return Ok(false);
+        }
+        sqlx::query("DELETE FROM lots WHERE portfolio_id = ?")
+            .bind(portfolio.id)
+            .execute(&mut *tx)
+            .await?;
+        insert_lots(&mut tx, portfolio.id, &portfolio.lots).await?;
+        tx.commit().await.context("Failed to store portfolio")?;
+        Ok(true)
+    }
+
+    async fn delete_portfolio(&self, pid: &str, id: i64) -> Result<bool> {
+        let deleted = sqlx::query("DELETE FROM portfolios WHERE user_pid = ? AND id = ?")
+            .bind(pid)
+            .bind(id)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        Ok(deleted == 1)
+    }
+}
@@ -0,0 +1,36 @@
+use crate::common::settings::Settings;
+use crate::datastore::sqlite::prepare_database;
+use anyhow::Context;
+use async_trait::async_trait;
+use axum::Router as AxumRouter;
+use loco_rs::Result;
+use loco_rs::app::{AppContext, Initializer};
+
+/// Creates the SQLite schema and ingests the configured workbook before the app serves requests
+pub struct DatabaseInitializer;
+
+#[async_trait]
+impl Initializer for DatabaseInitializer {
+    fn name(&self) -> String {
+        "database".to_string()
+    }
+
+    async fn after_routes(&self, router: AxumRouter, ctx: &AppContext) -> Result<AxumRouter> {
+        let settings = ctx
+            .config
+            .settings
+            .as_ref()
+            .context("Setting key in settings not found")
+            .and_then(Settings::from_json)
+            .context("Failed to parse settings")
+            .map_err(|e| loco_rs::Error::from(e.into_boxed_dyn_error()))?;
+
+        if let Some(database_url) = settings.database_url() {
+            prepare_database(database_url, &settings)
+                .await
+                .context("Failed to prepare database")
+                .map_err(|e| loco_rs::Error::from(e.into_boxed_dyn_error()))?;
+        }
+        Ok(router)
+    }
+}
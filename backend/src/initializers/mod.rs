@@ -1 +1,2 @@
-
+#[cfg(feature = "sqlite")]
+pub mod database;
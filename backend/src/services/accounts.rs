@@ -0,0 +1,364 @@
+use crate::common::settings::Settings;
+use crate::services::portfolios::{Lot, Portfolio};
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use loco_rs::auth::jwt::JWT;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::sync::{Arc, LazyLock, Mutex};
+
+/// Same as the `minLength` of the password in `openapi.yaml`
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Verified instead of a password hash when the email is not registered, so that a login takes
+/// as long whether the email is registered or not
+static DUMMY_PASSWORD_HASH: LazyLock<String> = LazyLock::new(|| {
+    loco_rs::hash::hash_password("not the password of anyone")
+        .expect("Hashing a constant password never fails")
+});
+
+/// A registered user, known to clients by `pid` only
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct User {
+    pub(crate) pid: String,
+    pub(crate) email: String,
+    pub(crate) name: String,
+    pub(crate) password_hash: String,
+}
+
+/// Users and their saved portfolios. Portfolios are only reachable through the user owning them.
+#[async_trait]
+pub(crate) trait AccountStore {
+    /// Returns `false` when the email is already registered
+    async fn insert_user(&self, user: &User) -> Result<bool>;
+    async fn find_user(&self, pid: &str) -> Result<Option<User>>;
+    async fn find_user_by_email(&self, email: &str) -> Result<Option<User>>;
+    async fn portfolios(&self, pid: &str) -> Result<Vec<Portfolio>>;
+    async fn portfolio(&self, pid: &str, id: i64) -> Result<Option<Portfolio>>;
+    async fn insert_portfolio(&self, pid: &str, name: &str, lots: &[Lot]) -> Result<Portfolio>;
+    /// Returns `false` when the user has no such portfolio
+    async fn update_portfolio(&self, pid: &str, portfolio: &Portfolio) -> Result<bool>;
+    /// Returns `false` when the user has no such portfolio
+    async fn delete_portfolio(&self, pid: &str, id: i64) -> Result<bool>;
+}
+
+/// Keeps the accounts in memory, or in SQLite when a `database_url` is configured
+pub(crate) fn create_account_store(
+    settings: &Settings,
+) -> Result<Arc<dyn AccountStore + Send + Sync>> {
+    match settings.database_url() {
+        Some(database_url) => open_database(database_url),
+        None => {
+            tracing::warn!("No database_url configured, accounts are lost on restart");
+            Ok(Arc::new(MemoryAccountStore::default()))
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+fn open_database(database_url: &str) -> Result<Arc<dyn AccountStore + Send + Sync>> {
+    let store = crate::datastore::sqlite::SqliteAccountStore::open(database_url)?;
+    Ok(Arc::new(store))
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn open_database(_database_url: &str) -> Result<Arc<dyn AccountStore + Send + Sync>> {
+    anyhow::bail!("database_url is configured, but the binary was built without the sqlite feature")
+}
+
+pub(crate) enum Registration {
+    Created(User),
+    Invalid(String),
+    EmailTaken,
+}
+
+/// Logging in is disabled, no JWT secret is configured
+#[derive(Debug)]
+pub(crate) struct LoginUnavailable;
+
+impl Display for LoginUnavailable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cannot log in, no JWT secret configured")
+    }
+}
+
+impl std::error::Error for LoginUnavailable {}
+
+/// Registration and JWT authentication of users, in the way of the Loco SaaS starter
+pub(crate) struct Accounts {
+    store: Arc<dyn AccountStore + Send + Sync>,
+    /// Missing when no JWT secret is configured, which keeps every user logged out
+    jwt: Option<JWT>,
+    /// Token lifetime in seconds
+    expiration: u64,
+}
+
+impl Accounts {
+    pub(crate) fn new(
+        store: Arc<dyn AccountStore + Send + Sync>,
+        jwt_secret: &str,
+        expiration: u64,
+    ) -> Self {
+        if jwt_secret.is_empty() {
+            tracing::warn!("No JWT secret configured, users cannot log in");
+        }
+        Self {
+            store,
+            jwt: Some(jwt_secret)
+                .filter(|secret| !secret.is_empty())
+                .map(JWT::new),
+            expiration,
+        }
+    }
+
+    pub(crate) fn store(&self) -> &(dyn AccountStore + Send + Sync) {
+        self.store.as_ref()
+    }
+
+    pub(crate) async fn register(
+        &self,
+        email: &str,
+        password: &str,
+        name: &str,
+    ) -> Result<Registration> {
+        let email = normalize_email(email);
+        if !is_valid_email(&email) {
+            return Ok(Registration::Invalid(format!("Invalid email [{email}]")));
+        }
+        if password.chars().count() < MIN_PASSWORD_LENGTH {
+            return Ok(Registration::Invalid(format!(
+                "Password must have at least {MIN_PASSWORD_LENGTH} characters"
+            )));
+        }
+
+        let password = password.to_string();
+        // Argon2 takes tens of milliseconds on purpose, too long for an async worker
+        let password_hash =
+            tokio::task::spawn_blocking(move || loco_rs::hash::hash_password(&password))
+                .await?
+                .map_err(|e| anyhow!("Failed to hash password: {e}"))?;
+        let user = User {
+            pid: uuid::Uuid::new_v4().to_string(),
+            email,
+            name: name.trim().to_string(),
+            password_hash,
+        };
+        if !self.store.insert_user(&user).await? {
+            return Ok(Registration::EmailTaken);
+        }
+
+        tracing::info!(pid = user.pid, "Registered user");
+        Ok(Registration::Created(user))
+    }
+
+    /// Token for the user with the given credentials, `None` when they do not match.
+    ///
+    /// Fails with [`LoginUnavailable`] when no JWT secret is configured.
+    pub(crate) async fn login(
+        &self,
+        email: &str,
+        password: &str,
+    ) -> Result<Option<(User, String)>> {
+        let jwt = self.jwt.as_ref().ok_or(LoginUnavailable)?;
+        let user = self
+            .store
+            .find_user_by_email(&normalize_email(email))
+            .await?;
+        let password = password.to_string();
+        let password_hash = user.as_ref().map_or_else(
+            || DUMMY_PASSWORD_HASH.clone(),
+            |user| user.password_hash.clone(),
+        );
+        let verified = tokio::task::spawn_blocking(move || {
+            loco_rs::hash::verify_password(&password, &password_hash)
+        })
+        .await?;
+        let Some(user) = user.filter(|_| verified) else {
+            return Ok(None);
+        };
+
+        let token = jwt
+            .generate_token(self.expiration, user.pid.clone(), serde_json::Map::new())
+            .map_err(|e| anyhow!("Failed to generate token: {e}"))?;
+        Ok(Some((user, token)))
+    }
+
+    /// Pid of the user the token was issued to, `None` when it is invalid or expired
+    pub(crate) fn authenticate(&self, token: &str) -> Option<String> {
+        let jwt = self.jwt.as_ref()?;
+        jwt.validate(token).map(|token| token.claims.pid).ok()
+    }
+}
+
+fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+fn is_valid_email(email: &str) -> bool {
+    email.split_once('@').is_some_and(|(local, domain)| {
+        !local.is_empty() && domain.contains('.') && !domain.contains('@')
+    })
+}
+
+/// Accounts kept for the lifetime of the process, for development and tests
+#[derive(Default)]
+pub(crate) struct MemoryAccountStore {
+    state: Mutex<MemoryAccounts>,
+}
+
+#[derive(Default)]
+struct MemoryAccounts {
+    users: Vec<User>,
+    /// Portfolios keyed by their ID, with the pid of their owner
+    portfolios: BTreeMap<i64, (String, Portfolio)>,
+    last_portfolio_id: i64,
+}
+
+impl MemoryAccountStore {
+    fn state(&self) -> std::sync::MutexGuard<'_, MemoryAccounts> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[async_trait]
+impl AccountStore for MemoryAccountStore {
+    async fn insert_user(&self, user: &User) -> Result<bool> {
+        let mut state = self.state();
+        if state.users.iter().any(|u| u.email == user.email) {
+            return Ok(false);
+        }
+        state.users.push(user.clone());
+        Ok(true)
+    }
+
+    async fn find_user(&self, pid: &str) -> Result<Option<User>> {
+        Ok(self.state().users.iter().find(|u| u.pid == pid).cloned())
+    }
+
+    async fn find_user_by_email(&self, email: &str) -> Result<Option<User>> {
+        Ok(self
+            .state()
+            .users
+            .iter()
+            .find(|u| u.email == email)
+            .cloned())
+    }
+
+    async fn portfolios(&self, pid: &str) -> Result<Vec<Portfolio>> {
+        Ok(self
+            .state()
+            .portfolios
+            .values()
+            .filter(|(owner, _)| owner == pid)
+            .map(|(_, portfolio)| portfolio.clone())
+            .collect())
+    }
+
+    async fn portfolio(&self, pid: &str, id: i64) -> Result<Option<Portfolio>> {
+        Ok(self
+            .state()
+            .portfolios
+            .get(&id)
+            .filter(|(owner, _)| owner == pid)
+            .map(|(_, portfolio)| portfolio.clone()))
+    }
+
+    async fn insert_portfolio(&self, pid: &str, name: &str, lots: &[Lot]) -> Result<Portfolio> {
+        let mut state = self.state();
+        state.last_portfolio_id += 1;
+        let portfolio = Portfolio {
+            id: state.last_portfolio_id,
+            name: name.to_string(),
+            lots: lots.to_vec(),
+        };
+        state
+            .portfolios
+            .insert(portfolio.id, (pid.to_string(), portfolio.clone()));
+        Ok(portfolio)
+    }
+
+    async fn update_portfolio(&self, pid: &str, portfolio: &Portfolio) -> Result<bool> {
+        match self.state().portfolios.get_mut(&portfolio.id) {
+            Some((owner, stored)) if owner == pid => {
+                *stored = portfolio.clone();
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn delete_portfolio(&self, pid: &str, id: i64) -> Result<bool> {
+        let mut state = self.state();
+        if state
+            .portfolios
+            .get(&id)
+            .is_some_and(|(owner, _)| owner == pid)
+        {
+            state.portfolios.remove(&id);
+            return Ok(true);
+        }
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accounts(jwt_secret: &str) -> Accounts {
+        Accounts::new(Arc::new(MemoryAccountStore::default()), jwt_secret, 60)
+    }
+
+    #[tokio::test]
+    async fn test_reject_short_password() {
+        let registration = accounts("secret")
+            .register("ann@example.com", "short", "Ann")
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            registration,
+            Registration::Invalid(reason) if reason == "Password must have at least 8 characters"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_login_only_with_registered_credentials() {
+        let accounts = accounts("secret");
+        accounts
+            .register("ann@example.com", "correct horse", "Ann")
+            .await
+            .unwrap();
+
+        let login = |email, password| accounts.login(email, password);
+
+        assert!(
+            login("ann@example.com", "correct horse")
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            login("ann@example.com", "wrong horse")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            login("bob@example.com", "correct horse")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cannot_login_without_jwt_secret() {
+        let error = accounts("")
+            .login("ann@example.com", "correct horse")
+            .await
+            .unwrap_err();
+
+        assert!(error.is::<LoginUnavailable>());
+    }
+}
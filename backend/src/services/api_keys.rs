@@ -0,0 +1,83 @@
+use crate::common::settings::ApiKeySettings;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Quotas are counted in fixed windows of this length
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Outcome of checking the key of a request
+#[derive(Debug)]
+pub(crate) enum Access {
+    /// The request has no key
+    Anonymous,
+    /// The request is counted against the quota of the key with this ID
+    Granted(String),
+    UnknownKey,
+    /// The quota of the key with this ID is used up until the window ends
+    QuotaExceeded {
+        id: String,
+        retry_after: Duration,
+    },
+}
+
+/// Authenticates API clients by their key and counts their requests
+pub(crate) struct ApiKeys {
+    keys: Vec<ApiKeySettings>,
+    /// Current window of every key that made a request, by key ID
+    windows: Mutex<HashMap<String, Window>>,
+}
+
+struct Window {
+    started: Instant,
+    requests: u32,
+}
+
+impl ApiKeys {
+    pub(crate) fn new<'a>(keys: impl IntoIterator<Item = &'a ApiKeySettings>) -> Self {
+        Self {
+            keys: keys.into_iter().cloned().collect(),
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub(crate) fn check(&self, key: Option<&[u8]>) -> Access {
+        let Some(key) = key else {
+            return Access::Anonymous;
+        };
+        let Some(api_key) = self
+            .keys
+            .iter()
+            .find(|api_key| constant_time_eq(key, api_key.key.as_bytes()))
+        else {
+            return Access::UnknownKey;
+        };
+
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        let window = windows.entry(api_key.id.clone()).or_insert(Window {
+            started: now,
+            requests: 0,
+        });
+        if now.duration_since(window.started) >= WINDOW {
+            window.started = now;
+            window.requests = 0;
+        }
+        if window.requests >= api_key.requests_per_minute {
+            return Access::QuotaExceeded {
+                id: api_key.id.clone(),
+                retry_after: WINDOW - now.duration_since(window.started),
+            };
+        }
+        window.requests += 1;
+        Access::Granted(api_key.id.clone())
+    }
+}
+
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -1,41 +1,881 @@
+use crate::common::settings::{Settings, SourceFormat, SourceSettings};
+use crate::services::catalogue::{BondsQuery, Page, Pagination};
 use anyhow::{Context, Result};
-use model::{Bond, BondId};
-use std::path::Path;
+use async_trait::async_trait;
+use bonds_reader::{
+    BondPatch, DatasetVersion, DatasetVersions, DefinitionFormat, Diagnostic, MergedBonds,
+    Provenance, SheetStatistics,
+};
+use chrono::{NaiveDate, Utc};
+use itertools::Either;
+use model::{AllBonds, Bond, BondId};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::Write;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
 
+/// `as_of` selects the dataset version published on that day, `None` is the current one
+#[async_trait]
 pub(crate) trait BondsService {
-    fn get_bonds(&self) -> Vec<BondId>;
-    fn get_bond(&self, id: &BondId) -> Option<&Bond>;
+    async fn get_bonds(
+        &self,
+        query: &BondsQuery,
+        pagination: &Pagination,
+        as_of: Option<NaiveDate>,
+    ) -> Result<Page<BondId>>;
+    async fn get_bond(&self, id: &BondId, as_of: Option<NaiveDate>) -> Result<Option<SharedBond>>;
+    /// Bond together with the dataset version it was read from
+    async fn get_bond_with_version(
+        &self,
+        id: &BondId,
+        as_of: Option<NaiveDate>,
+    ) -> Result<Option<(SharedBond, DatasetVersion)>>;
+    /// Every bond together with the dataset version they were read from
+    async fn get_all_bonds(
+        &self,
+        as_of: Option<NaiveDate>,
+    ) -> Result<(Arc<AllBonds>, DatasetVersion)>;
+    /// Dataset version the bonds are read from, without reading any of them
+    async fn version_as_of(&self, as_of: Option<NaiveDate>) -> Result<DatasetVersion>;
+    /// Bond served on the given day, the current one without a day, together with the source of
+    /// each of its fields and the dataset version
+    async fn get_bond_with_provenance(
+        &self,
+        id: &BondId,
+        as_of: Option<NaiveDate>,
+    ) -> Result<Option<(SharedBond, Provenance, DatasetVersion)>>;
+    /// Validates an uploaded workbook and starts serving it when it parses
+    async fn replace_workbook(&self, workbook: Vec<u8>) -> Result<WorkbookUpload>;
+    /// Recorded dataset versions, oldest first
+    async fn versions(&self) -> Result<Vec<DatasetVersion>>;
+}
+
+/// Keeps the bonds in memory, or in SQLite when a `database_url` is configured
+pub(crate) fn create_bonds_service(
+    settings: &Settings,
+) -> Result<Arc<dyn BondsService + Send + Sync>> {
+    if let Some(database_url) = settings.database_url() {
+        return open_database(database_url, settings);
+    }
+
+    let service = BondsServiceImpl::load(settings).map(Arc::new)?;
+    #[cfg(unix)]
+    reload_on_hangup({
+        let service = service.clone();
+        move || {
+            let service = service.clone();
+            async move {
+                // Errors are already logged by `reload`, the previous bonds stay in place
+                let _ = tokio::task::spawn_blocking(move || service.reload()).await;
+            }
+        }
+    })?;
+    Ok(service)
+}
+
+#[cfg(feature = "sqlite")]
+fn open_database(
+    database_url: &str,
+    settings: &Settings,
+) -> Result<Arc<dyn BondsService + Send + Sync>> {
+    let service =
+        crate::datastore::sqlite::SqliteBondsService::open(database_url, settings).map(Arc::new)?;
+    #[cfg(unix)]
+    reload_on_hangup({
+        let service = service.clone();
+        move || {
+            let service = service.clone();
+            async move {
+                // Errors are already logged by `reload`, the latest version stays in place
+                let _ = service.reload().await;
+            }
+        }
+    })?;
+    Ok(service)
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn open_database(
+    _database_url: &str,
+    _settings: &Settings,
+) -> Result<Arc<dyn BondsService + Send + Sync>> {
+    anyhow::bail!("database_url is configured, but the binary was built without the sqlite feature")
+}
+
+/// Bond of a snapshot of the bonds, which it keeps alive instead of copying the bond out of it
+#[derive(Clone)]
+pub(crate) struct SharedBond {
+    bonds: Arc<AllBonds>,
+    id: BondId,
+}
+
+impl SharedBond {
+    /// `None` when the bond is not in the snapshot
+    pub(crate) fn new(bonds: Arc<AllBonds>, id: &BondId) -> Option<Self> {
+        bonds.get(id)?;
+        Some(Self {
+            bonds,
+            id: id.clone(),
+        })
+    }
+}
+
+impl From<Bond> for SharedBond {
+    fn from(bond: Bond) -> Self {
+        let id = bond.id.clone();
+        Self {
+            bonds: Arc::new([bond].into_iter().collect()),
+            id,
+        }
+    }
+}
+
+impl Deref for SharedBond {
+    type Target = Bond;
+
+    fn deref(&self) -> &Bond {
+        self.bonds
+            .get(&self.id)
+            .expect("Bond was in the snapshot when it was shared")
+    }
+}
+
+/// No dataset version was ingested on or before the requested day
+#[derive(Debug)]
+pub(crate) struct NoVersionAsOf(pub(crate) NaiveDate);
+
+impl Display for NoVersionAsOf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "No dataset version published on or before [{}]", self.0)
+    }
+}
+
+impl std::error::Error for NoVersionAsOf {}
+
+pub(crate) enum WorkbookUpload {
+    Activated(Vec<SheetStatistics>),
+    /// The workbook failed validation, the current bonds stay in place
+    Rejected(Vec<WorkbookProblem>),
+}
+
+/// Reason for rejecting an uploaded workbook
+#[derive(Debug)]
+pub(crate) enum WorkbookProblem {
+    /// Found in a sheet by the validation, which may be a mere warning
+    Diagnostic(Diagnostic),
+    /// The workbook as a whole could not be read
+    Unreadable(String),
+}
+
+/// Uploaded workbook that passed validation, with the sources merged on top
+pub(crate) struct ParsedWorkbook {
+    pub(crate) workbook: Vec<u8>,
+    /// Bonds of the workbook alone
+    pub(crate) all_bonds: AllBonds,
+    pub(crate) merged: MergedBonds,
+    pub(crate) statistics: Vec<SheetStatistics>,
+}
+
+pub(crate) enum ParsedUpload {
+    Valid(ParsedWorkbook),
+    Rejected(Vec<WorkbookProblem>),
+}
+
+/// Provenance of the fields supplied by the issuer workbook
+const WORKBOOK_SOURCE: &str = "workbook";
+
+/// Bonds being served, replaced as a whole so that readers keep the snapshot they started with
+#[derive(Clone)]
+struct Catalogue {
+    bonds: Arc<AllBonds>,
+    provenance: Arc<HashMap<BondId, Provenance>>,
+    version: DatasetVersion,
+}
+
+impl Catalogue {
+    fn new(merged: MergedBonds, version: DatasetVersion) -> Self {
+        Self {
+            bonds: Arc::new(merged.all_bonds),
+            provenance: Arc::new(merged.provenance),
+            version,
+        }
+    }
 }
 
 pub(crate) struct BondsServiceImpl {
-    map: std::collections::HashMap<BondId, Bond>,
+    catalogue: RwLock<Catalogue>,
+    /// `None` when serving the data embedded in the binary
+    bonds_location: Option<String>,
+    dataset_location: Option<String>,
+    sources: Vec<SourceSettings>,
+    versions: Option<Arc<Mutex<DatasetVersions>>>,
+    history: Mutex<History>,
+}
+
+/// Number of older versions kept in memory, `as_of` requests mostly ask for the last few
+const HISTORY_CAPACITY: usize = 4;
+
+/// Older versions loaded for `as_of` requests, least recently used first
+#[derive(Default)]
+struct History(VecDeque<Catalogue>);
+
+impl History {
+    fn get(&mut self, hash: &str) -> Option<Catalogue> {
+        let position = self
+            .0
+            .iter()
+            .position(|cached| cached.version.hash == hash)?;
+        let catalogue = self.0.remove(position)?;
+        self.0.push_back(catalogue.clone());
+        Some(catalogue)
+    }
+
+    fn insert(&mut self, catalogue: Catalogue) {
+        // Concurrent requests may have loaded the same version
+        self.0
+            .retain(|cached| cached.version.hash != catalogue.version.hash);
+        if self.0.len() == HISTORY_CAPACITY {
+            self.0.pop_front();
+        }
+        self.0.push_back(catalogue);
+    }
 }
 
 impl BondsServiceImpl {
-    pub(crate) fn new<P: AsRef<Path>>(directory: P) -> Result<Self> {
-        let all_bonds = bonds_reader::read_bonds(directory.as_ref()).with_context(|| {
+    /// Loads the compiled dataset when one is configured, falling back to the workbook.
+    ///
+    /// Without a `bonds_location` the data embedded in the binary is used.
+    pub(crate) fn load(settings: &Settings) -> Result<Self> {
+        let Some(bonds_location) = &settings.bonds_location else {
+            return Self::embedded(settings);
+        };
+        if let Some(dataset_location) = &settings.dataset_location {
+            match read_dataset(dataset_location) {
+                Ok(all_bonds) => return Self::with_bonds(all_bonds, settings),
+                Err(e) => tracing::warn!(
+                    error = ?e,
+                    dataset_location,
+                    "Failed to load dataset, reading workbook instead"
+                ),
+            }
+        }
+        let workbook = std::fs::read(bonds_location)
+            .with_context(|| format!("Failed to read workbook: {bonds_location}"))?;
+        Self::from_workbook(&workbook, settings)
+    }
+
+    #[cfg(feature = "embedded-dataset")]
+    fn embedded(settings: &Settings) -> Result<Self> {
+        use crate::common::embedded;
+
+        let workbook = embedded::workbook().context("No workbook embedded in the binary")?;
+        match embedded::dataset().map(bonds_reader::read_dataset_from_reader) {
+            Some(Ok(all_bonds)) => Self::with_bonds(all_bonds, settings),
+            Some(Err(e)) => {
+                tracing::warn!(error = ?e, "Failed to load embedded dataset, reading workbook instead");
+                Self::from_workbook(workbook, settings)
+            }
+            None => Self::from_workbook(workbook, settings),
+        }
+    }
+
+    #[cfg(not(feature = "embedded-dataset"))]
+    fn embedded(_settings: &Settings) -> Result<Self> {
+        anyhow::bail!("Missing bonds_location, required without the embedded-dataset feature")
+    }
+
+    /// Builds the service from workbook content that does not have to come from `bonds_location`
+    pub(crate) fn from_workbook(workbook: &[u8], settings: &Settings) -> Result<Self> {
+        let all_bonds = read_workbook(workbook)?;
+        Self::with_bonds(all_bonds, settings)
+    }
+
+    fn with_bonds(all_bonds: AllBonds, settings: &Settings) -> Result<Self> {
+        let versions = settings
+            .versions_location
+            .as_ref()
+            .map(DatasetVersions::open)
+            .transpose()
+            .context("Failed to open dataset versions")?
+            .map(|versions| Arc::new(Mutex::new(versions)));
+
+        let merged = merge_sources(&settings.sources, &all_bonds)?;
+        let version = record_version(versions.as_deref(), &merged);
+
+        Ok(Self {
+            catalogue: RwLock::new(Catalogue::new(merged, version)),
+            bonds_location: settings.bonds_location.clone(),
+            dataset_location: settings.dataset_location.clone(),
+            sources: settings.sources.clone(),
+            versions,
+            history: Mutex::default(),
+        })
+    }
+
+    /// Re-reads the workbook and the sources and swaps them in, the current bonds are kept when
+    /// any of them fails to parse
+    #[tracing::instrument(err(Debug), skip(self), fields(bonds_location = ?self.bonds_location))]
+    pub(crate) fn reload(&self) -> Result<usize> {
+        let bonds_location = self
+            .bonds_location
+            .as_ref()
+            .context("Missing bonds_location, the embedded data cannot be reloaded")?;
+        let workbook = std::fs::read(bonds_location)
+            .with_context(|| format!("Failed to read workbook: {bonds_location}"))?;
+        let all_bonds = read_workbook(&workbook)?;
+        let merged = merge_sources(&self.sources, &all_bonds)?;
+        let count = merged.all_bonds.len();
+        self.activate(&all_bonds, merged);
+
+        tracing::info!(count, "Reloaded bonds");
+        Ok(count)
+    }
+
+    /// Swaps in new bonds and refreshes the dataset, blocking while the files are written
+    fn activate(&self, all_bonds: &AllBonds, merged: MergedBonds) {
+        let version = store_version(
+            self.dataset_location.as_deref(),
+            self.versions.as_deref(),
+            all_bonds,
+            &merged,
+        );
+        self.serve(merged, version);
+    }
+
+    fn serve(&self, merged: MergedBonds, version: DatasetVersion) {
+        *self.catalogue.write().expect("Bonds lock poisoned") = Catalogue::new(merged, version);
+    }
+
+    /// Version published on the given day, with the paths of its dataset and provenance
+    fn recorded_version(&self, as_of: NaiveDate) -> Result<(DatasetVersion, PathBuf, PathBuf)> {
+        let versions = self
+            .versions
+            .as_ref()
+            .ok_or(NoVersionAsOf(as_of))?
+            .lock()
+            .expect("Versions lock poisoned");
+        let version = versions.as_of(as_of).ok_or(NoVersionAsOf(as_of))?.clone();
+        let dataset_path = versions.dataset_path(&version);
+        let provenance_path = versions.provenance_path(&version);
+        Ok((version, dataset_path, provenance_path))
+    }
+
+    /// Bonds being served, with their version
+    fn current(&self) -> (Arc<AllBonds>, DatasetVersion) {
+        let catalogue = self.catalogue.read().expect("Bonds lock poisoned");
+        (catalogue.bonds.clone(), catalogue.version.clone())
+    }
+
+    /// Bonds served on the given day, the current ones without a day, with their provenance and
+    /// version.
+    ///
+    /// Older versions are loaded on the blocking pool, without holding any lock meanwhile.
+    async fn catalogue_as_of(&self, as_of: Option<NaiveDate>) -> Result<Catalogue> {
+        let Some(as_of) = as_of else {
+            return Ok(self.catalogue.read().expect("Bonds lock poisoned").clone());
+        };
+        let (version, dataset_path, provenance_path) = self.recorded_version(as_of)?;
+
+        let cached = self
+            .history
+            .lock()
+            .expect("History lock poisoned")
+            .get(&version.hash);
+        if let Some(catalogue) = cached {
+            return Ok(catalogue);
+        }
+        let (all_bonds, provenance) = tokio::task::spawn_blocking(move || {
+            anyhow::Ok((
+                read_dataset(dataset_path)?,
+                bonds_reader::read_provenance(provenance_path)?,
+            ))
+        })
+        .await?
+        .with_context(|| format!("Failed to load dataset version [{}]", version.hash))?;
+        let catalogue = Catalogue::new(
+            MergedBonds {
+                all_bonds,
+                provenance,
+            },
+            version,
+        );
+        self.history
+            .lock()
+            .expect("History lock poisoned")
+            .insert(catalogue.clone());
+        Ok(catalogue)
+    }
+
+    /// Bonds served on the given day, the current ones without a day, with their version
+    async fn snapshot_as_of(
+        &self,
+        as_of: Option<NaiveDate>,
+    ) -> Result<(Arc<AllBonds>, DatasetVersion)> {
+        let catalogue = self.catalogue_as_of(as_of).await?;
+        Ok((catalogue.bonds, catalogue.version))
+    }
+}
+
+/// Refreshes the dataset, so that a restart does not bring back old data, and records the version
+/// of the merged bonds.
+///
+/// The dataset only holds the workbook bonds, sources are merged again at boot.
+fn store_version(
+    dataset_location: Option<&str>,
+    versions: Option<&Mutex<DatasetVersions>>,
+    all_bonds: &AllBonds,
+    merged: &MergedBonds,
+) -> DatasetVersion {
+    if let Some(dataset_location) = dataset_location
+        && let Err(e) = bonds_reader::write_dataset(all_bonds, dataset_location)
+    {
+        tracing::warn!(error = ?e, dataset_location, "Failed to refresh dataset");
+    }
+    record_version(versions, merged)
+}
+
+/// Keeps a copy of the bonds merged from the workbook and the sources, unless they did not change.
+///
+/// Without recorded versions the bonds are versioned by their content as of now.
+fn record_version(
+    versions: Option<&Mutex<DatasetVersions>>,
+    merged: &MergedBonds,
+) -> DatasetVersion {
+    let ingested_at = Utc::now();
+    if let Some(versions) = versions {
+        let mut versions = versions.lock().expect("Versions lock poisoned");
+        match versions.record(merged, ingested_at) {
+            Ok(version) => {
+                tracing::info!(
+                    hash = %version.hash,
+                    ingested_at = %version.ingested_at,
+                    "Serving dataset version"
+                );
+                return version.clone();
+            }
+            Err(e) => tracing::warn!(error = ?e, "Failed to record dataset version"),
+        }
+    }
+    DatasetVersion {
+        hash: bonds_reader::bonds_hash(&merged.all_bonds),
+        ingested_at,
+    }
+}
+
+fn read_dataset<P: AsRef<Path>>(path: P) -> Result<AllBonds> {
+    bonds_reader::read_dataset(path.as_ref()).with_context(|| {
+        format!(
+            "Failed to read Bonds from dataset: {}",
+            path.as_ref().display()
+        )
+    })
+}
+
+/// Merges the supplementary sources on top of the workbook bonds, in the configured order
+pub(crate) fn merge_sources(
+    sources: &[SourceSettings],
+    all_bonds: &AllBonds,
+) -> Result<MergedBonds> {
+    let mut merged = MergedBonds::new(WORKBOOK_SOURCE, all_bonds.clone());
+    for source in sources {
+        let patches = read_source(source).with_context(|| {
             format!(
-                "Failed to read Bonds from directory: {}",
-                directory.as_ref().display()
+                "Failed to read source [{}]: {}",
+                source.name, source.location
             )
         })?;
-        let mut map = std::collections::HashMap::new();
-        for bond in all_bonds.edo.values().chain(all_bonds.rod.values()) {
-            map.insert(bond.id.clone(), bond.clone());
+        merged.apply(&source.name, patches)?;
+    }
+    Ok(merged)
+}
+
+fn read_source(source: &SourceSettings) -> Result<Vec<BondPatch>> {
+    match source.format {
+        SourceFormat::Workbook => Ok(bonds_reader::read_bonds(&source.location)?
+            .iter()
+            .map(BondPatch::from)
+            .collect()),
+        SourceFormat::Csv => bonds_reader::read_corrections(File::open(&source.location)?),
+        SourceFormat::Definitions => {
+            let format = DefinitionFormat::from_path(&source.location)?;
+            Ok(
+                bonds_reader::read_definitions(File::open(&source.location)?, format)?
+                    .iter()
+                    .map(BondPatch::from)
+                    .collect(),
+            )
         }
+    }
+}
 
-        Ok(Self { map })
+/// Validates and reads an uploaded workbook on the blocking pool, as parsing takes a while
+pub(crate) async fn parse_upload(
+    workbook: Vec<u8>,
+    sources: &[SourceSettings],
+) -> Result<ParsedUpload> {
+    let sources = sources.to_vec();
+    tokio::task::spawn_blocking(move || parse_workbook(workbook, &sources)).await?
+}
+
+fn parse_workbook(workbook: Vec<u8>, sources: &[SourceSettings]) -> Result<ParsedUpload> {
+    let problems = match bonds_reader::validate_workbook_from_reader(workbook.as_slice()) {
+        Ok(report) if report.has_errors() => report
+            .diagnostics
+            .into_iter()
+            .map(WorkbookProblem::Diagnostic)
+            .collect(),
+        Ok(_) => Vec::new(),
+        Err(e) => vec![WorkbookProblem::Unreadable(format!("{e:#}"))],
+    };
+    let read = if problems.is_empty() {
+        bonds_reader::read_bonds_with_statistics_from_reader(workbook.as_slice())
+            .map_err(|e| vec![WorkbookProblem::Unreadable(format!("{e:#}"))])
+    } else {
+        Err(problems)
+    };
+    let (all_bonds, statistics) = match read {
+        Ok(result) => result,
+        Err(problems) => {
+            tracing::warn!(?problems, "Rejected uploaded workbook");
+            return Ok(ParsedUpload::Rejected(problems));
+        }
+    };
+    let merged = merge_sources(sources, &all_bonds)?;
+
+    Ok(ParsedUpload::Valid(ParsedWorkbook {
+        workbook,
+        all_bonds,
+        merged,
+        statistics,
+    }))
+}
+
+pub(crate) fn read_workbook(workbook: &[u8]) -> Result<AllBonds> {
+    bonds_reader::read_bonds_from_bytes(workbook).context("Failed to read Bonds from workbook")
+}
+
+/// Keeps an uploaded workbook at `bonds_location`, so that it is served again after a restart
+pub(crate) fn persist_upload(bonds_location: Option<&str>, workbook: &[u8]) -> Result<()> {
+    match bonds_location {
+        Some(bonds_location) => persist_workbook(workbook, Path::new(bonds_location)),
+        None => {
+            tracing::warn!("Missing bonds_location, the uploaded workbook is lost on restart");
+            Ok(())
+        }
     }
 }
 
+/// Replaces the workbook at `bonds_location` with a rename, so that readers never see a partial file
+fn persist_workbook(workbook: &[u8], bonds_location: &Path) -> Result<()> {
+    let directory = bonds_location
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+
+    let mut upload = tempfile::NamedTempFile::new_in(directory)
+        .context("Failed to create temporary workbook")?;
+    upload
+        .write_all(workbook)
+        .context("Failed to write temporary workbook")?;
+    upload
+        .persist(bonds_location)
+        .context("Failed to replace workbook")?;
+    Ok(())
+}
+
+/// Runs `reload` whenever the process receives `SIGHUP`
+#[cfg(unix)]
+fn reload_on_hangup<F, Fut>(reload: F) -> Result<()>
+where
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut hangup = signal(SignalKind::hangup()).context("Failed to listen for SIGHUP")?;
+    tokio::spawn(async move {
+        while hangup.recv().await.is_some() {
+            reload().await;
+        }
+    });
+    Ok(())
+}
+
+#[async_trait]
 impl BondsService for BondsServiceImpl {
-    fn get_bonds(&self) -> Vec<BondId> {
-        let mut v: Vec<_> = self.map.keys().cloned().collect();
+    async fn get_bonds(
+        &self,
+        query: &BondsQuery,
+        pagination: &Pagination,
+        as_of: Option<NaiveDate>,
+    ) -> Result<Page<BondId>> {
+        let (bonds, _) = self.snapshot_as_of(as_of).await?;
+        let candidates = match query.bond_type {
+            Some(bond_type) => Either::Left(bonds.of_type(bond_type)),
+            None => Either::Right(bonds.iter()),
+        };
+        let mut v: Vec<_> = candidates
+            .filter(|bond| query.matches(bond))
+            .map(|bond| bond.id.clone())
+            .collect();
         v.sort();
-        v
+        Ok(pagination.apply(v))
+    }
+
+    async fn get_bond(&self, id: &BondId, as_of: Option<NaiveDate>) -> Result<Option<SharedBond>> {
+        let (bonds, _) = self.snapshot_as_of(as_of).await?;
+        Ok(SharedBond::new(bonds, id))
+    }
+
+    async fn get_bond_with_version(
+        &self,
+        id: &BondId,
+        as_of: Option<NaiveDate>,
+    ) -> Result<Option<(SharedBond, DatasetVersion)>> {
+        let (bonds, version) = self.snapshot_as_of(as_of).await?;
+        Ok(SharedBond::new(bonds, id).map(|bond| (bond, version)))
+    }
+
+    async fn get_all_bonds(
+        &self,
+        as_of: Option<NaiveDate>,
+    ) -> Result<(Arc<AllBonds>, DatasetVersion)> {
+        self.snapshot_as_of(as_of).await
+    }
+
+    async fn get_bond_with_provenance(
+        &self,
+        id: &BondId,
+        as_of: Option<NaiveDate>,
+    ) -> Result<Option<(SharedBond, Provenance, DatasetVersion)>> {
+        let catalogue = self.catalogue_as_of(as_of).await?;
+        let bond = SharedBond::new(catalogue.bonds, id);
+        let provenance = catalogue.provenance.get(id).cloned();
+        Ok(bond
+            .zip(provenance)
+            .map(|(bond, provenance)| (bond, provenance, catalogue.version)))
+    }
+
+    #[tracing::instrument(err(Debug), skip_all, fields(bonds_location = ?self.bonds_location))]
+    async fn replace_workbook(&self, workbook: Vec<u8>) -> Result<WorkbookUpload> {
+        let parsed = match parse_upload(workbook, &self.sources).await? {
+            ParsedUpload::Valid(parsed) => parsed,
+            ParsedUpload::Rejected(problems) => return Ok(WorkbookUpload::Rejected(problems)),
+        };
+        let bonds_location = self.bonds_location.clone();
+        let dataset_location = self.dataset_location.clone();
+        let versions = self.versions.clone();
+        // Writing the files and hashing the bonds block like the parsing does
+        let (parsed, version) = tokio::task::spawn_blocking(move || {
+            persist_upload(bonds_location.as_deref(), &parsed.workbook)?;
+            let version = store_version(
+                dataset_location.as_deref(),
+                versions.as_deref(),
+                &parsed.all_bonds,
+                &parsed.merged,
+            );
+            anyhow::Ok((parsed, version))
+        })
+        .await??;
+
+        let count = parsed.merged.all_bonds.len();
+        self.serve(parsed.merged, version);
+
+        tracing::info!(count, "Activated uploaded workbook");
+        Ok(WorkbookUpload::Activated(parsed.statistics))
     }
 
-    fn get_bond(&self, id: &BondId) -> Option<&Bond> {
-        self.map.get(id)
+    async fn version_as_of(&self, as_of: Option<NaiveDate>) -> Result<DatasetVersion> {
+        match as_of {
+            Some(as_of) => self.recorded_version(as_of).map(|(version, ..)| version),
+            None => Ok(self.current().1),
+        }
+    }
+
+    async fn versions(&self) -> Result<Vec<DatasetVersion>> {
+        Ok(self
+            .versions
+            .as_ref()
+            .map(|versions| {
+                versions
+                    .lock()
+                    .expect("Versions lock poisoned")
+                    .versions()
+                    .to_vec()
+            })
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+    use pretty_assertions::{assert_eq, assert_ne};
+    use serde_json::json;
+
+    const WORKBOOK: &str = "tests/fixtures/bonds/test.xls";
+
+    /// Service reading a copy of the test workbook, which the test may overwrite
+    fn load_copy(
+        directory: &tempfile::TempDir,
+        mut settings: serde_json::Value,
+    ) -> (BondsServiceImpl, std::path::PathBuf) {
+        let bonds_location = directory.path().join("bonds.xls");
+        std::fs::copy(WORKBOOK, &bonds_location).unwrap();
+        settings["bonds_location"] = json!(bonds_location);
+        let settings = Settings::from_json(&settings).unwrap();
+        (BondsServiceImpl::load(&settings).unwrap(), bonds_location)
+    }
+
+    #[test]
+    fn test_reload_workbook() {
+        let directory = tempfile::tempdir().unwrap();
+        let (service, bonds_location) = load_copy(&directory, json!({}));
+        let (_, version) = service.current();
+
+        // Same bonds saved as XLSX, which are still the same version
+        std::fs::copy("tests/fixtures/bonds/test.xlsx", &bonds_location).unwrap();
+        assert_eq!(service.reload().unwrap(), 6);
+        let (_, reloaded) = service.current();
+        assert_eq!(reloaded.hash, version.hash);
+
+        std::fs::copy("tests/fixtures/bonds/revised.ods", &bonds_location).unwrap();
+        assert_eq!(service.reload().unwrap(), 6);
+        let (_, reloaded) = service.current();
+        assert_ne!(reloaded.hash, version.hash);
+    }
+
+    #[test]
+    fn test_record_version_when_source_changes() {
+        let directory = tempfile::tempdir().unwrap();
+        let corrections = directory.path().join("corrections.csv");
+        std::fs::write(&corrections, "id,rates\nEDO0835,0.06;0.0575\n").unwrap();
+        let (service, _) = load_copy(
+            &directory,
+            json!({
+                "versions_location": directory.path().join("versions"),
+                "sources": [{ "name": "corrections", "location": corrections, "format": "csv" }],
+            }),
+        );
+        let (_, version) = service.current();
+
+        // Only the source changes, the workbook stays the same
+        std::fs::write(&corrections, "id,rates\nEDO0835,0.06;0.058\n").unwrap();
+
+        service.reload().unwrap();
+        let (bonds, reloaded) = service.current();
+        assert_ne!(reloaded.hash, version.hash);
+        let edo0835 = bonds.get(&BondId::new("EDO0835").unwrap()).unwrap();
+        assert_eq!(edo0835.rates, vec![0.06, 0.058]);
+        let versions = service.versions.as_ref().unwrap().lock().unwrap();
+        assert_eq!(versions.versions().len(), 2);
+    }
+
+    #[test]
+    fn test_load_dataset_without_reading_workbook() {
+        let directory = tempfile::tempdir().unwrap();
+        let dataset_location = directory.path().join("bonds.dataset");
+        let all_bonds = bonds_reader::read_bonds(WORKBOOK).unwrap();
+        bonds_reader::write_dataset(&all_bonds, &dataset_location).unwrap();
+        let settings = Settings::from_json(&json!({
+            "bonds_location": directory.path().join("missing.xls"),
+            "dataset_location": dataset_location,
+        }))
+        .unwrap();
+
+        let service = BondsServiceImpl::load(&settings).unwrap();
+
+        assert_eq!(service.current().0.len(), 6);
+    }
+
+    #[test]
+    fn test_keep_bonds_when_reloaded_workbook_fails_to_parse() {
+        let directory = tempfile::tempdir().unwrap();
+        let (service, bonds_location) = load_copy(&directory, json!({}));
+        let (bonds, version) = service.current();
+
+        std::fs::write(&bonds_location, b"not a workbook").unwrap();
+
+        assert!(service.reload().is_err());
+        let (served, served_version) = service.current();
+        assert!(Arc::ptr_eq(&served, &bonds));
+        assert_eq!(served_version, version);
+        assert!(served.get(&BondId::new("EDO0835").unwrap()).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_bond_with_provenance_as_of_older_version() {
+        let directory = tempfile::tempdir().unwrap();
+        let versions_location = directory.path().join("versions");
+        let id = BondId::new("EDO0835").unwrap();
+        let mut archived = MergedBonds::new("archive", AllBonds::default());
+        archived
+            .apply(
+                "archive",
+                vec![BondPatch {
+                    rates: Some(vec![0.06]),
+                    ..BondPatch::from(
+                        bonds_reader::read_bonds(WORKBOOK)
+                            .unwrap()
+                            .get(&id)
+                            .unwrap(),
+                    )
+                }],
+            )
+            .unwrap();
+        DatasetVersions::open(&versions_location)
+            .unwrap()
+            .record(&archived, "2025-08-01T10:00:00Z".parse().unwrap())
+            .unwrap();
+        let (service, _) = load_copy(
+            &directory,
+            json!({ "versions_location": versions_location }),
+        );
+
+        let (bond, provenance, version) = service
+            .get_bond_with_provenance(&id, Some("2025-08-15".parse().unwrap()))
+            .await
+            .unwrap()
+            .expect("Bond was in the older version");
+        assert_eq!(bond.rates, vec![0.06]);
+        assert_eq!(provenance.rates, "archive");
+        assert_eq!(
+            version.ingested_at,
+            "2025-08-01T10:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+
+        let (_, provenance, _) = service
+            .get_bond_with_provenance(&id, None)
+            .await
+            .unwrap()
+            .expect("Bond is in the workbook");
+        assert_eq!(provenance.rates, WORKBOOK_SOURCE);
+    }
+
+    fn catalogue(hash: &str) -> Catalogue {
+        let version = DatasetVersion {
+            hash: hash.to_string(),
+            ingested_at: Utc::now(),
+        };
+        Catalogue::new(MergedBonds::default(), version)
+    }
+
+    #[test]
+    fn test_history_evicts_least_recently_used_version() {
+        let mut history = History::default();
+        for hash in ["a", "b", "c", "d"] {
+            history.insert(catalogue(hash));
+        }
+        assert!(history.get("a").is_some());
+
+        history.insert(catalogue("e"));
+
+        assert!(history.get("b").is_none());
+        assert!(history.get("a").is_some());
+        assert!(history.get("e").is_some());
     }
 }
@@ -0,0 +1,90 @@
+use anyhow::{Context, Result};
+use chrono::{Months, NaiveDate};
+use model::{Bond, BondType};
+
+/// Filters applied to the bond catalogue. Every filter is optional, bonds have to match all of them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct BondsQuery {
+    pub(crate) bond_type: Option<BondType>,
+    pub(crate) on_sale: Option<NaiveDate>,
+    pub(crate) sale_month: Option<SaleMonth>,
+    pub(crate) maturity_from: Option<NaiveDate>,
+    pub(crate) maturity_to: Option<NaiveDate>,
+}
+
+impl BondsQuery {
+    pub(crate) fn matches(&self, bond: &Bond) -> bool {
+        let on_sale = |date: NaiveDate| bond.initial_date <= date && date <= bond.sale_end;
+
+        self.bond_type
+            .is_none_or(|bond_type| bond.id.bond_type() == bond_type)
+            && self.on_sale.is_none_or(on_sale)
+            && self.sale_month.as_ref().is_none_or(|month| {
+                bond.initial_date <= month.last_day() && month.first_day() <= bond.sale_end
+            })
+            && self
+                .maturity_from
+                .is_none_or(|from| from <= bond.buyout_date)
+            && self.maturity_to.is_none_or(|to| bond.buyout_date <= to)
+    }
+}
+
+/// Calendar month in `YYYY-MM` format
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct SaleMonth(NaiveDate);
+
+impl SaleMonth {
+    pub(crate) fn parse(value: &str) -> Result<Self> {
+        NaiveDate::parse_from_str(&format!("{value}-01"), "%Y-%m-%d")
+            .map(SaleMonth)
+            .with_context(|| format!("Invalid month [{value}], expected YYYY-MM"))
+    }
+
+    pub(crate) fn first_day(&self) -> NaiveDate {
+        self.0
+    }
+
+    pub(crate) fn last_day(&self) -> NaiveDate {
+        self.0 + Months::new(1) - chrono::Duration::days(1)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Pagination {
+    /// 1-based page number
+    pub(crate) page: usize,
+    /// `None` returns everything on a single page
+    pub(crate) per_page: Option<usize>,
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Self {
+            page: 1,
+            per_page: None,
+        }
+    }
+}
+
+impl Pagination {
+    pub(crate) fn apply<T>(&self, items: Vec<T>) -> Page<T> {
+        let total = items.len();
+        let items = match self.per_page {
+            Some(per_page) => items
+                .into_iter()
+                .skip(self.page.saturating_sub(1) * per_page)
+                .take(per_page)
+                .collect(),
+            None if self.page <= 1 => items,
+            None => Vec::new(),
+        };
+        Page { items, total }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Page<T> {
+    pub(crate) items: Vec<T>,
+    /// Number of items across all pages
+    pub(crate) total: usize,
+}
@@ -0,0 +1,128 @@
+use std::io::{Result, Write};
+
+/// Content codings responses are compressed with
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum Encoding {
+    Brotli,
+    Zstd,
+    Gzip,
+    Identity,
+}
+
+/// Supported codings from the best compressing one, picked first when the client has no preference
+const PREFERRED: [Encoding; 3] = [Encoding::Brotli, Encoding::Zstd, Encoding::Gzip];
+
+impl Encoding {
+    /// Name of the coding in `Accept-Encoding` and `Content-Encoding`
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Encoding::Brotli => "br",
+            Encoding::Zstd => "zstd",
+            Encoding::Gzip => "gzip",
+            Encoding::Identity => "identity",
+        }
+    }
+
+    /// Coding with the highest weight in the `Accept-Encoding` value, `Identity` when the client
+    /// accepts none of the supported ones
+    pub(crate) fn negotiate(accept_encoding: &str) -> Self {
+        let weights: Vec<(&str, f32)> = accept_encoding
+            .split(',')
+            .filter_map(|item| {
+                let mut parameters = item.split(';');
+                let coding = parameters.next()?.trim();
+                let weight = parameters
+                    .find_map(|parameter| parameter.trim().strip_prefix("q="))
+                    .map_or(Some(1.0), |weight| weight.trim().parse().ok())?;
+                (!coding.is_empty()).then_some((coding, weight))
+            })
+            .collect();
+        let weight = |name: &str| {
+            weights
+                .iter()
+                .find(|(coding, _)| coding.eq_ignore_ascii_case(name))
+                .or_else(|| weights.iter().find(|(coding, _)| *coding == "*"))
+                .map_or(0.0, |(_, weight)| *weight)
+        };
+
+        let mut best = (Encoding::Identity, 0.0);
+        for encoding in PREFERRED {
+            let weight = weight(encoding.name());
+            if weight > best.1 {
+                best = (encoding, weight);
+            }
+        }
+        best.0
+    }
+
+    pub(crate) fn encode(self, bytes: &[u8]) -> Result<Vec<u8>> {
+        let mut encoder = self.encoder(Vec::new())?;
+        encoder.write_all(bytes)?;
+        encoder.finish()
+    }
+
+    /// Writer compressing what is written to it into `writer`, for bodies encoded as they are sent
+    pub(crate) fn encoder<W: Write>(self, writer: W) -> Result<Encoder<W>> {
+        Ok(match self {
+            Encoding::Brotli => {
+                Encoder::Brotli(Box::new(brotli::CompressorWriter::new(writer, 4096, 9, 22)))
+            }
+            Encoding::Zstd => Encoder::Zstd(zstd::stream::write::Encoder::new(writer, 0)?),
+            Encoding::Gzip => Encoder::Gzip(flate2::write::GzEncoder::new(
+                writer,
+                flate2::Compression::default(),
+            )),
+            Encoding::Identity => Encoder::Identity(writer),
+        })
+    }
+}
+
+pub(crate) enum Encoder<W: Write> {
+    /// Boxed, the window of the encoder takes kilobytes
+    Brotli(Box<brotli::CompressorWriter<W>>),
+    Zstd(zstd::stream::write::Encoder<'static, W>),
+    Gzip(flate2::write::GzEncoder<W>),
+    Identity(W),
+}
+
+impl<W: Write> Encoder<W> {
+    /// Inner writer, holding what was compressed so far
+    pub(crate) fn get_mut(&mut self) -> &mut W {
+        match self {
+            Encoder::Brotli(writer) => writer.get_mut(),
+            Encoder::Zstd(encoder) => encoder.get_mut(),
+            Encoder::Gzip(encoder) => encoder.get_mut(),
+            Encoder::Identity(writer) => writer,
+        }
+    }
+
+    /// Writes the end of the compressed stream, returning the inner writer
+    pub(crate) fn finish(self) -> Result<W> {
+        match self {
+            Encoder::Brotli(writer) => Ok((*writer).into_inner()),
+            Encoder::Zstd(encoder) => encoder.finish(),
+            Encoder::Gzip(encoder) => encoder.finish(),
+            Encoder::Identity(writer) => Ok(writer),
+        }
+    }
+}
+
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        match self {
+            Encoder::Brotli(writer) => writer.write(buf),
+            Encoder::Zstd(encoder) => encoder.write(buf),
+            Encoder::Gzip(encoder) => encoder.write(buf),
+            Encoder::Identity(writer) => writer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        match self {
+            Encoder::Brotli(writer) => writer.flush(),
+            Encoder::Zstd(encoder) => encoder.flush(),
+            Encoder::Gzip(encoder) => encoder.flush(),
+            Encoder::Identity(writer) => writer.flush(),
+        }
+    }
+}
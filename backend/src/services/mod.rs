@@ -1 +1,6 @@
+pub(crate) mod accounts;
+pub(crate) mod api_keys;
 pub(crate) mod bonds;
+pub(crate) mod catalogue;
+pub(crate) mod encoding;
+pub(crate) mod portfolios;
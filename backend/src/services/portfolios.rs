@@ -0,0 +1,96 @@
+use crate::services::bonds::BondsService;
+use anyhow::Result;
+use chrono::NaiveDate;
+use model::{Bond, BondId};
+use std::fmt::{Display, Formatter};
+
+/// Bonds of a single series bought on the same day
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Lot {
+    pub(crate) bond_id: BondId,
+    pub(crate) quantity: u32,
+    pub(crate) purchase_date: NaiveDate,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Portfolio {
+    pub(crate) id: i64,
+    pub(crate) name: String,
+    pub(crate) lots: Vec<Lot>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct PortfolioValuation {
+    pub(crate) date: NaiveDate,
+    /// Sum of the lots with a known value, rounded to grosz
+    pub(crate) total: f64,
+    /// Lots in the order of the portfolio, with their value if known
+    pub(crate) lots: Vec<(Lot, Option<f64>)>,
+}
+
+/// A lot that can not have been bought, e.g. of an unknown series or outside of its sale
+#[derive(Debug)]
+pub(crate) struct InvalidLot(pub(crate) String);
+
+impl Display for InvalidLot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for InvalidLot {}
+
+/// Checks every lot against the current bonds, failing with [`InvalidLot`] on the first invalid one
+pub(crate) async fn check_lots(
+    bonds_service: &(dyn BondsService + Send + Sync),
+    lots: &[Lot],
+) -> Result<()> {
+    for lot in lots {
+        let Some(bond) = bonds_service.get_bond(&lot.bond_id, None).await? else {
+            return Err(InvalidLot(format!("Unknown bond [{}]", lot.bond_id.as_str())).into());
+        };
+        if lot.purchase_date < bond.initial_date || bond.sale_end < lot.purchase_date {
+            return Err(InvalidLot(format!(
+                "Bond [{}] was on sale from {} to {}, not on {}",
+                bond.id.as_str(),
+                bond.initial_date,
+                bond.sale_end,
+                lot.purchase_date
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Values every lot of the portfolio with the current bonds
+pub(crate) async fn value_portfolio(
+    bonds_service: &(dyn BondsService + Send + Sync),
+    portfolio: &Portfolio,
+    date: NaiveDate,
+) -> Result<PortfolioValuation> {
+    let mut lots = Vec::with_capacity(portfolio.lots.len());
+    for lot in &portfolio.lots {
+        let bond = bonds_service.get_bond(&lot.bond_id, None).await?;
+        let value = bond.and_then(|bond| lot_value(lot, &bond, date));
+        lots.push((lot.clone(), value));
+    }
+    let total = lots.iter().filter_map(|(_, value)| *value).sum::<f64>();
+
+    Ok(PortfolioValuation {
+        date,
+        total: (total * 100.0).round() / 100.0,
+        lots,
+    })
+}
+
+/// The series of a bond starts on its first day of sale, a lot bought later follows it shifted
+/// by the days in between
+fn lot_value(lot: &Lot, bond: &Bond, date: NaiveDate) -> Option<f64> {
+    if date < lot.purchase_date {
+        return None;
+    }
+    let held_for = date - lot.purchase_date;
+    bond.value_on(bond.initial_date + held_for)
+        .map(|value| value * f64::from(lot.quantity))
+}
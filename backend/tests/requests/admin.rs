@@ -0,0 +1,199 @@
+use super::settings::request_with_settings;
+use axum_test::multipart::{MultipartForm, Part};
+use bonds_reader::{DatasetVersions, MergedBonds};
+use loco_rs::testing::prelude::*;
+use myapp::app::App;
+use pretty_assertions::{assert_eq, assert_ne};
+use serde_json::json;
+use serial_test::serial;
+use tempfile::TempDir;
+
+const WORKBOOK: &str = "tests/fixtures/bonds/test.xls";
+/// Same bonds as the test workbook, with another first year rate of EDO0835
+const REVISED_WORKBOOK: &str = "tests/fixtures/bonds/revised.ods";
+
+fn workbook_form(content: Vec<u8>) -> MultipartForm {
+    MultipartForm::new().add_part("file", Part::bytes(content).file_name("workbook.xls"))
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_upload_workbook_without_admin_key() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .post("/admin/workbook")
+            .multipart(workbook_form(std::fs::read(WORKBOOK).unwrap()))
+            .await;
+        assert_eq!(res.status_code(), 401);
+
+        let res = request
+            .post("/admin/workbook")
+            .add_header("X-Admin-Key", "wrong-key")
+            .multipart(workbook_form(std::fs::read(WORKBOOK).unwrap()))
+            .await;
+        assert_eq!(res.status_code(), 401);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_upload_workbook() {
+    // The activated workbook replaces the configured one, so it must not be the fixture
+    let directory = TempDir::new().unwrap();
+    let bonds_location = directory.path().join("bonds.xls");
+    std::fs::copy(WORKBOOK, &bonds_location).unwrap();
+
+    let settings = json!({ "bonds_location": bonds_location });
+    request_with_settings(settings, |request, _ctx| async move {
+        let res = request
+            .post("/admin/workbook")
+            .add_header("X-Admin-Key", "test-admin-key")
+            .multipart(workbook_form(std::fs::read(WORKBOOK).unwrap()))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        res.assert_json(&json!({
+            "activated": true,
+            "sheets": [
+                {
+                    "sheet": "EDO",
+                    "bonds": 3,
+                    "first_sale": "2004-10-01",
+                    "last_sale": "2025-08-31"
+                },
+                {
+                    "sheet": "ROD",
+                    "bonds": 3,
+                    "first_sale": "2016-10-01",
+                    "last_sale": "2025-08-31"
+                }
+            ],
+            "errors": []
+        }));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_get_bond_as_of_before_upload() {
+    let directory = TempDir::new().unwrap();
+    let bonds_location = directory.path().join("bonds.xls");
+    std::fs::copy(WORKBOOK, &bonds_location).unwrap();
+
+    // The configured workbook was ingested long before the upload
+    let versions_location = directory.path().join("versions");
+    DatasetVersions::open(&versions_location)
+        .unwrap()
+        .record(
+            &MergedBonds::new("workbook", bonds_reader::read_bonds(WORKBOOK).unwrap()),
+            "2025-01-01T00:00:00Z".parse().unwrap(),
+        )
+        .unwrap();
+
+    let settings = json!({
+        "bonds_location": bonds_location,
+        "versions_location": versions_location,
+        // Without corrections, the recorded version holds the bonds being served
+        "sources": [],
+    });
+    request_with_settings(settings, |request, _ctx| async move {
+        let before = request.get("/bonds/EDO0835/csv").await.text();
+        let bond_before = request
+            .get("/bonds/EDO0835")
+            .await
+            .json::<serde_json::Value>();
+
+        let res = request
+            .post("/admin/workbook")
+            .add_header("X-Admin-Key", "test-admin-key")
+            .multipart(workbook_form(std::fs::read(REVISED_WORKBOOK).unwrap()))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let versions = request
+            .get("/versions")
+            .await
+            .json::<Vec<serde_json::Value>>();
+        assert_eq!(versions.len(), 2);
+
+        let res = request.get("/bonds/EDO0835/csv?as_of=2025-01-31").await;
+        assert_eq!(res.status_code(), 200);
+        assert_eq!(res.text(), before);
+        // Twice, the second time from the versions kept in memory
+        let res = request.get("/bonds/EDO0835/csv?as_of=2025-01-31").await;
+        assert_eq!(res.text(), before);
+
+        let res = request.get("/bonds/EDO0835/csv").await;
+        assert_ne!(res.text(), before);
+
+        let res = request.get("/bonds/EDO0835?as_of=2025-01-31").await;
+        assert_eq!(res.status_code(), 200);
+        res.assert_json(&bond_before);
+        let res = request.get("/bonds/EDO0835").await;
+        assert_ne!(res.json::<serde_json::Value>(), bond_before);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_upload_invalid_workbook() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .post("/admin/workbook")
+            .add_header("X-Admin-Key", "test-admin-key")
+            .multipart(workbook_form(b"not a workbook".to_vec()))
+            .await;
+        assert_eq!(res.status_code(), 422);
+        let report = res.json::<serde_json::Value>();
+        assert_eq!(report["activated"], json!(false));
+        assert_eq!(report["errors"].as_array().map(Vec::len), Some(1));
+        // Not a sheet nor a cell, the workbook as a whole is unreadable
+        assert_eq!(report["errors"][0]["severity"], json!("error"));
+        assert_eq!(report["errors"][0].get("sheet"), None);
+
+        // The previous workbook is still served
+        let res = request.get("/bonds?type=EDO").await;
+        res.assert_json(&json!(["EDO0732", "EDO0835", "EDO1014"]));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_upload_without_file() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .post("/admin/workbook")
+            .add_header("X-Admin-Key", "test-admin-key")
+            .multipart(MultipartForm::new().add_text("name", "workbook.xls"))
+            .await;
+        assert_eq!(res.status_code(), 400);
+        res.assert_json(&json!({
+            "error": "Missing multipart field [file]"
+        }));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_upload_workbook_larger_than_the_limit() {
+    request::<App, _, _>(|request, _ctx| async move {
+        // Above the default limit of axum, so it gets to the workbook validation
+        let res = request
+            .post("/admin/workbook")
+            .add_header("X-Admin-Key", "test-admin-key")
+            .multipart(workbook_form(vec![0; 3 * 1024 * 1024]))
+            .await;
+        assert_eq!(res.status_code(), 422);
+
+        let res = request
+            .post("/admin/workbook")
+            .add_header("X-Admin-Key", "test-admin-key")
+            .multipart(workbook_form(vec![0; 8 * 1024 * 1024 + 1]))
+            .await;
+        assert_eq!(res.status_code(), 413);
+    })
+    .await;
+}
@@ -0,0 +1,108 @@
+use super::auth::register_and_login;
+use super::settings::request_with_settings;
+use loco_rs::testing::prelude::*;
+use myapp::app::App;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use serial_test::serial;
+
+const API_KEY: &str = "test-api-key";
+
+/// Configures a single API key, allowing 3 requests per minute
+fn api_key_settings() -> serde_json::Value {
+    json!({
+        "api_keys": [{ "id": "tests", "key": API_KEY, "requests_per_minute": 3 }]
+    })
+}
+
+#[tokio::test]
+#[serial]
+async fn can_get_bonds_anonymously() {
+    request_with_settings(api_key_settings(), |request, _ctx| async move {
+        let res = request.get("/bonds?type=ROD").await;
+        assert_eq!(res.status_code(), 200);
+
+        let res = request.get("/bonds/EDO0835").await;
+        assert_eq!(res.status_code(), 200);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_use_accounts_with_token_only() {
+    request_with_settings(api_key_settings(), |request, _ctx| async move {
+        let login = register_and_login(&request, "ann@example.com").await;
+        let bearer = format!("Bearer {}", login["token"].as_str().unwrap());
+
+        let res = request
+            .get("/auth/current")
+            .add_header("Authorization", &bearer)
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        let res = request
+            .get("/portfolios")
+            .add_header("Authorization", &bearer)
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        // Paths merely starting like the open ones still need a key
+        let res = request.get("/portfolios-export").await;
+        assert_eq!(res.status_code(), 401);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_get_versions_without_api_key() {
+    request_with_settings(api_key_settings(), |request, _ctx| async move {
+        let res = request.get("/versions").await;
+        assert_eq!(res.status_code(), 401);
+        res.assert_json(&json!({ "error": "Missing API key" }));
+
+        let res = request
+            .get("/versions")
+            .add_header("X-API-Key", "wrong-key")
+            .await;
+        assert_eq!(res.status_code(), 401);
+        res.assert_json(&json!({ "error": "Invalid API key" }));
+
+        let res = request
+            .get("/versions")
+            .add_header("X-API-Key", API_KEY)
+            .await;
+        assert_eq!(res.status_code(), 200);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_exceed_quota_of_api_key() {
+    request_with_settings(api_key_settings(), |request, _ctx| async move {
+        // The test key allows 3 requests per minute, anonymous ones are not counted
+        request.get("/bonds").await;
+        for _ in 0..3 {
+            let res = request.get("/bonds").add_header("X-API-Key", API_KEY).await;
+            assert_eq!(res.status_code(), 200);
+        }
+
+        let res = request.get("/bonds").add_header("X-API-Key", API_KEY).await;
+        assert_eq!(res.status_code(), 429);
+        assert!(res.headers().contains_key("Retry-After"));
+        res.assert_json(&json!({ "error": "Quota of API key [tests] exceeded" }));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_get_versions_without_configured_api_keys() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/versions").await;
+        assert_eq!(res.status_code(), 200);
+    })
+    .await;
+}
@@ -0,0 +1,131 @@
+use axum_test::TestServer;
+use loco_rs::testing::prelude::*;
+use myapp::app::App;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use serial_test::serial;
+
+/// Registers a user and returns the response of logging in
+pub async fn register_and_login(request: &TestServer, email: &str) -> serde_json::Value {
+    let res = request
+        .post("/auth/register")
+        .json(&json!({ "email": email, "password": "correct horse", "name": "Ann" }))
+        .await;
+    assert_eq!(res.status_code(), 200);
+
+    let res = request
+        .post("/auth/login")
+        .json(&json!({ "email": email, "password": "correct horse" }))
+        .await;
+    assert_eq!(res.status_code(), 200);
+    res.json()
+}
+
+#[tokio::test]
+#[serial]
+async fn can_register_and_login() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .post("/auth/register")
+            .json(&json!({ "email": " Ann@Example.com ", "password": "correct horse", "name": "Ann" }))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let user = res.json::<serde_json::Value>();
+        assert_eq!(user["email"], "ann@example.com");
+        assert_eq!(user["name"], "Ann");
+
+        let res = request
+            .post("/auth/login")
+            .json(&json!({ "email": "ann@example.com", "password": "correct horse" }))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let login = res.json::<serde_json::Value>();
+        assert_eq!(login["pid"], user["pid"]);
+        assert_eq!(login["name"], "Ann");
+
+        let res = request
+            .get("/auth/current")
+            .add_header("Authorization", format!("Bearer {}", login["token"].as_str().unwrap()))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        res.assert_json(&user);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_register_twice() {
+    request::<App, _, _>(|request, _ctx| async move {
+        register_and_login(&request, "ann@example.com").await;
+
+        let res = request
+            .post("/auth/register")
+            .json(&json!({ "email": "ANN@example.com", "password": "another one", "name": "Ann" }))
+            .await;
+        assert_eq!(res.status_code(), 409);
+        res.assert_json(&json!({
+            "error": "Email [ANN@example.com] is already registered"
+        }));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_register_with_invalid_params() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .post("/auth/register")
+            .json(&json!({ "email": "ann", "password": "correct horse", "name": "Ann" }))
+            .await;
+        assert_eq!(res.status_code(), 400);
+        res.assert_json(&json!({ "error": "Invalid email [ann]" }));
+
+        // Too short password
+        let res = request
+            .post("/auth/register")
+            .json(&json!({ "email": "ann@example.com", "password": "short", "name": "Ann" }))
+            .await;
+        assert_eq!(res.status_code(), 400);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_login_with_wrong_password() {
+    request::<App, _, _>(|request, _ctx| async move {
+        register_and_login(&request, "ann@example.com").await;
+
+        let res = request
+            .post("/auth/login")
+            .json(&json!({ "email": "ann@example.com", "password": "wrong horse" }))
+            .await;
+        assert_eq!(res.status_code(), 401);
+        res.assert_json(&json!({ "error": "Invalid email or password" }));
+
+        let res = request
+            .post("/auth/login")
+            .json(&json!({ "email": "bob@example.com", "password": "correct horse" }))
+            .await;
+        assert_eq!(res.status_code(), 401);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_get_current_user_without_token() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/auth/current").await;
+        assert_eq!(res.status_code(), 401);
+
+        let res = request
+            .get("/auth/current")
+            .add_header("Authorization", "Bearer not-a-token")
+            .await;
+        assert_eq!(res.status_code(), 401);
+    })
+    .await;
+}
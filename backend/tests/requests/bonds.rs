@@ -18,6 +18,177 @@ async fn can_get_bonds() {
     .await;
 }
 
+#[tokio::test]
+#[serial]
+async fn can_filter_bonds_by_type() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/bonds?type=ROD").await;
+        assert_eq!(res.status_code(), 200);
+        res.assert_json(&json!(["ROD0832", "ROD0837", "ROD1028"]));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_filter_bonds_by_unknown_type() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/bonds?type=XYZ").await;
+        assert_eq!(res.status_code(), 400);
+        res.assert_json(&json!({
+            "error": "Unknown bond type [XYZ]"
+        }));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_get_bonds_on_sale() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/bonds?on_sale=2025-08-31").await;
+        assert_eq!(res.status_code(), 200);
+        res.assert_json(&json!(["EDO0835", "ROD0837"]));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_get_bonds_on_sale_in_month() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/bonds?sale_month=2022-07").await;
+        assert_eq!(res.status_code(), 200);
+        res.assert_json(&json!(["EDO0732"]));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_get_bonds_maturing_in_year() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .get("/bonds?maturity_from=2032-01-01&maturity_to=2032-12-31")
+            .await;
+        assert_eq!(res.status_code(), 200);
+        res.assert_json(&json!(["EDO0732", "ROD0832"]));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_paginate_bonds() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/bonds?page=2&per_page=4").await;
+        assert_eq!(res.status_code(), 200);
+        assert_eq!(res.header("x-total-count"), "6");
+        res.assert_json(&json!(["ROD0837", "ROD1028"]));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_get_bonds_with_invalid_sale_month() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/bonds?sale_month=2025-13").await;
+        assert_eq!(res.status_code(), 400);
+        res.assert_json(&json!({
+            "error": "Invalid month [2025-13], expected YYYY-MM: input is out of range"
+        }));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_get_bonds_as_of_date_without_versions() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/bonds?as_of=2025-01-01").await;
+        assert_eq!(res.status_code(), 404);
+        res.assert_json(&json!({
+            "error": "No dataset version published on or before [2025-01-01]"
+        }));
+
+        let res = request.get("/bonds/ROD0837/csv?as_of=2025-01-01").await;
+        assert_eq!(res.status_code(), 404);
+
+        let res = request.get("/bonds/ROD0837?as_of=2025-01-01").await;
+        assert_eq!(res.status_code(), 404);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_list_versions() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/versions").await;
+        assert_eq!(res.status_code(), 200);
+        res.assert_json(&json!([]));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_get_bond_with_provenance() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/bonds/EDO0835").await;
+        assert_eq!(res.status_code(), 200);
+        res.assert_json(&json!({
+            "id": "EDO0835",
+            "name": "EDO0835",
+            "initial_date": "2025-08-01",
+            "sale_end": "2025-08-31",
+            "buyout_date": "2035-08-01",
+            "nominal": 100.0,
+            "rates": [0.06, 0.0575],
+            "provenance": {
+                "series": "workbook",
+                "initial_date": "workbook",
+                "sale_end": "workbook",
+                "nominal": "workbook",
+                "rates": "corrections"
+            }
+        }));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_revalidate_bond_with_etag() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/bonds/EDO0835").await;
+        assert_eq!(res.status_code(), 200);
+        assert_eq!(res.header("Cache-Control"), "public, no-cache");
+        let etag = res.header("ETag");
+
+        let res = request
+            .get("/bonds/EDO0835")
+            .add_header("If-None-Match", etag)
+            .await;
+        assert_eq!(res.status_code(), 304);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_get_non_existing_bond() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/bonds/NONEXISTENT").await;
+        assert_eq!(res.status_code(), 404);
+        res.assert_json(&json!({
+            "error": "Bond with ID NONEXISTENT not found"
+        }));
+    })
+    .await;
+}
+
 #[tokio::test]
 #[serial]
 async fn can_get_existing_bond_csv() {
@@ -42,3 +213,238 @@ async fn can_get_non_existing_bond_csv() {
     })
     .await;
 }
+
+#[tokio::test]
+#[serial]
+async fn can_revalidate_bond_csv_with_etag() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/bonds/ROD0837/csv").await;
+        assert_eq!(res.status_code(), 200);
+        assert_eq!(res.header("Cache-Control"), "public, no-cache");
+        let etag = res.header("ETag");
+
+        let res = request
+            .get("/bonds/ROD0837/csv")
+            .add_header("If-None-Match", etag.clone())
+            .await;
+        assert_eq!(res.status_code(), 304);
+        assert_eq!(res.header("ETag"), etag);
+        assert_eq!(res.text(), "");
+
+        let res = request
+            .get("/bonds/ROD0837/csv")
+            .add_header("If-None-Match", "\"ROD0837-outdated\"")
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        let res = request
+            .get("/bonds/EDO0835/csv")
+            .add_header("If-None-Match", etag)
+            .await;
+        assert_eq!(res.status_code(), 200);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_revalidate_bond_csv_with_last_modified() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/bonds/ROD0837/csv").await;
+        assert_eq!(res.status_code(), 200);
+        let last_modified = res.header("Last-Modified");
+
+        let res = request
+            .get("/bonds/ROD0837/csv")
+            .add_header("If-Modified-Since", last_modified)
+            .await;
+        assert_eq!(res.status_code(), 304);
+
+        let res = request
+            .get("/bonds/ROD0837/csv")
+            .add_header("If-Modified-Since", "Mon, 01 Jan 2001 00:00:00 GMT")
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        // Invalid dates are ignored
+        let res = request
+            .get("/bonds/ROD0837/csv")
+            .add_header("If-Modified-Since", "yesterday")
+            .await;
+        assert_eq!(res.status_code(), 200);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_get_bond_values_in_requested_format() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/bonds/ROD0837/csv?format=json").await;
+        assert_eq!(res.status_code(), 200);
+        assert_eq!(res.header("Content-Type"), "application/json");
+        let values = res.json::<Vec<serde_json::Value>>();
+        assert_eq!(values[0], json!({ "date": "2025-08-01", "value": 100.0 }));
+
+        let res = request.get("/bonds/ROD0837/csv?format=ndjson").await;
+        assert_eq!(res.status_code(), 200);
+        assert_eq!(res.header("Content-Type"), "application/x-ndjson");
+        assert_eq!(res.text().lines().count(), values.len());
+
+        for (format, content_type, magic) in [
+            ("parquet", "application/vnd.apache.parquet", &b"PAR1"[..]),
+            ("arrow", "application/vnd.apache.arrow.file", b"ARROW1"),
+            (
+                "xlsx",
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+                b"PK",
+            ),
+        ] {
+            let res = request
+                .get(&format!("/bonds/ROD0837/csv?format={format}"))
+                .await;
+            assert_eq!(res.status_code(), 200);
+            assert_eq!(res.header("Content-Type"), content_type);
+            assert!(res.as_bytes().starts_with(magic), "{format}");
+        }
+
+        let res = request.get("/bonds/ROD0837/csv?format=xls").await;
+        assert_eq!(res.status_code(), 400);
+        res.assert_json(&json!({
+            "error": "Unknown format xls, expected one of csv, json, ndjson, parquet, arrow, xlsx"
+        }));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_negotiate_bond_values_format() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .get("/bonds/ROD0837/csv")
+            .add_header("Accept", "text/csv;q=0.5, application/vnd.apache.parquet")
+            .await;
+        assert_eq!(res.status_code(), 200);
+        assert_eq!(res.header("Content-Type"), "application/vnd.apache.parquet");
+        assert_eq!(res.header("Vary"), "accept");
+        let parquet_etag = res.header("ETag");
+
+        let res = request
+            .get("/bonds/ROD0837/csv")
+            .add_header("Accept", "*/*")
+            .await;
+        assert_eq!(res.header("Content-Type"), "text/csv");
+        assert_ne!(res.header("ETag"), parquet_etag);
+
+        // The parameter takes precedence over the header
+        let res = request
+            .get("/bonds/ROD0837/csv?format=json")
+            .add_header("Accept", "text/csv")
+            .await;
+        assert_eq!(res.header("Content-Type"), "application/json");
+
+        let res = request
+            .get("/bonds/ROD0837/csv")
+            .add_header("Accept", "text/html")
+            .await;
+        assert_eq!(res.status_code(), 406);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_get_bond_csv_in_polish_dialect() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let etag = request.get("/bonds/ROD0837/csv").await.header("ETag");
+
+        let res = request
+            .get(
+                "/bonds/ROD0837/csv?delimiter=;&decimal_separator=,&date_format=%25d.%25m.%25Y\
+                 &columns=date,value,period,accrued_interest&header=data,wartosc,okres,odsetki",
+            )
+            .await;
+        assert_eq!(res.status_code(), 200);
+        assert_ne!(res.header("ETag"), etag);
+        assert_eq!(
+            res.text().lines().take(4).collect::<Vec<_>>(),
+            vec![
+                "data;wartosc;okres;odsetki",
+                "01.08.2025;100;1;0",
+                "02.08.2025;100,02;1;0,02",
+                "03.08.2025;100,03;1;0,03",
+            ]
+        );
+
+        let res = request
+            .get("/bonds/ROD0837/csv?delimiter=,&decimal_separator=,")
+            .await;
+        assert_eq!(res.status_code(), 400);
+        res.assert_json(&json!({
+            "error": "Delimiter and decimal separator cannot both be ,"
+        }));
+
+        let res = request.get("/bonds/ROD0837/csv?columns=date,yield").await;
+        assert_eq!(res.status_code(), 400);
+        res.assert_json(&json!({
+            "error": "Unknown column yield, expected one of date, value, period, accrued_interest"
+        }));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_export_all_bonds_in_long_format() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/export").await;
+        assert_eq!(res.status_code(), 200);
+        assert_eq!(res.header("Content-Type"), "text/csv");
+        assert_eq!(
+            res.header("Content-Disposition"),
+            "attachment; filename=\"bonds.csv\""
+        );
+        let csv = res.text();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("bond_id,date,value"));
+        assert!(lines.next().unwrap().starts_with("EDO0732,"));
+        assert!(csv.lines().last().unwrap().starts_with("ROD1028,"));
+
+        let etag = res.header("ETag");
+        let res = request
+            .get("/export")
+            .add_header("If-None-Match", etag)
+            .await;
+        assert_eq!(res.status_code(), 304);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_export_all_bonds_as_zip() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/export?layout=zip&format=xlsx").await;
+        assert_eq!(res.status_code(), 200);
+        assert_eq!(res.header("Content-Type"), "application/zip");
+        assert_eq!(
+            res.header("Content-Disposition"),
+            "attachment; filename=\"bonds.zip\""
+        );
+        assert!(res.as_bytes().starts_with(b"PK\x03\x04"));
+
+        let res = request.get("/export?format=xlsx").await;
+        assert_eq!(res.status_code(), 400);
+        res.assert_json(&json!({
+            "error": "xlsx exports are only available with the zip layout"
+        }));
+
+        let res = request.get("/export?layout=wide").await;
+        assert_eq!(res.status_code(), 400);
+        res.assert_json(&json!({
+            "error": "Unknown layout wide, expected one of long, zip"
+        }));
+    })
+    .await;
+}
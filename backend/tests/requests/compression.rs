@@ -0,0 +1,121 @@
+use loco_rs::testing::prelude::*;
+use myapp::app::App;
+use pretty_assertions::assert_eq;
+use serial_test::serial;
+use std::io::Read;
+
+fn decode(encoding: &str, body: &[u8]) -> String {
+    let mut decoded = String::new();
+    match encoding {
+        "br" => brotli::Decompressor::new(body, 4096)
+            .read_to_string(&mut decoded)
+            .unwrap(),
+        "zstd" => zstd::Decoder::new(body)
+            .unwrap()
+            .read_to_string(&mut decoded)
+            .unwrap(),
+        "gzip" => flate2::read::GzDecoder::new(body)
+            .read_to_string(&mut decoded)
+            .unwrap(),
+        _ => panic!("Unexpected encoding {encoding}"),
+    };
+    decoded
+}
+
+#[tokio::test]
+#[serial]
+async fn can_get_compressed_bond_csv() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let csv = request.get("/bonds/ROD0837/csv").await.text();
+
+        for encoding in ["br", "zstd", "gzip"] {
+            let res = request
+                .get("/bonds/ROD0837/csv")
+                .add_header("Accept-Encoding", encoding)
+                .await;
+            assert_eq!(res.status_code(), 200);
+            assert_eq!(res.header("Content-Encoding"), encoding);
+            assert_eq!(res.header("Vary"), "accept-encoding");
+            assert_eq!(decode(encoding, res.as_bytes()), csv);
+
+            // Served from the kept payload while the version does not change
+            let cached = request
+                .get("/bonds/ROD0837/csv")
+                .add_header("Accept-Encoding", encoding)
+                .await;
+            assert_eq!(cached.status_code(), 200);
+            assert_eq!(cached.header("ETag"), res.header("ETag"));
+            assert_eq!(cached.as_bytes(), res.as_bytes());
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn does_not_compress_small_responses() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .get("/bonds/EDO0835")
+            .add_header("Accept-Encoding", "gzip")
+            .await;
+        assert_eq!(res.status_code(), 200);
+        assert_eq!(res.header("Vary"), "accept-encoding");
+        assert!(!res.headers().contains_key("Content-Encoding"));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn keeps_conditional_requests_working() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .get("/bonds/ROD0837/csv")
+            .add_header("Accept-Encoding", "br")
+            .await;
+        let etag = res.header("ETag");
+
+        let res = request
+            .get("/bonds/ROD0837/csv")
+            .add_header("Accept-Encoding", "br")
+            .add_header("If-None-Match", etag)
+            .await;
+        assert_eq!(res.status_code(), 304);
+        assert_eq!(res.as_bytes().len(), 0);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn does_not_compress_without_accepted_encoding() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .get("/bonds/ROD0837/csv")
+            .add_header("Accept-Encoding", "deflate")
+            .await;
+        assert_eq!(res.status_code(), 200);
+        assert!(!res.headers().contains_key("Content-Encoding"));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_get_compressed_export() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let csv = request.get("/export").await.text();
+
+        let res = request
+            .get("/export")
+            .add_header("Accept-Encoding", "zstd")
+            .await;
+        assert_eq!(res.status_code(), 200);
+        assert_eq!(res.header("Content-Encoding"), "zstd");
+        // Compressed while it is written
+        assert!(!res.headers().contains_key("Content-Length"));
+        assert_eq!(decode("zstd", res.as_bytes()), csv);
+    })
+    .await;
+}
@@ -1 +1,8 @@
+pub mod admin;
+pub mod api_keys;
+pub mod auth;
 pub mod bonds;
+pub mod compression;
+pub mod portfolios;
+pub mod settings;
+pub mod sqlite;
@@ -0,0 +1,204 @@
+use super::auth::register_and_login;
+use loco_rs::testing::prelude::*;
+use myapp::app::App;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use serial_test::serial;
+
+fn bearer(login: &serde_json::Value) -> String {
+    format!("Bearer {}", login["token"].as_str().unwrap())
+}
+
+fn savings() -> serde_json::Value {
+    json!({
+        "name": "Savings",
+        "lots": [
+            { "bond_id": "EDO0835", "quantity": 10, "purchase_date": "2025-08-01" },
+            { "bond_id": "EDO0835", "quantity": 2, "purchase_date": "2025-08-15" }
+        ]
+    })
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_use_portfolios_without_token() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/portfolios").await;
+        assert_eq!(res.status_code(), 401);
+
+        let res = request.post("/portfolios").json(&savings()).await;
+        assert_eq!(res.status_code(), 401);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_manage_portfolios() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let login = register_and_login(&request, "ann@example.com").await;
+
+        let res = request
+            .post("/portfolios")
+            .add_header("Authorization", bearer(&login))
+            .json(&savings())
+            .await;
+        assert_eq!(res.status_code(), 201);
+        let id = res.json::<serde_json::Value>()["id"].as_i64().unwrap();
+
+        let res = request
+            .get(&format!("/portfolios/{id}"))
+            .add_header("Authorization", bearer(&login))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let mut expected = savings();
+        expected["id"] = json!(id);
+        res.assert_json(&expected);
+
+        let renamed = json!({
+            "name": "Renamed",
+            "lots": [{ "bond_id": "ROD0837", "quantity": 1, "purchase_date": "2025-08-20" }]
+        });
+        let res = request
+            .put(&format!("/portfolios/{id}"))
+            .add_header("Authorization", bearer(&login))
+            .json(&renamed)
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        let res = request
+            .get("/portfolios")
+            .add_header("Authorization", bearer(&login))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let mut expected = renamed.clone();
+        expected["id"] = json!(id);
+        res.assert_json(&json!([expected]));
+
+        let res = request
+            .delete(&format!("/portfolios/{id}"))
+            .add_header("Authorization", bearer(&login))
+            .await;
+        assert_eq!(res.status_code(), 204);
+
+        let res = request
+            .get(&format!("/portfolios/{id}"))
+            .add_header("Authorization", bearer(&login))
+            .await;
+        assert_eq!(res.status_code(), 404);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_see_portfolios_of_other_users() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let ann = register_and_login(&request, "ann@example.com").await;
+        let bob = register_and_login(&request, "bob@example.com").await;
+
+        let res = request
+            .post("/portfolios")
+            .add_header("Authorization", bearer(&ann))
+            .json(&savings())
+            .await;
+        let id = res.json::<serde_json::Value>()["id"].as_i64().unwrap();
+
+        let res = request
+            .get("/portfolios")
+            .add_header("Authorization", bearer(&bob))
+            .await;
+        res.assert_json(&json!([]));
+
+        let res = request
+            .get(&format!("/portfolios/{id}"))
+            .add_header("Authorization", bearer(&bob))
+            .await;
+        assert_eq!(res.status_code(), 404);
+        res.assert_json(&json!({ "error": format!("Portfolio with ID {id} not found") }));
+
+        let res = request
+            .delete(&format!("/portfolios/{id}"))
+            .add_header("Authorization", bearer(&bob))
+            .await;
+        assert_eq!(res.status_code(), 404);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_save_lots_that_could_not_be_bought() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let login = register_and_login(&request, "ann@example.com").await;
+
+        let res = request
+            .post("/portfolios")
+            .add_header("Authorization", bearer(&login))
+            .json(&json!({
+                "name": "Savings",
+                "lots": [{ "bond_id": "EDO0835", "quantity": 1, "purchase_date": "2025-07-01" }]
+            }))
+            .await;
+        assert_eq!(res.status_code(), 400);
+        res.assert_json(&json!({
+            "error": "Bond [EDO0835] was on sale from 2025-08-01 to 2025-08-31, not on 2025-07-01"
+        }));
+
+        let res = request
+            .post("/portfolios")
+            .add_header("Authorization", bearer(&login))
+            .json(&json!({
+                "name": "Savings",
+                "lots": [{ "bond_id": "EDO9999", "quantity": 1, "purchase_date": "2025-08-01" }]
+            }))
+            .await;
+        assert_eq!(res.status_code(), 400);
+        res.assert_json(&json!({ "error": "Unknown bond [EDO9999]" }));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_value_portfolio() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let login = register_and_login(&request, "ann@example.com").await;
+        let res = request
+            .post("/portfolios")
+            .add_header("Authorization", bearer(&login))
+            .json(&savings())
+            .await;
+        let id = res.json::<serde_json::Value>()["id"].as_i64().unwrap();
+
+        let res = request
+            .get(&format!("/portfolios/{id}/value?date=2025-09-01"))
+            .add_header("Authorization", bearer(&login))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        res.assert_json(&json!({
+            "date": "2025-09-01",
+            "total": 1205.66,
+            "lots": [
+                { "bond_id": "EDO0835", "quantity": 10, "purchase_date": "2025-08-01", "value": 1005.1 },
+                { "bond_id": "EDO0835", "quantity": 2, "purchase_date": "2025-08-15", "value": 200.56 }
+            ]
+        }));
+
+        // The second lot was not bought yet
+        let res = request
+            .get(&format!("/portfolios/{id}/value?date=2025-08-10"))
+            .add_header("Authorization", bearer(&login))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        res.assert_json(&json!({
+            "date": "2025-08-10",
+            "total": 1001.5,
+            "lots": [
+                { "bond_id": "EDO0835", "quantity": 10, "purchase_date": "2025-08-01", "value": 1001.5 },
+                { "bond_id": "EDO0835", "quantity": 2, "purchase_date": "2025-08-15" }
+            ]
+        }));
+    })
+    .await;
+}
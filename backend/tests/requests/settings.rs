@@ -0,0 +1,37 @@
+use axum_test::{TestServer, TestServerConfig};
+use loco_rs::app::{AppContext, Hooks};
+use loco_rs::boot::StartMode;
+use loco_rs::environment::Environment;
+use myapp::app::App;
+
+/// Same as `request`, with the given settings replacing those of the test configuration
+pub async fn request_with_settings<F, Fut>(overrides: serde_json::Value, callback: F)
+where
+    F: FnOnce(TestServer, AppContext) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut config = App::load_config(&Environment::Test)
+        .await
+        .expect("Should load test configuration");
+    let mut settings = config.settings.take().unwrap_or_default();
+    let serde_json::Value::Object(overrides) = overrides else {
+        panic!("Settings overrides must be an object, got {overrides}");
+    };
+    for (key, value) in overrides {
+        settings[key] = value;
+    }
+    config.settings = Some(settings);
+
+    let boot = App::boot(StartMode::ServerOnly, &Environment::Test, config)
+        .await
+        .expect("Should boot the app");
+    let server = TestServer::new_with_config(
+        boot.router.expect("Should have a router"),
+        TestServerConfig {
+            default_content_type: Some("application/json".to_string()),
+            ..Default::default()
+        },
+    )
+    .expect("Should start the test server");
+    callback(server, boot.app_context).await;
+}
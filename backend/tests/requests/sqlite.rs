@@ -0,0 +1,191 @@
+#![cfg(feature = "sqlite")]
+
+use super::auth::register_and_login;
+use super::settings::request_with_settings;
+use axum_test::multipart::{MultipartForm, Part};
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use serial_test::serial;
+use tempfile::TempDir;
+
+const WORKBOOK: &str = "tests/fixtures/bonds/test.xls";
+
+/// Serves a copy of the test workbook from a fresh database, both kept in `directory`
+fn database_settings(directory: &TempDir) -> serde_json::Value {
+    let bonds_location = directory.path().join("bonds.xls");
+    std::fs::copy(WORKBOOK, &bonds_location).unwrap();
+    json!({
+        "bonds_location": bonds_location,
+        "database_url": format!("sqlite://{}", directory.path().join("bonds.sqlite").display()),
+    })
+}
+
+#[tokio::test]
+#[serial]
+async fn can_get_bonds_from_database() {
+    let directory = TempDir::new().unwrap();
+    request_with_settings(database_settings(&directory), |request, _ctx| async move {
+        let res = request.get("/bonds?type=ROD").await;
+        assert_eq!(res.status_code(), 200);
+        res.assert_json(&json!(["ROD0832", "ROD0837", "ROD1028"]));
+
+        let res = request.get("/bonds/EDO0835").await;
+        assert_eq!(res.status_code(), 200);
+        res.assert_json(&json!({
+            "id": "EDO0835",
+            "name": "EDO0835",
+            "initial_date": "2025-08-01",
+            "sale_end": "2025-08-31",
+            "buyout_date": "2035-08-01",
+            "nominal": 100.0,
+            "rates": [0.06, 0.0575],
+            "provenance": {
+                "series": "workbook",
+                "initial_date": "workbook",
+                "sale_end": "workbook",
+                "nominal": "workbook",
+                "rates": "corrections"
+            }
+        }));
+
+        let res = request.get("/bonds/NONEXISTENT").await;
+        assert_eq!(res.status_code(), 404);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_get_bonds_as_of_ingestion_date() {
+    let directory = TempDir::new().unwrap();
+    request_with_settings(database_settings(&directory), |request, _ctx| async move {
+        let today = chrono::Utc::now().date_naive();
+        let res = request.get(&format!("/bonds?as_of={today}")).await;
+        assert_eq!(res.status_code(), 200);
+        let res = request.get(&format!("/bonds/EDO0835?as_of={today}")).await;
+        assert_eq!(res.status_code(), 200);
+
+        let res = request.get("/bonds?as_of=2025-01-01").await;
+        assert_eq!(res.status_code(), 404);
+        res.assert_json(&json!({
+            "error": "No dataset version published on or before [2025-01-01]"
+        }));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn keeps_versions_across_restarts() {
+    let directory = TempDir::new().unwrap();
+    let versions = || {
+        request_with_settings(database_settings(&directory), |request, _ctx| async move {
+            let res = request.get("/versions").await;
+            assert_eq!(res.status_code(), 200);
+            let versions = res.json::<Vec<serde_json::Value>>();
+            assert_eq!(versions.len(), 1);
+        })
+    };
+    versions().await;
+    // Booting again with the same workbook serves the stored version
+    versions().await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_upload_workbook_into_database() {
+    let directory = TempDir::new().unwrap();
+    request_with_settings(database_settings(&directory), |request, _ctx| async move {
+        let form = MultipartForm::new().add_part(
+            "file",
+            Part::bytes(std::fs::read(WORKBOOK).unwrap()).file_name("workbook.xls"),
+        );
+        let res = request
+            .post("/admin/workbook")
+            .add_header("X-Admin-Key", "test-admin-key")
+            .multipart(form)
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        // Same content as the configured workbook, so no new version
+        let res = request.get("/versions").await;
+        assert_eq!(res.json::<Vec<serde_json::Value>>().len(), 1);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn keeps_etag_while_version_is_served() {
+    let directory = TempDir::new().unwrap();
+    request_with_settings(database_settings(&directory), |request, _ctx| async move {
+        let res = request.get("/bonds/ROD0837/csv").await;
+        assert_eq!(res.status_code(), 200);
+        let etag = res.header("ETag");
+
+        // Same content as the configured workbook, so the same version stays served
+        let form = MultipartForm::new().add_part(
+            "file",
+            Part::bytes(std::fs::read(WORKBOOK).unwrap()).file_name("workbook.xls"),
+        );
+        let res = request
+            .post("/admin/workbook")
+            .add_header("X-Admin-Key", "test-admin-key")
+            .multipart(form)
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        let res = request
+            .get("/bonds/ROD0837/csv")
+            .add_header("If-None-Match", etag)
+            .await;
+        assert_eq!(res.status_code(), 304);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn keeps_portfolios_across_restarts() {
+    let directory = TempDir::new().unwrap();
+    request_with_settings(database_settings(&directory), |request, _ctx| async move {
+        let login = register_and_login(&request, "ann@example.com").await;
+        let res = request
+            .post("/portfolios")
+            .add_header(
+                "Authorization",
+                format!("Bearer {}", login["token"].as_str().unwrap()),
+            )
+            .json(&json!({
+                "name": "Savings",
+                "lots": [{ "bond_id": "EDO0835", "quantity": 10, "purchase_date": "2025-08-01" }]
+            }))
+            .await;
+        assert_eq!(res.status_code(), 201);
+    })
+    .await;
+
+    request_with_settings(database_settings(&directory), |request, _ctx| async move {
+        let res = request
+            .post("/auth/login")
+            .json(&json!({ "email": "ann@example.com", "password": "correct horse" }))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let login = res.json::<serde_json::Value>();
+
+        let res = request
+            .get("/portfolios")
+            .add_header(
+                "Authorization",
+                format!("Bearer {}", login["token"].as_str().unwrap()),
+            )
+            .await;
+        assert_eq!(res.status_code(), 200);
+        res.assert_json(&json!([{
+            "id": 1,
+            "name": "Savings",
+            "lots": [{ "bond_id": "EDO0835", "quantity": 10, "purchase_date": "2025-08-01" }]
+        }]));
+    })
+    .await;
+}